@@ -0,0 +1,410 @@
+use acpi::error::Error;
+use acpi::{
+    AcpiTable, Disassemble, Linter, Severity, SnapshotMetadata, TableSnapshot, get_raw_table,
+    table_types,
+};
+use bytes::Bytes;
+use clap::{Parser, Subcommand};
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+const BYTES_PER_LINE: usize = 16;
+
+#[derive(Parser)]
+#[command(version, about = "Read ACPI tables of the running system")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List tables with their header fields and checksum status.
+    List,
+    /// Dump tables in acpidump hex format, or as raw binary.
+    Dump {
+        /// Table signature, e.g. `DSDT`. All tables are dumped if omitted.
+        signature: Option<String>,
+        /// Write the raw table instead of a hex dump.
+        #[arg(short, long, requires = "signature")]
+        binary: bool,
+        /// Write to a file instead of stdout.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Read the tables from a snapshot instead of the running system.
+        #[arg(short, long)]
+        snapshot: Option<PathBuf>,
+    },
+    /// Decode a table with its typed parser.
+    Decode {
+        /// Table signature, e.g. `MCFG`.
+        signature: String,
+        /// Print JSON instead of the annotated field layout.
+        #[arg(short, long)]
+        json: bool,
+    },
+    /// Write each table to a `.dat` file, as acpixtract does.
+    Extract {
+        /// Table signature, e.g. `SSDT`. All tables are extracted if omitted.
+        signature: Option<String>,
+        /// Directory to write the files into.
+        #[arg(short, long, default_value = ".")]
+        directory: PathBuf,
+    },
+    /// Save all tables and host details into a single archive file.
+    Snapshot {
+        /// Archive file to write.
+        output: PathBuf,
+    },
+    /// Compare two snapshots, or a snapshot with the running system.
+    Diff {
+        /// Snapshot taken before the change.
+        old: PathBuf,
+        /// Snapshot taken after the change. The running system is used if omitted.
+        new: Option<PathBuf>,
+        /// Print JSON instead of a summary.
+        #[arg(short, long)]
+        json: bool,
+    },
+    /// Check tables for specification violations.
+    Lint {
+        /// Snapshot to check. The running system is checked if omitted.
+        snapshot: Option<PathBuf>,
+        /// Rule to skip; may be repeated.
+        #[arg(short, long)]
+        disable: Vec<String>,
+        /// List the rules and exit.
+        #[arg(short, long)]
+        list: bool,
+        /// Print JSON instead of one finding per line.
+        #[arg(short, long)]
+        json: bool,
+    },
+}
+
+fn main() -> Result<(), Error> {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::List => list(),
+        Command::Dump {
+            signature,
+            binary,
+            output,
+            snapshot,
+        } => dump(signature.as_deref(), binary, output, snapshot),
+        Command::Decode { signature, json } => decode(&signature, json),
+        Command::Extract {
+            signature,
+            directory,
+        } => extract(signature.as_deref(), directory),
+        Command::Snapshot { output } => snapshot(output),
+        Command::Diff { old, new, json } => diff(old, new, json),
+        Command::Lint {
+            snapshot,
+            disable,
+            list,
+            json,
+        } => lint(snapshot, &disable, list, json),
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+fn list() -> Result<(), Error> {
+    println!(
+        "{:<8} {:>8} {:>3} {:<6} {:<8} {:>8} Checksum",
+        "Name", "Length", "Rev", "OEM ID", "Table ID", "OEM Rev"
+    );
+    for name in table_types()? {
+        let table = get_raw_table(&name)?;
+        let checksum = if table.is_checksum_valid() {
+            "OK"
+        } else {
+            "Invalid"
+        };
+        println!(
+            "{:<8} {:>8} {:>3} {:<6} {:<8} {:08X} {}",
+            name,
+            table.length,
+            table.revision,
+            table.oem_id,
+            table.oem_table_id,
+            table.oem_revision,
+            checksum
+        );
+    }
+
+    Ok(())
+}
+
+fn dump(
+    signature: Option<&str>,
+    binary: bool,
+    output: Option<PathBuf>,
+    snapshot: Option<PathBuf>,
+) -> Result<(), Error> {
+    // The OS does not say where a table lives, so only snapshots read from memory have addresses.
+    let tables = match snapshot {
+        Some(path) => {
+            let tables = TableSnapshot::load(path)?
+                .tables
+                .into_iter()
+                .filter(|t| signature.is_none_or(|s| t.signature == s))
+                .map(|t| (t.signature, t.address, t.data))
+                .collect::<Vec<_>>();
+            if let (Some(signature), true) = (signature, tables.is_empty()) {
+                let message = format!("{} table not found", signature);
+                return Err(Error::Io(io::Error::new(io::ErrorKind::NotFound, message)));
+            }
+            tables
+        }
+        None => select(signature)?
+            .iter()
+            .map(|name| {
+                let raw = get_raw_table(name)?;
+                Ok((raw.signature.clone(), None, Bytes::from(raw)))
+            })
+            .collect::<Result<Vec<_>, Error>>()?,
+    };
+
+    let mut out = vec![];
+    for (signature, address, table) in tables {
+        if binary {
+            out.extend_from_slice(&table);
+        } else {
+            out.extend_from_slice(hex_dump(&signature, address, &table).as_bytes());
+        }
+    }
+
+    match output {
+        Some(path) => fs::write(path, out)?,
+        None => io::stdout().write_all(&out)?,
+    }
+
+    Ok(())
+}
+
+fn decode(signature: &str, json: bool) -> Result<(), Error> {
+    for name in select(Some(signature))? {
+        let table = AcpiTable::from(get_raw_table(&name)?);
+        if json {
+            println!("{}", to_json(&table)?);
+        } else {
+            println!("{}", table.disassembly());
+        }
+    }
+
+    Ok(())
+}
+
+fn extract(signature: Option<&str>, directory: PathBuf) -> Result<(), Error> {
+    for name in select(signature)? {
+        let table = Bytes::from(get_raw_table(&name)?);
+        let path = directory.join(format!("{}.dat", name.to_lowercase()));
+        fs::write(&path, &table)?;
+        println!("{} - {} bytes written", path.display(), table.len());
+    }
+
+    Ok(())
+}
+
+fn snapshot(output: PathBuf) -> Result<(), Error> {
+    let snapshot = TableSnapshot::capture()?;
+    snapshot.save(&output)?;
+    println!(
+        "{} - {} tables written",
+        output.display(),
+        snapshot.tables.len()
+    );
+
+    Ok(())
+}
+
+fn diff(old: PathBuf, new: Option<PathBuf>, json: bool) -> Result<(), Error> {
+    let old = TableSnapshot::load(old)?;
+    let new = match new {
+        Some(path) => TableSnapshot::load(path)?,
+        None => TableSnapshot::capture()?,
+    };
+
+    let diffs = old.diff(&new);
+    if json {
+        println!("{}", to_json(&diffs)?);
+    } else {
+        println!("--- {}", describe(&old.metadata));
+        println!("+++ {}", describe(&new.metadata));
+        for diff in diffs {
+            print!("{}", diff);
+        }
+    }
+
+    Ok(())
+}
+
+// Exits with status 1 when an error is found, so it can gate CI jobs.
+fn lint(
+    snapshot: Option<PathBuf>,
+    disable: &[String],
+    list: bool,
+    json: bool,
+) -> Result<(), Error> {
+    let mut linter = Linter::default();
+    if list {
+        for (rule, _) in linter.rules() {
+            println!("{:<12} {}", rule.name(), rule.description());
+        }
+        return Ok(());
+    }
+
+    for name in disable {
+        if !linter.set_enabled(name, false) {
+            let message = format!("{} rule not found", name);
+            return Err(Error::Io(io::Error::new(io::ErrorKind::NotFound, message)));
+        }
+    }
+
+    let snapshot = match snapshot {
+        Some(path) => TableSnapshot::load(path)?,
+        None => TableSnapshot::capture()?,
+    };
+
+    let findings = linter.run(&snapshot);
+    if json {
+        println!("{}", to_json(&findings)?);
+    } else {
+        for finding in &findings {
+            println!("{}", finding);
+        }
+    }
+
+    if findings.iter().any(|f| f.severity == Severity::Error) {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------------------------
+
+fn describe(metadata: &SnapshotMetadata) -> String {
+    format!(
+        "{} ({}/{}) firmware {:?} captured at {}",
+        metadata.hostname,
+        metadata.os,
+        metadata.arch,
+        metadata.firmware_version,
+        metadata.captured_at
+    )
+}
+
+// Tables that appear more than once are numbered by the OS (`SSDT1`, `SSDT2`, ...).
+fn select(signature: Option<&str>) -> Result<Vec<String>, Error> {
+    let names = table_types()?;
+    let Some(signature) = signature else {
+        return Ok(names);
+    };
+
+    let selected = names
+        .into_iter()
+        .filter(|n| matches_signature(n, signature))
+        .collect::<Vec<String>>();
+    if selected.is_empty() {
+        let message = format!("{} table not found", signature);
+        return Err(Error::Io(io::Error::new(io::ErrorKind::NotFound, message)));
+    }
+
+    Ok(selected)
+}
+
+fn matches_signature(name: &str, signature: &str) -> bool {
+    match name.strip_prefix(signature) {
+        Some(rest) => rest.chars().all(|c| c.is_ascii_digit()),
+        None => false,
+    }
+}
+
+fn to_json<T>(value: &T) -> Result<String, Error>
+where
+    T: serde::Serialize,
+{
+    let text = serde_json::to_string_pretty(value).map_err(io::Error::from)?;
+    Ok(text)
+}
+
+// Same layout as `acpidump`, so the output can be fed to `acpixtract`. Unknown addresses are
+// written as zero because `acpixtract` needs the ` @ ` to find the header line.
+fn hex_dump(signature: &str, address: Option<u64>, table: &[u8]) -> String {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{} @ 0x{:016x}",
+        signature,
+        address.unwrap_or_default()
+    )
+    .unwrap();
+    for (i, line) in table.chunks(BYTES_PER_LINE).enumerate() {
+        let hex = line
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect::<Vec<String>>()
+            .join(" ");
+        let ascii = line
+            .iter()
+            .map(|b| {
+                if b.is_ascii_graphic() || *b == b' ' {
+                    *b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+        writeln!(
+            out,
+            "    {:04X}: {:<width$}  {}",
+            i * BYTES_PER_LINE,
+            hex,
+            ascii,
+            width = BYTES_PER_LINE * 3 - 1
+        )
+        .unwrap();
+    }
+    out.push('\n');
+    out
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_dump_layout() {
+        let table = b"MCFG\x3c\x00\x00\x00\x01\x7fFIRECKFCMV";
+        let out = hex_dump("MCFG", Some(0x7fff_0000), table);
+        let lines = out.lines().collect::<Vec<&str>>();
+        assert_eq!(lines[0], "MCFG @ 0x000000007fff0000");
+        assert_eq!(
+            lines[1],
+            "    0000: 4D 43 46 47 3C 00 00 00 01 7F 46 49 52 45 43 4B  MCFG<.....FIRECK"
+        );
+        assert_eq!(
+            lines[2],
+            "    0010: 46 43 4D 56                                      FCMV"
+        );
+        assert_eq!(lines[3], "");
+
+        let out = hex_dump("MCFG", None, table);
+        assert_eq!(out.lines().next(), Some("MCFG @ 0x0000000000000000"));
+    }
+
+    #[test]
+    fn signature_selection() {
+        assert!(matches_signature("DSDT", "DSDT"));
+        assert!(matches_signature("SSDT12", "SSDT"));
+        assert!(!matches_signature("SSDT", "DSDT"));
+        assert!(!matches_signature("FACS", "FAC"));
+    }
+}
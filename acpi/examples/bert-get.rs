@@ -0,0 +1,15 @@
+use acpi::error::Error;
+use acpi::{BootErrorRecord, Disassemble, get};
+
+fn main() -> Result<(), Error> {
+    let bert = get::<BootErrorRecord>("BERT")?;
+    println!("{}", bert.disassembly());
+
+    #[cfg(target_family = "unix")]
+    {
+        let region = acpi::get_boot_error_region()?;
+        println!("{:?}", &region);
+    }
+
+    Ok(())
+}
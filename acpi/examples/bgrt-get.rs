@@ -1,9 +1,24 @@
 use acpi::error::Error;
-use acpi::{BootGraphicsResource, get};
+use acpi::{BootGraphicsResource, Disassemble, get};
 
 fn main() -> Result<(), Error> {
     let bgrt = get::<BootGraphicsResource>("BGRT")?;
-    println!("{:?}", &bgrt);
+    println!("{}", bgrt.disassembly());
+
+    #[cfg(target_family = "unix")]
+    {
+        use acpi::Bitmap;
+
+        let image = Bitmap::try_from(acpi::get_boot_graphics_image()?)?;
+        println!(
+            "{}x{} {}bpp displayed={} orientation={}",
+            image.width(),
+            image.height(),
+            image.bits_per_pixel,
+            bgrt.status.displayed(),
+            bgrt.status.orientation()
+        );
+    }
 
     Ok(())
 }
@@ -0,0 +1,15 @@
+use acpi::error::Error;
+use acpi::{CxlEarlyDiscovery, Disassemble, get};
+
+fn main() -> Result<(), Error> {
+    let cedt = get::<CxlEarlyDiscovery>("CEDT")?;
+    println!("{}", cedt.disassembly());
+
+    for window in cedt.fixed_memory_windows() {
+        if let Some(target) = cedt.host_bridge_for(window.base_hpa) {
+            println!("{:#x}: host bridge {}", window.base_hpa, target.uid);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+use acpi::error::Error;
+use acpi::{Disassemble, ErrorInjection, get};
+
+fn main() -> Result<(), Error> {
+    let einj = get::<ErrorInjection>("EINJ")?;
+    println!("{}", einj.disassembly());
+
+    Ok(())
+}
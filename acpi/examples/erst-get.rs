@@ -0,0 +1,9 @@
+use acpi::error::Error;
+use acpi::{Disassemble, ErrorRecordSerialization, get};
+
+fn main() -> Result<(), Error> {
+    let erst = get::<ErrorRecordSerialization>("ERST")?;
+    println!("{}", erst.disassembly());
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use acpi::error::Error;
+use acpi::{Disassemble, FirmwarePerformanceData, get};
+
+fn main() -> Result<(), Error> {
+    let fpdt = get::<FirmwarePerformanceData>("FPDT")?;
+    println!("{}", fpdt.disassembly());
+
+    #[cfg(target_family = "unix")]
+    {
+        let timeline = acpi::get_boot_timeline()?;
+        for (name, ns) in timeline.events() {
+            println!("{name}: {ns} ns");
+        }
+
+        if let Some(count) = timeline.resume_count {
+            println!("ResumeCount: {count}");
+        }
+        for (name, ns) in timeline.s3_events() {
+            println!("{name}: {ns} ns");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+use acpi::error::Error;
+use acpi::{Disassemble, GenericTimerDescription, get};
+
+fn main() -> Result<(), Error> {
+    let gtdt = get::<GenericTimerDescription>("GTDT")?;
+    println!("{}", gtdt.disassembly());
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+use acpi::error::Error;
+use acpi::{Disassemble, HighPrecisionEventTimer, get};
+
+fn main() -> Result<(), Error> {
+    let hpet = get::<HighPrecisionEventTimer>("HPET")?;
+    println!("{}", hpet.disassembly());
+
+    Ok(())
+}
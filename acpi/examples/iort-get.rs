@@ -0,0 +1,9 @@
+use acpi::error::Error;
+use acpi::{Disassemble, IoRemapping, get};
+
+fn main() -> Result<(), Error> {
+    let iort = get::<IoRemapping>("IORT")?;
+    println!("{}", iort.disassembly());
+
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use acpi::error::Error;
+use acpi::{Disassemble, LowPowerIdle, get, get_raw_table};
+
+fn main() -> Result<(), Error> {
+    let lpit = get::<LowPowerIdle>("LPIT")?;
+    println!("{}", lpit.disassembly());
+
+    let fadt = get_raw_table("FACP").ok();
+    println!("{:?}", lpit.summary(fadt.as_ref()));
+
+    Ok(())
+}
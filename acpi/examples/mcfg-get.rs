@@ -1,9 +1,26 @@
 use acpi::error::Error;
-use acpi::{MemoryMappedConfiguration, get};
+use acpi::{Disassemble, MemoryMappedConfiguration, get};
 
 fn main() -> Result<(), Error> {
     let mcfg = get::<MemoryMappedConfiguration>("MCFG")?;
-    println!("{:?}", &mcfg);
+    println!("{}", mcfg.disassembly());
+
+    for issue in mcfg.validate() {
+        println!("{:?}", issue);
+    }
+
+    if let Some(address) = mcfg.ecam_address(0, 0, 0, 0, 0) {
+        println!("00:00.0 ECAM: {:#x}", address);
+    }
+
+    #[cfg(target_family = "unix")]
+    {
+        use acpi::{PciConfigReader, SysfsPciConfig};
+
+        let mut id = [0u8; 4];
+        SysfsPciConfig.read_config(0, 0, 0, 0, 0, &mut id)?;
+        println!("00:00.0 ID: {:08x}", u32::from_le_bytes(id));
+    }
 
     Ok(())
 }
@@ -0,0 +1,22 @@
+use acpi::error::Error;
+use acpi::{Disassemble, NvdimmFirmwareInterface, get};
+
+fn main() -> Result<(), Error> {
+    let nfit = get::<NvdimmFirmwareInterface>("NFIT")?;
+    println!("{}", nfit.disassembly());
+
+    for spa in nfit.spa_ranges() {
+        println!(
+            "SPA {}: {} {:#x}+{:#x}",
+            spa.spa_range_index,
+            spa.address_range_type.name(),
+            spa.base_address,
+            spa.length
+        );
+        for region in nfit.regions(spa.spa_range_index) {
+            println!("  {:?}", region.mapping);
+        }
+    }
+
+    Ok(())
+}
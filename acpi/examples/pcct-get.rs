@@ -0,0 +1,15 @@
+use acpi::error::Error;
+use acpi::{Disassemble, PlatformCommunicationsChannel, get};
+
+fn main() -> Result<(), Error> {
+    let pcct = get::<PlatformCommunicationsChannel>("PCCT")?;
+    println!("{}", pcct.disassembly());
+
+    for (id, subspace) in pcct.subspaces.iter().enumerate() {
+        if let Some((base, length)) = subspace.shared_memory() {
+            println!("subspace {}: {:#x}+{:#x}", id, base, length);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use acpi::error::Error;
+use acpi::{Disassemble, ProcessorPropertiesTopology, get};
+
+fn main() -> Result<(), Error> {
+    let pptt = get::<ProcessorPropertiesTopology>("PPTT")?;
+    println!("{}", pptt.disassembly());
+
+    for id in pptt.leaf_processor_ids() {
+        for cache in pptt.caches(id) {
+            println!(
+                "cpu {}: L{} {:?} {:?} bytes",
+                id,
+                cache.level,
+                cache.cache.cache_type(),
+                cache.cache.size()
+            );
+        }
+    }
+
+    Ok(())
+}
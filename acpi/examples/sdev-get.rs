@@ -0,0 +1,9 @@
+use acpi::error::Error;
+use acpi::{Disassemble, SecureDevices, get};
+
+fn main() -> Result<(), Error> {
+    let sdev = get::<SecureDevices>("SDEV")?;
+    println!("{}", sdev.disassembly());
+
+    Ok(())
+}
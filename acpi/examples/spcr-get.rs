@@ -0,0 +1,13 @@
+use acpi::error::Error;
+use acpi::{Disassemble, SerialPortConsoleRedirection, get};
+
+fn main() -> Result<(), Error> {
+    let spcr = get::<SerialPortConsoleRedirection>("SPCR")?;
+    println!("{}", spcr.disassembly());
+
+    if let Some(options) = spcr.console_options() {
+        println!("earlycon={}", options);
+    }
+
+    Ok(())
+}
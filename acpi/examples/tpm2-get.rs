@@ -0,0 +1,9 @@
+use acpi::error::Error;
+use acpi::{Disassemble, TrustedPlatformModule2, get};
+
+fn main() -> Result<(), Error> {
+    let tpm2 = get::<TrustedPlatformModule2>("TPM2")?;
+    println!("{}", tpm2.disassembly());
+
+    Ok(())
+}
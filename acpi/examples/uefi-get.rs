@@ -1,9 +1,9 @@
 use acpi::error::Error;
-use acpi::{SystemManagementModeCommunication, get};
+use acpi::{Disassemble, SystemManagementModeCommunication, get};
 
 fn main() -> Result<(), Error> {
     let uefi = get::<SystemManagementModeCommunication>("UEFI")?;
-    println!("{:?}", &uefi);
+    println!("{}", uefi.disassembly());
 
     Ok(())
 }
@@ -0,0 +1,9 @@
+use acpi::error::Error;
+use acpi::{Disassemble, WindowsSmmSecurityMitigations, get};
+
+fn main() -> Result<(), Error> {
+    let wsmt = get::<WindowsSmmSecurityMitigations>("WSMT")?;
+    println!("{}", wsmt.disassembly());
+
+    Ok(())
+}
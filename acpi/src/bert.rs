@@ -0,0 +1,1045 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::error::Error;
+use super::guid::Guid;
+use super::{RawAcpiData, extract_string, string_to_array};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://uefi.org/specs/ACPI/6.5/18_Platform_Error_Interfaces.html#boot-error-source
+// https://uefi.org/specs/UEFI/2.10/Apx_N_Common_Platform_Error_Record.html
+
+pub const PROCESSOR_GENERIC_ERROR_SECTION: Guid = Guid::new(
+    0x9876ccad,
+    0x47b4,
+    0x4bdb,
+    [0xb6, 0x5e, 0x16, 0xf1, 0x93, 0xc4, 0xf3, 0xdb],
+);
+pub const MEMORY_ERROR_SECTION: Guid = Guid::new(
+    0xa5bc1114,
+    0x6f64,
+    0x4ede,
+    [0xb8, 0x63, 0x3e, 0x83, 0xed, 0x7c, 0x83, 0xb1],
+);
+pub const PCI_EXPRESS_ERROR_SECTION: Guid = Guid::new(
+    0xd995e954,
+    0xbbc1,
+    0x430f,
+    [0xad, 0x91, 0xb4, 0x4d, 0xcb, 0x3c, 0x6f, 0x35],
+);
+pub const ARM_PROCESSOR_ERROR_SECTION: Guid = Guid::new(
+    0xe19e3d16,
+    0xbc11,
+    0x11e4,
+    [0x9c, 0xaa, 0xc2, 0x05, 0x1d, 0x5d, 0x46, 0xb0],
+);
+pub const FIRMWARE_ERROR_RECORD_REFERENCE_SECTION: Guid = Guid::new(
+    0x81212a96,
+    0x09ed,
+    0x4996,
+    [0x94, 0x71, 0x8d, 0x72, 0x9c, 0x8e, 0x69, 0xed],
+);
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BootErrorRecord {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub boot_error_region_length: u32,
+    pub boot_error_region: u64,
+}
+
+impl From<RawAcpiData> for BootErrorRecord {
+    fn from(mut data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+        let boot_error_region_length = data.acpi_table_data.get_u32_le();
+        let boot_error_region = data.acpi_table_data.get_u64_le();
+
+        BootErrorRecord {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            boot_error_region_length,
+            boot_error_region,
+        }
+    }
+}
+
+impl From<BootErrorRecord> for Bytes {
+    fn from(val: BootErrorRecord) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        b.put_u32_le(val.boot_error_region_length);
+        b.put_u64_le(val.boot_error_region);
+        b.freeze()
+    }
+}
+
+impl Disassemble for BootErrorRecord {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Boot Error Record Table");
+        w.u32("Boot Error Region Length", self.boot_error_region_length);
+        w.u64("Boot Error Region Address", self.boot_error_region);
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErrorSeverity {
+    #[default]
+    Recoverable,
+    Fatal,
+    Corrected,
+    Informational,
+    Reserved(u32),
+}
+
+impl From<u32> for ErrorSeverity {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => ErrorSeverity::Recoverable,
+            1 => ErrorSeverity::Fatal,
+            2 => ErrorSeverity::Corrected,
+            3 => ErrorSeverity::Informational,
+            v => ErrorSeverity::Reserved(v),
+        }
+    }
+}
+
+impl From<ErrorSeverity> for u32 {
+    fn from(val: ErrorSeverity) -> Self {
+        match val {
+            ErrorSeverity::Recoverable => 0,
+            ErrorSeverity::Fatal => 1,
+            ErrorSeverity::Corrected => 2,
+            ErrorSeverity::Informational => 3,
+            ErrorSeverity::Reserved(v) => v,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenericErrorStatusBlock {
+    pub block_status: u32,
+    pub raw_data_offset: u32,
+    pub raw_data_length: u32,
+    pub data_length: u32,
+    pub error_severity: ErrorSeverity,
+    pub entries: Vec<GenericErrorDataEntry>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub raw_data: Bytes,
+}
+
+impl GenericErrorStatusBlock {
+    pub fn uncorrectable_error_valid(&self) -> bool {
+        self.block_status & 0x01 != 0
+    }
+
+    pub fn correctable_error_valid(&self) -> bool {
+        self.block_status & 0x02 != 0
+    }
+
+    pub fn multiple_uncorrectable_errors(&self) -> bool {
+        self.block_status & 0x04 != 0
+    }
+
+    pub fn multiple_correctable_errors(&self) -> bool {
+        self.block_status & 0x08 != 0
+    }
+
+    pub fn error_data_entry_count(&self) -> u16 {
+        ((self.block_status >> 4) & 0x3ff) as u16
+    }
+}
+
+impl GenericErrorStatusBlock {
+    const HEADER_LENGTH: usize = 20;
+}
+
+impl TryFrom<Bytes> for GenericErrorStatusBlock {
+    type Error = Error;
+
+    fn try_from(mut buf: Bytes) -> Result<Self, Self::Error> {
+        if buf.len() < GenericErrorStatusBlock::HEADER_LENGTH {
+            return Err(Error::InvalidTable);
+        }
+
+        let region = buf.clone();
+        let block_status = buf.get_u32_le();
+        let raw_data_offset = buf.get_u32_le();
+        let raw_data_length = buf.get_u32_le();
+        let data_length = buf.get_u32_le();
+        let error_severity = ErrorSeverity::from(buf.get_u32_le());
+
+        let mut data = buf.split_to((data_length as usize).min(buf.len()));
+        let mut entries = vec![];
+        while data.len() >= GenericErrorDataEntry::HEADER_LENGTH {
+            entries.push(GenericErrorDataEntry::from(&mut data));
+        }
+
+        let raw_data = if raw_data_length > 0 {
+            let start = (raw_data_offset as usize).min(region.len());
+            let end = (start + raw_data_length as usize).min(region.len());
+            region.slice(start..end)
+        } else {
+            Bytes::new()
+        };
+
+        Ok(GenericErrorStatusBlock {
+            block_status,
+            raw_data_offset,
+            raw_data_length,
+            data_length,
+            error_severity,
+            entries,
+            raw_data,
+        })
+    }
+}
+
+impl From<GenericErrorStatusBlock> for Bytes {
+    fn from(val: GenericErrorStatusBlock) -> Self {
+        let mut b = BytesMut::new();
+        b.put_u32_le(val.block_status);
+        b.put_u32_le(val.raw_data_offset);
+        b.put_u32_le(val.raw_data_length);
+        b.put_u32_le(val.data_length);
+        b.put_u32_le(val.error_severity.into());
+        for entry in val.entries {
+            b.put(Bytes::from(entry));
+        }
+        if !val.raw_data.is_empty() {
+            if b.len() < val.raw_data_offset as usize {
+                b.resize(val.raw_data_offset as usize, 0);
+            }
+            b.put(val.raw_data);
+        }
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenericErrorDataEntry {
+    pub section_type: Guid,
+    pub error_severity: ErrorSeverity,
+    pub revision: u16,
+    pub validation_bits: u8,
+    pub flags: u8,
+    pub error_data_length: u32,
+    pub fru_id: Guid,
+    pub fru_text: String,
+    pub timestamp: Option<u64>,
+    pub section: ErrorSection,
+}
+
+impl GenericErrorDataEntry {
+    const HEADER_LENGTH: usize = 64;
+
+    pub fn fru_id_valid(&self) -> bool {
+        self.validation_bits & 0x01 != 0
+    }
+
+    pub fn fru_text_valid(&self) -> bool {
+        self.validation_bits & 0x02 != 0
+    }
+
+    pub fn timestamp_valid(&self) -> bool {
+        self.validation_bits & 0x04 != 0
+    }
+}
+
+impl From<&mut Bytes> for GenericErrorDataEntry {
+    fn from(buf: &mut Bytes) -> Self {
+        let section_type = Guid(buf.split_to(16)[..].try_into().unwrap());
+        let error_severity = ErrorSeverity::from(buf.get_u32_le());
+        let revision = buf.get_u16_le();
+        let validation_bits = buf.get_u8();
+        let flags = buf.get_u8();
+        let error_data_length = buf.get_u32_le();
+        let fru_id = Guid(buf.split_to(16)[..].try_into().unwrap());
+        let fru_text = extract_string::<20>(buf);
+        let timestamp = if revision >= 0x0300 && buf.len() >= 8 {
+            Some(buf.get_u64_le())
+        } else {
+            None
+        };
+        let data = buf.split_to((error_data_length as usize).min(buf.len()));
+        let section = ErrorSection::new(&section_type, data);
+
+        GenericErrorDataEntry {
+            section_type,
+            error_severity,
+            revision,
+            validation_bits,
+            flags,
+            error_data_length,
+            fru_id,
+            fru_text,
+            timestamp,
+            section,
+        }
+    }
+}
+
+impl From<GenericErrorDataEntry> for Bytes {
+    fn from(val: GenericErrorDataEntry) -> Self {
+        let fru_text = string_to_array::<20>(&val.fru_text);
+
+        let mut b = BytesMut::new();
+        b.put_slice(&val.section_type.0);
+        b.put_u32_le(val.error_severity.into());
+        b.put_u16_le(val.revision);
+        b.put_u8(val.validation_bits);
+        b.put_u8(val.flags);
+        b.put_u32_le(val.error_data_length);
+        b.put_slice(&val.fru_id.0);
+        b.put_slice(&fru_text);
+        if let Some(v) = val.timestamp {
+            b.put_u64_le(v);
+        }
+        b.put(Bytes::from(val.section));
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErrorSection {
+    ProcessorGeneric(ProcessorGenericError),
+    Memory(MemoryError),
+    PciExpress(PciExpressError),
+    ArmProcessor(ArmProcessorError),
+    FirmwareErrorRecordReference(FirmwareErrorRecordReference),
+    Unknown(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Bytes),
+}
+
+impl Default for ErrorSection {
+    fn default() -> Self {
+        ErrorSection::Unknown(Bytes::new())
+    }
+}
+
+impl ErrorSection {
+    pub fn new(section_type: &Guid, data: Bytes) -> Self {
+        match *section_type {
+            PROCESSOR_GENERIC_ERROR_SECTION if data.len() >= ProcessorGenericError::LENGTH => {
+                ErrorSection::ProcessorGeneric(ProcessorGenericError::from(data))
+            }
+            MEMORY_ERROR_SECTION if data.len() >= MemoryError::LENGTH => {
+                ErrorSection::Memory(MemoryError::from(data))
+            }
+            PCI_EXPRESS_ERROR_SECTION if data.len() >= PciExpressError::LENGTH => {
+                ErrorSection::PciExpress(PciExpressError::from(data))
+            }
+            ARM_PROCESSOR_ERROR_SECTION if data.len() >= ArmProcessorError::LENGTH => {
+                ErrorSection::ArmProcessor(ArmProcessorError::from(data))
+            }
+            FIRMWARE_ERROR_RECORD_REFERENCE_SECTION
+                if data.len() >= FirmwareErrorRecordReference::LENGTH =>
+            {
+                ErrorSection::FirmwareErrorRecordReference(FirmwareErrorRecordReference::from(data))
+            }
+            _ => ErrorSection::Unknown(data),
+        }
+    }
+}
+
+impl From<ErrorSection> for Bytes {
+    fn from(val: ErrorSection) -> Self {
+        match val {
+            ErrorSection::ProcessorGeneric(v) => Bytes::from(v),
+            ErrorSection::Memory(v) => Bytes::from(v),
+            ErrorSection::PciExpress(v) => Bytes::from(v),
+            ErrorSection::ArmProcessor(v) => Bytes::from(v),
+            ErrorSection::FirmwareErrorRecordReference(v) => Bytes::from(v),
+            ErrorSection::Unknown(v) => v,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessorGenericError {
+    pub validation_bits: u64,
+    pub processor_type: u8,
+    pub processor_isa: u8,
+    pub processor_error_type: u8,
+    pub operation: u8,
+    pub flags: u8,
+    pub level: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 2],
+    pub cpu_version_info: u64,
+    pub cpu_brand_string: String,
+    pub processor_id: u64,
+    pub target_address: u64,
+    pub requestor_id: u64,
+    pub responder_id: u64,
+    pub instruction_ip: u64,
+}
+
+impl ProcessorGenericError {
+    const LENGTH: usize = 192;
+}
+
+impl From<Bytes> for ProcessorGenericError {
+    fn from(mut buf: Bytes) -> Self {
+        let validation_bits = buf.get_u64_le();
+        let processor_type = buf.get_u8();
+        let processor_isa = buf.get_u8();
+        let processor_error_type = buf.get_u8();
+        let operation = buf.get_u8();
+        let flags = buf.get_u8();
+        let level = buf.get_u8();
+        let reserved = buf.split_to(2)[..].try_into().unwrap();
+        let cpu_version_info = buf.get_u64_le();
+        let cpu_brand_string = extract_string::<128>(&mut buf);
+        let processor_id = buf.get_u64_le();
+        let target_address = buf.get_u64_le();
+        let requestor_id = buf.get_u64_le();
+        let responder_id = buf.get_u64_le();
+        let instruction_ip = buf.get_u64_le();
+
+        ProcessorGenericError {
+            validation_bits,
+            processor_type,
+            processor_isa,
+            processor_error_type,
+            operation,
+            flags,
+            level,
+            reserved,
+            cpu_version_info,
+            cpu_brand_string,
+            processor_id,
+            target_address,
+            requestor_id,
+            responder_id,
+            instruction_ip,
+        }
+    }
+}
+
+impl From<ProcessorGenericError> for Bytes {
+    fn from(val: ProcessorGenericError) -> Self {
+        let cpu_brand_string = string_to_array::<128>(&val.cpu_brand_string);
+
+        let mut b = BytesMut::with_capacity(ProcessorGenericError::LENGTH);
+        b.put_u64_le(val.validation_bits);
+        b.put_u8(val.processor_type);
+        b.put_u8(val.processor_isa);
+        b.put_u8(val.processor_error_type);
+        b.put_u8(val.operation);
+        b.put_u8(val.flags);
+        b.put_u8(val.level);
+        b.put_slice(&val.reserved);
+        b.put_u64_le(val.cpu_version_info);
+        b.put_slice(&cpu_brand_string);
+        b.put_u64_le(val.processor_id);
+        b.put_u64_le(val.target_address);
+        b.put_u64_le(val.requestor_id);
+        b.put_u64_le(val.responder_id);
+        b.put_u64_le(val.instruction_ip);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryError {
+    pub validation_bits: u64,
+    pub error_status: u64,
+    pub physical_address: u64,
+    pub physical_address_mask: u64,
+    pub node: u16,
+    pub card: u16,
+    pub module: u16,
+    pub bank: u16,
+    pub device: u16,
+    pub row: u16,
+    pub column: u16,
+    pub bit_position: u16,
+    pub requestor_id: u64,
+    pub responder_id: u64,
+    pub target_id: u64,
+    pub memory_error_type: u8,
+    pub extended: u8,
+    pub rank_number: u16,
+    pub card_handle: u16,
+    pub module_handle: u16,
+}
+
+impl MemoryError {
+    const LENGTH: usize = 80;
+}
+
+impl From<Bytes> for MemoryError {
+    fn from(mut buf: Bytes) -> Self {
+        let validation_bits = buf.get_u64_le();
+        let error_status = buf.get_u64_le();
+        let physical_address = buf.get_u64_le();
+        let physical_address_mask = buf.get_u64_le();
+        let node = buf.get_u16_le();
+        let card = buf.get_u16_le();
+        let module = buf.get_u16_le();
+        let bank = buf.get_u16_le();
+        let device = buf.get_u16_le();
+        let row = buf.get_u16_le();
+        let column = buf.get_u16_le();
+        let bit_position = buf.get_u16_le();
+        let requestor_id = buf.get_u64_le();
+        let responder_id = buf.get_u64_le();
+        let target_id = buf.get_u64_le();
+        let memory_error_type = buf.get_u8();
+        let extended = buf.get_u8();
+        let rank_number = buf.get_u16_le();
+        let card_handle = buf.get_u16_le();
+        let module_handle = buf.get_u16_le();
+
+        MemoryError {
+            validation_bits,
+            error_status,
+            physical_address,
+            physical_address_mask,
+            node,
+            card,
+            module,
+            bank,
+            device,
+            row,
+            column,
+            bit_position,
+            requestor_id,
+            responder_id,
+            target_id,
+            memory_error_type,
+            extended,
+            rank_number,
+            card_handle,
+            module_handle,
+        }
+    }
+}
+
+impl From<MemoryError> for Bytes {
+    fn from(val: MemoryError) -> Self {
+        let mut b = BytesMut::with_capacity(MemoryError::LENGTH);
+        b.put_u64_le(val.validation_bits);
+        b.put_u64_le(val.error_status);
+        b.put_u64_le(val.physical_address);
+        b.put_u64_le(val.physical_address_mask);
+        b.put_u16_le(val.node);
+        b.put_u16_le(val.card);
+        b.put_u16_le(val.module);
+        b.put_u16_le(val.bank);
+        b.put_u16_le(val.device);
+        b.put_u16_le(val.row);
+        b.put_u16_le(val.column);
+        b.put_u16_le(val.bit_position);
+        b.put_u64_le(val.requestor_id);
+        b.put_u64_le(val.responder_id);
+        b.put_u64_le(val.target_id);
+        b.put_u8(val.memory_error_type);
+        b.put_u8(val.extended);
+        b.put_u16_le(val.rank_number);
+        b.put_u16_le(val.card_handle);
+        b.put_u16_le(val.module_handle);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PciExpressError {
+    pub validation_bits: u64,
+    pub port_type: u32,
+    pub version_minor: u8,
+    pub version_major: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved1: [u8; 2],
+    pub command: u16,
+    pub status: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved2: [u8; 4],
+    pub vendor_id: u16,
+    pub device_id: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub class_code: [u8; 3],
+    pub function_number: u8,
+    pub device_number: u8,
+    pub segment_number: u16,
+    pub primary_bus_number: u8,
+    pub secondary_bus_number: u8,
+    pub slot_number: u16,
+    pub reserved3: u8,
+    pub device_serial_number: u64,
+    pub secondary_status: u16,
+    pub bridge_control: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub capability_structure: Bytes,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub aer_info: Bytes,
+}
+
+impl PciExpressError {
+    const LENGTH: usize = 208;
+
+    pub fn slot(&self) -> u16 {
+        self.slot_number >> 3
+    }
+}
+
+impl From<Bytes> for PciExpressError {
+    fn from(mut buf: Bytes) -> Self {
+        let validation_bits = buf.get_u64_le();
+        let port_type = buf.get_u32_le();
+        let version_minor = buf.get_u8();
+        let version_major = buf.get_u8();
+        let reserved1 = buf.split_to(2)[..].try_into().unwrap();
+        let command = buf.get_u16_le();
+        let status = buf.get_u16_le();
+        let reserved2 = buf.split_to(4)[..].try_into().unwrap();
+        let vendor_id = buf.get_u16_le();
+        let device_id = buf.get_u16_le();
+        let class_code = buf.split_to(3)[..].try_into().unwrap();
+        let function_number = buf.get_u8();
+        let device_number = buf.get_u8();
+        let segment_number = buf.get_u16_le();
+        let primary_bus_number = buf.get_u8();
+        let secondary_bus_number = buf.get_u8();
+        let slot_number = buf.get_u16_le();
+        let reserved3 = buf.get_u8();
+        let device_serial_number = buf.get_u64_le();
+        let secondary_status = buf.get_u16_le();
+        let bridge_control = buf.get_u16_le();
+        let capability_structure = buf.split_to(60);
+        let aer_info = buf.split_to(96);
+
+        PciExpressError {
+            validation_bits,
+            port_type,
+            version_minor,
+            version_major,
+            reserved1,
+            command,
+            status,
+            reserved2,
+            vendor_id,
+            device_id,
+            class_code,
+            function_number,
+            device_number,
+            segment_number,
+            primary_bus_number,
+            secondary_bus_number,
+            slot_number,
+            reserved3,
+            device_serial_number,
+            secondary_status,
+            bridge_control,
+            capability_structure,
+            aer_info,
+        }
+    }
+}
+
+impl From<PciExpressError> for Bytes {
+    fn from(val: PciExpressError) -> Self {
+        let mut b = BytesMut::with_capacity(PciExpressError::LENGTH);
+        b.put_u64_le(val.validation_bits);
+        b.put_u32_le(val.port_type);
+        b.put_u8(val.version_minor);
+        b.put_u8(val.version_major);
+        b.put_slice(&val.reserved1);
+        b.put_u16_le(val.command);
+        b.put_u16_le(val.status);
+        b.put_slice(&val.reserved2);
+        b.put_u16_le(val.vendor_id);
+        b.put_u16_le(val.device_id);
+        b.put_slice(&val.class_code);
+        b.put_u8(val.function_number);
+        b.put_u8(val.device_number);
+        b.put_u16_le(val.segment_number);
+        b.put_u8(val.primary_bus_number);
+        b.put_u8(val.secondary_bus_number);
+        b.put_u16_le(val.slot_number);
+        b.put_u8(val.reserved3);
+        b.put_u64_le(val.device_serial_number);
+        b.put_u16_le(val.secondary_status);
+        b.put_u16_le(val.bridge_control);
+        b.put(val.capability_structure);
+        b.put(val.aer_info);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArmProcessorError {
+    pub validation_bits: u32,
+    pub error_info_num: u16,
+    pub context_info_num: u16,
+    pub section_length: u32,
+    pub error_affinity_level: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 3],
+    pub mpidr_el1: u64,
+    pub midr_el1: u64,
+    pub running_state: u32,
+    pub psci_state: u32,
+    pub error_info: Vec<ArmErrorInformation>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub context_info: Bytes,
+}
+
+impl ArmProcessorError {
+    const LENGTH: usize = 40;
+}
+
+impl From<Bytes> for ArmProcessorError {
+    fn from(mut buf: Bytes) -> Self {
+        let validation_bits = buf.get_u32_le();
+        let error_info_num = buf.get_u16_le();
+        let context_info_num = buf.get_u16_le();
+        let section_length = buf.get_u32_le();
+        let error_affinity_level = buf.get_u8();
+        let reserved = buf.split_to(3)[..].try_into().unwrap();
+        let mpidr_el1 = buf.get_u64_le();
+        let midr_el1 = buf.get_u64_le();
+        let running_state = buf.get_u32_le();
+        let psci_state = buf.get_u32_le();
+
+        let mut error_info = vec![];
+        for _ in 0..error_info_num {
+            if buf.len() < ArmErrorInformation::LENGTH {
+                break;
+            }
+
+            let info = buf.split_to(ArmErrorInformation::LENGTH);
+            error_info.push(ArmErrorInformation::from(info));
+        }
+
+        let context_info = buf.split_off(0);
+
+        ArmProcessorError {
+            validation_bits,
+            error_info_num,
+            context_info_num,
+            section_length,
+            error_affinity_level,
+            reserved,
+            mpidr_el1,
+            midr_el1,
+            running_state,
+            psci_state,
+            error_info,
+            context_info,
+        }
+    }
+}
+
+impl From<ArmProcessorError> for Bytes {
+    fn from(val: ArmProcessorError) -> Self {
+        let mut b = BytesMut::with_capacity(val.section_length as usize);
+        b.put_u32_le(val.validation_bits);
+        b.put_u16_le(val.error_info_num);
+        b.put_u16_le(val.context_info_num);
+        b.put_u32_le(val.section_length);
+        b.put_u8(val.error_affinity_level);
+        b.put_slice(&val.reserved);
+        b.put_u64_le(val.mpidr_el1);
+        b.put_u64_le(val.midr_el1);
+        b.put_u32_le(val.running_state);
+        b.put_u32_le(val.psci_state);
+        for info in val.error_info {
+            b.put(Bytes::from(info));
+        }
+        b.put(val.context_info);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArmErrorInformation {
+    pub version: u8,
+    pub length: u8,
+    pub validation_bits: u16,
+    pub error_type: u8,
+    pub multiple_error: u16,
+    pub flags: u8,
+    pub error_information: u64,
+    pub virtual_fault_address: u64,
+    pub physical_fault_address: u64,
+}
+
+impl ArmErrorInformation {
+    const LENGTH: usize = 32;
+}
+
+impl From<Bytes> for ArmErrorInformation {
+    fn from(mut buf: Bytes) -> Self {
+        let version = buf.get_u8();
+        let length = buf.get_u8();
+        let validation_bits = buf.get_u16_le();
+        let error_type = buf.get_u8();
+        let multiple_error = buf.get_u16_le();
+        let flags = buf.get_u8();
+        let error_information = buf.get_u64_le();
+        let virtual_fault_address = buf.get_u64_le();
+        let physical_fault_address = buf.get_u64_le();
+
+        ArmErrorInformation {
+            version,
+            length,
+            validation_bits,
+            error_type,
+            multiple_error,
+            flags,
+            error_information,
+            virtual_fault_address,
+            physical_fault_address,
+        }
+    }
+}
+
+impl From<ArmErrorInformation> for Bytes {
+    fn from(val: ArmErrorInformation) -> Self {
+        let mut b = BytesMut::with_capacity(ArmErrorInformation::LENGTH);
+        b.put_u8(val.version);
+        b.put_u8(val.length);
+        b.put_u16_le(val.validation_bits);
+        b.put_u8(val.error_type);
+        b.put_u16_le(val.multiple_error);
+        b.put_u8(val.flags);
+        b.put_u64_le(val.error_information);
+        b.put_u64_le(val.virtual_fault_address);
+        b.put_u64_le(val.physical_fault_address);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FirmwareErrorRecordReference {
+    pub record_type: u8,
+    pub revision: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 6],
+    pub record_identifier: u64,
+    pub record_identifier_guid: Option<Guid>,
+}
+
+impl FirmwareErrorRecordReference {
+    const LENGTH: usize = 16;
+}
+
+impl From<Bytes> for FirmwareErrorRecordReference {
+    fn from(mut buf: Bytes) -> Self {
+        let record_type = buf.get_u8();
+        let revision = buf.get_u8();
+        let reserved = buf.split_to(6)[..].try_into().unwrap();
+        let record_identifier = buf.get_u64_le();
+        let record_identifier_guid = if revision >= 2 && buf.len() >= 16 {
+            Some(Guid(buf.split_to(16)[..].try_into().unwrap()))
+        } else {
+            None
+        };
+
+        FirmwareErrorRecordReference {
+            record_type,
+            revision,
+            reserved,
+            record_identifier,
+            record_identifier_guid,
+        }
+    }
+}
+
+impl From<FirmwareErrorRecordReference> for Bytes {
+    fn from(val: FirmwareErrorRecordReference) -> Self {
+        let mut b = BytesMut::with_capacity(32);
+        b.put_u8(val.record_type);
+        b.put_u8(val.revision);
+        b.put_slice(&val.reserved);
+        b.put_u64_le(val.record_identifier);
+        if let Some(v) = val.record_identifier_guid {
+            b.put_slice(&v.0);
+        }
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boot_error_record() {
+        let data = BootErrorRecord {
+            signature: "BERT".to_string(),
+            length: 48,
+            revision: 1,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            boot_error_region_length: 6,
+            boot_error_region: 0x0102030405060708,
+        };
+        let b = Bytes::from(data.clone());
+        let raw = RawAcpiData::from(b);
+        let ret = BootErrorRecord::from(raw);
+        assert_eq!(data, ret);
+    }
+
+    #[test]
+    fn generic_error_status_block() {
+        let memory = MemoryError {
+            validation_bits: 1,
+            error_status: 2,
+            physical_address: 0x12345000,
+            physical_address_mask: 0xfffff000,
+            node: 3,
+            card: 4,
+            module: 5,
+            bank: 6,
+            device: 7,
+            row: 8,
+            column: 9,
+            bit_position: 10,
+            requestor_id: 11,
+            responder_id: 12,
+            target_id: 13,
+            memory_error_type: 14,
+            extended: 15,
+            rank_number: 16,
+            card_handle: 17,
+            module_handle: 18,
+        };
+        let firmware = FirmwareErrorRecordReference {
+            record_type: 2,
+            revision: 2,
+            reserved: [0; 6],
+            record_identifier: 19,
+            record_identifier_guid: Some(Guid([20; 16])),
+        };
+        let data = GenericErrorStatusBlock {
+            block_status: 0x21,
+            raw_data_offset: 0,
+            raw_data_length: 0,
+            data_length: 72 + 80 + 64 + 32,
+            error_severity: ErrorSeverity::Fatal,
+            entries: vec![
+                GenericErrorDataEntry {
+                    section_type: MEMORY_ERROR_SECTION,
+                    error_severity: ErrorSeverity::Fatal,
+                    revision: 0x0300,
+                    validation_bits: 0x04,
+                    flags: 0,
+                    error_data_length: 80,
+                    fru_id: Guid::default(),
+                    fru_text: "DIMM A1".to_string(),
+                    timestamp: Some(21),
+                    section: ErrorSection::Memory(memory),
+                },
+                GenericErrorDataEntry {
+                    section_type: FIRMWARE_ERROR_RECORD_REFERENCE_SECTION,
+                    error_severity: ErrorSeverity::Fatal,
+                    revision: 0x0201,
+                    validation_bits: 0,
+                    flags: 0,
+                    error_data_length: 32,
+                    fru_id: Guid::default(),
+                    fru_text: "".to_string(),
+                    timestamp: None,
+                    section: ErrorSection::FirmwareErrorRecordReference(firmware),
+                },
+            ],
+            raw_data: Bytes::new(),
+        };
+        let b = Bytes::from(data.clone());
+        let ret = GenericErrorStatusBlock::try_from(b.clone()).unwrap();
+        assert_eq!(data, ret);
+        assert!(ret.uncorrectable_error_valid());
+        assert_eq!(ret.error_data_entry_count(), 2);
+
+        assert!(matches!(
+            GenericErrorStatusBlock::try_from(b.slice(..19)),
+            Err(Error::InvalidTable)
+        ));
+
+        // An offset inside the header must not cut off what was already written.
+        let data = GenericErrorStatusBlock {
+            raw_data_offset: 4,
+            raw_data_length: 3,
+            raw_data: Bytes::from("xyz"),
+            ..Default::default()
+        };
+        let b = Bytes::from(data);
+        assert_eq!(b.len(), 23);
+        assert_eq!(&b[20..], b"xyz");
+    }
+}
@@ -0,0 +1,243 @@
+use super::error::Error;
+use super::memory::PhysicalMemoryReader;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, Bytes};
+
+// https://learn.microsoft.com/en-us/windows/win32/gdi/bitmap-storage
+
+const BI_RGB: u32 = 0;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Bitmap {
+    pub file_size: u32,
+    pub pixel_data_offset: u32,
+    pub header_size: u32,
+    pub width: i32,
+    // Negative for top-down bitmaps.
+    pub height: i32,
+    pub planes: u16,
+    pub bits_per_pixel: u16,
+    pub compression: u32,
+    pub image_size: u32,
+    // BGRX entries for bitmaps with 8 or fewer bits per pixel.
+    pub palette: Vec<[u8; 4]>,
+    // The whole file, headers included.
+    pub data: Bytes,
+}
+
+impl Bitmap {
+    const FILE_HEADER_LENGTH: usize = 14;
+    const INFO_HEADER_LENGTH: u32 = 40;
+    const MAX_FILE_SIZE: u32 = 64 << 20;
+
+    pub fn read_from<M>(memory: &mut M, address: u64) -> Result<Self, Error>
+    where
+        M: PhysicalMemoryReader,
+    {
+        let mut header = [0u8; Bitmap::FILE_HEADER_LENGTH];
+        memory.read_physical(address, &mut header)?;
+        if &header[0..2] != b"BM" {
+            return Err(Error::InvalidImage);
+        }
+
+        let file_size = u32::from_le_bytes(header[2..6].try_into().unwrap());
+        if (file_size as usize) < Bitmap::FILE_HEADER_LENGTH || file_size > Bitmap::MAX_FILE_SIZE {
+            return Err(Error::InvalidImage);
+        }
+
+        let mut data = vec![0u8; file_size as usize];
+        memory.read_physical(address, &mut data)?;
+        Bitmap::try_from(Bytes::from(data))
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width.unsigned_abs()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height.unsigned_abs()
+    }
+
+    pub fn top_down(&self) -> bool {
+        self.height < 0
+    }
+
+    // Returns width * height * 4 bytes of RGBA, rows ordered from the top of the image.
+    pub fn to_rgba(&self) -> Result<Vec<u8>, Error> {
+        if self.compression != BI_RGB {
+            return Err(Error::InvalidImage);
+        }
+
+        let bpp = self.bits_per_pixel as usize;
+        if !matches!(bpp, 1 | 4 | 8 | 24 | 32) {
+            return Err(Error::InvalidImage);
+        }
+
+        let width = self.width() as usize;
+        let height = self.height() as usize;
+        // Header fields are untrusted; the pixel slice check below also bounds the allocation.
+        let stride = width
+            .checked_mul(bpp)
+            .map(|bits| bits.div_ceil(32) * 4)
+            .ok_or(Error::InvalidImage)?;
+        let start = self.pixel_data_offset as usize;
+        let end = stride
+            .checked_mul(height)
+            .and_then(|size| start.checked_add(size))
+            .ok_or(Error::InvalidImage)?;
+        let pixels = self.data.get(start..end).ok_or(Error::InvalidImage)?;
+        let size = width
+            .checked_mul(height)
+            .and_then(|v| v.checked_mul(4))
+            .ok_or(Error::InvalidImage)?;
+
+        let mut rgba = Vec::with_capacity(size);
+        for y in 0..height {
+            let row = if self.top_down() { y } else { height - 1 - y };
+            let line = &pixels[row * stride..(row + 1) * stride];
+            for x in 0..width {
+                let [b, g, r, a] = match bpp {
+                    24 => [line[x * 3], line[x * 3 + 1], line[x * 3 + 2], 0xff],
+                    32 => [line[x * 4], line[x * 4 + 1], line[x * 4 + 2], 0xff],
+                    _ => {
+                        let bit = x * bpp;
+                        let shift = 8 - bpp - bit % 8;
+                        let index = (line[bit / 8] >> shift) & ((1 << bpp) - 1) as u8;
+                        let [b, g, r, _] = *self
+                            .palette
+                            .get(index as usize)
+                            .ok_or(Error::InvalidImage)?;
+                        [b, g, r, 0xff]
+                    }
+                };
+                rgba.extend_from_slice(&[r, g, b, a]);
+            }
+        }
+
+        Ok(rgba)
+    }
+}
+
+impl TryFrom<Bytes> for Bitmap {
+    type Error = Error;
+
+    fn try_from(data: Bytes) -> Result<Self, Self::Error> {
+        let header_end = Bitmap::FILE_HEADER_LENGTH + Bitmap::INFO_HEADER_LENGTH as usize;
+        if data.len() < header_end || &data[0..2] != b"BM" {
+            return Err(Error::InvalidImage);
+        }
+
+        let mut buf = data.slice(2..);
+        let file_size = buf.get_u32_le();
+        let _reserved = buf.get_u32_le();
+        let pixel_data_offset = buf.get_u32_le();
+        let header_size = buf.get_u32_le();
+        if header_size < Bitmap::INFO_HEADER_LENGTH {
+            return Err(Error::InvalidImage);
+        }
+
+        let width = buf.get_i32_le();
+        let height = buf.get_i32_le();
+        let planes = buf.get_u16_le();
+        let bits_per_pixel = buf.get_u16_le();
+        let compression = buf.get_u32_le();
+        let image_size = buf.get_u32_le();
+        let _x_pixels_per_meter = buf.get_i32_le();
+        let _y_pixels_per_meter = buf.get_i32_le();
+        let colors_used = buf.get_u32_le();
+
+        let palette = if bits_per_pixel <= 8 {
+            let count = match colors_used {
+                0 => 1usize << bits_per_pixel,
+                n => (n as usize).min(256),
+            };
+            let start = Bitmap::FILE_HEADER_LENGTH + header_size as usize;
+            let end = (start + 4 * count).min(data.len());
+            data.get(start..end)
+                .unwrap_or_default()
+                .chunks_exact(4)
+                .map(|d| d.try_into().unwrap())
+                .collect()
+        } else {
+            vec![]
+        };
+
+        Ok(Bitmap {
+            file_size,
+            pixel_data_offset,
+            header_size,
+            width,
+            height,
+            planes,
+            bits_per_pixel,
+            compression,
+            image_size,
+            palette,
+            data,
+        })
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use bytes::{BufMut, BytesMut};
+    use std::io::Cursor;
+
+    // 2x2, 24 bpp, bottom-up: bottom row red/green, top row blue/white.
+    fn bitmap() -> Bytes {
+        let mut b = BytesMut::new();
+        b.put_slice(b"BM");
+        b.put_u32_le(70);
+        b.put_u32_le(0);
+        b.put_u32_le(54);
+        b.put_u32_le(40);
+        b.put_i32_le(2);
+        b.put_i32_le(2);
+        b.put_u16_le(1);
+        b.put_u16_le(24);
+        b.put_u32_le(BI_RGB);
+        b.put_u32_le(16);
+        b.put_i32_le(2835);
+        b.put_i32_le(2835);
+        b.put_u32_le(0);
+        b.put_u32_le(0);
+        b.put_slice(&[0, 0, 0xff, 0, 0xff, 0, 0, 0]);
+        b.put_slice(&[0xff, 0, 0, 0xff, 0xff, 0xff, 0, 0]);
+        b.freeze()
+    }
+
+    #[test]
+    fn bitmap_read_from() {
+        let mut memory = vec![0u8; 0x100];
+        memory.extend_from_slice(&bitmap());
+        let mut memory = Cursor::new(memory);
+
+        let image = Bitmap::read_from(&mut memory, 0x100).unwrap();
+        assert_eq!((image.width(), image.height()), (2, 2));
+        assert_eq!(image.bits_per_pixel, 24);
+        assert!(!image.top_down());
+
+        let rgba = image.to_rgba().unwrap();
+        assert_eq!(
+            rgba,
+            vec![
+                0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // top
+                0xff, 0, 0, 0xff, 0, 0xff, 0, 0xff, // bottom
+            ]
+        );
+
+        assert!(Bitmap::read_from(&mut memory, 0).is_err());
+
+        // 2^31 x 2^31 at 32 bpp overflows the pixel data size.
+        let mut data = BytesMut::from(&bitmap()[..]);
+        data[18..22].copy_from_slice(&i32::MIN.to_le_bytes());
+        data[22..26].copy_from_slice(&i32::MIN.to_le_bytes());
+        data[28..30].copy_from_slice(&32u16.to_le_bytes());
+        let image = Bitmap::try_from(data.freeze()).unwrap();
+        assert!(matches!(image.to_rgba(), Err(Error::InvalidImage)));
+    }
+}
@@ -0,0 +1,712 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::{RawAcpiData, string_to_array};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://computeexpresslink.org/ (CXL 3.1, 9.18 CXL Early Discovery Table)
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CxlEarlyDiscovery {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub structures: Vec<CedtStructure>,
+}
+
+impl CxlEarlyDiscovery {
+    pub fn host_bridges(&self) -> impl Iterator<Item = &CxlHostBridge> {
+        self.structures.iter().filter_map(|s| match s {
+            CedtStructure::HostBridge(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    pub fn host_bridge(&self, uid: u32) -> Option<&CxlHostBridge> {
+        self.host_bridges().find(|h| h.uid == uid)
+    }
+
+    pub fn fixed_memory_windows(&self) -> impl Iterator<Item = &CxlFixedMemoryWindow> {
+        self.structures.iter().filter_map(|s| match s {
+            CedtStructure::FixedMemoryWindow(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    pub fn xor_interleave_math(&self, granularity: u32) -> Option<&CxlXorInterleaveMath> {
+        self.structures.iter().find_map(|s| match s {
+            CedtStructure::XorInterleaveMath(v) if v.granularity as u32 == granularity => Some(v),
+            _ => None,
+        })
+    }
+
+    // Finds the fixed memory window containing `hpa` and the host bridge its interleave selects.
+    pub fn host_bridge_for(&self, hpa: u64) -> Option<HostBridgeTarget<'_>> {
+        let window = self.fixed_memory_windows().find(|w| w.contains(hpa))?;
+        let position = match window.interleave_arithmetic {
+            InterleaveArithmetic::Modulo => window.modulo_position(hpa)?,
+            InterleaveArithmetic::Xor => {
+                let xor = self.xor_interleave_math(window.granularity)?;
+                window.xor_position(hpa, xor)?
+            }
+            InterleaveArithmetic::Unknown(_) => return None,
+        };
+        let uid = *window.targets.get(position)?;
+
+        Some(HostBridgeTarget {
+            window,
+            position,
+            uid,
+            host_bridge: self.host_bridge(uid),
+        })
+    }
+}
+
+impl From<RawAcpiData> for CxlEarlyDiscovery {
+    fn from(data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+
+        let mut body = data.acpi_table_data;
+        let mut structures = vec![];
+        while body.len() >= 4 {
+            let len = u16::from_le_bytes([body[2], body[3]]) as usize;
+            if len < 4 || body.len() < len {
+                break;
+            }
+            structures.push(CedtStructure::from(body.split_to(len)));
+        }
+
+        CxlEarlyDiscovery {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            structures,
+        }
+    }
+}
+
+impl From<CxlEarlyDiscovery> for Bytes {
+    fn from(val: CxlEarlyDiscovery) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        for structure in val.structures {
+            b.put(Bytes::from(structure));
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for CxlEarlyDiscovery {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "CXL Early Discovery Table");
+        for structure in &self.structures {
+            structure.disassemble(w);
+            w.blank();
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HostBridgeTarget<'a> {
+    pub window: &'a CxlFixedMemoryWindow,
+    pub position: usize,
+    pub uid: u32,
+    pub host_bridge: Option<&'a CxlHostBridge>,
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CedtStructure {
+    HostBridge(CxlHostBridge),
+    FixedMemoryWindow(CxlFixedMemoryWindow),
+    XorInterleaveMath(CxlXorInterleaveMath),
+    RcecDownstreamPortAssociation(RcecDownstreamPortAssociation),
+    Unknown(
+        u8,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Bytes,
+    ),
+}
+
+impl CedtStructure {
+    pub fn structure_type(&self) -> u8 {
+        match self {
+            CedtStructure::HostBridge(_) => 0,
+            CedtStructure::FixedMemoryWindow(_) => 1,
+            CedtStructure::XorInterleaveMath(_) => 2,
+            CedtStructure::RcecDownstreamPortAssociation(_) => 3,
+            CedtStructure::Unknown(t, _) => *t,
+        }
+    }
+}
+
+impl From<Bytes> for CedtStructure {
+    fn from(mut buf: Bytes) -> Self {
+        let structure_type = buf.get_u8();
+        let _reserved = buf.get_u8();
+        let _length = buf.get_u16_le();
+        match structure_type {
+            0 if buf.len() >= 28 => CedtStructure::HostBridge(CxlHostBridge::from(buf)),
+            1 if buf.len() >= 32 => {
+                CedtStructure::FixedMemoryWindow(CxlFixedMemoryWindow::from(buf))
+            }
+            2 if buf.len() >= 4 => {
+                CedtStructure::XorInterleaveMath(CxlXorInterleaveMath::from(buf))
+            }
+            3 if buf.len() >= 13 => CedtStructure::RcecDownstreamPortAssociation(
+                RcecDownstreamPortAssociation::from(buf),
+            ),
+            t => CedtStructure::Unknown(t, buf),
+        }
+    }
+}
+
+impl From<CedtStructure> for Bytes {
+    fn from(val: CedtStructure) -> Self {
+        let structure_type = val.structure_type();
+        let data = match val {
+            CedtStructure::HostBridge(v) => Bytes::from(v),
+            CedtStructure::FixedMemoryWindow(v) => Bytes::from(v),
+            CedtStructure::XorInterleaveMath(v) => Bytes::from(v),
+            CedtStructure::RcecDownstreamPortAssociation(v) => Bytes::from(v),
+            CedtStructure::Unknown(_, v) => v,
+        };
+
+        let mut b = BytesMut::with_capacity(4 + data.len());
+        b.put_u8(structure_type);
+        b.put_u8(0);
+        b.put_u16_le(4 + data.len() as u16);
+        b.put(data);
+        b.freeze()
+    }
+}
+
+impl Disassemble for CedtStructure {
+    fn disassemble(&self, w: &mut Disassembler) {
+        let name = match self {
+            CedtStructure::HostBridge(_) => "CXL Host Bridge Structure",
+            CedtStructure::FixedMemoryWindow(_) => "CXL Fixed Memory Window Structure",
+            CedtStructure::XorInterleaveMath(_) => "CXL XOR Interleave Math Structure",
+            CedtStructure::RcecDownstreamPortAssociation(_) => {
+                "RCEC Downstream Port Association Structure"
+            }
+            CedtStructure::Unknown(_, _) => "Unknown Subtable Type",
+        };
+        let length = Bytes::from(self.clone()).len();
+        w.annotated("Subtable Type", 1, self.structure_type() as u64, name);
+        w.u8("Reserved", 0);
+        w.u16("Length", length as u16);
+
+        match self {
+            CedtStructure::HostBridge(v) => {
+                w.u32("Associated host bridge", v.uid);
+                w.u32("Specification version", v.cxl_version);
+                w.bytes("Reserved", &v.reserved);
+                w.u64("Register base", v.base);
+                w.u64("Register length", v.length);
+            }
+            CedtStructure::FixedMemoryWindow(v) => {
+                w.bytes("Reserved", &v.reserved1);
+                w.u64("Window base address", v.base_hpa);
+                w.u64("Window size", v.window_size);
+                w.u8("Interleave Members", v.encoded_interleave_ways);
+                w.u8("Interleave Arithmetic", v.interleave_arithmetic.into());
+                w.bytes("Reserved", &v.reserved2);
+                w.u32("Granularity", v.granularity);
+                w.u16("Restrictions", v.restrictions);
+                w.u16("QtgId", v.qtg_id);
+                for target in &v.targets {
+                    w.u32("Target", *target);
+                }
+            }
+            CedtStructure::XorInterleaveMath(v) => {
+                w.bytes("Reserved", &v.reserved);
+                w.u8("Interleave Granularity", v.granularity);
+                w.u8("Xormap List Count", v.number_of_bitmaps);
+                for map in &v.xormaps {
+                    w.u64("Xormap", *map);
+                }
+            }
+            CedtStructure::RcecDownstreamPortAssociation(v) => {
+                w.u16("Segment", v.segment_number);
+                w.u16("BDF", v.bdf);
+                w.u8("Protocol Type", v.protocol_type);
+                w.u64("Base Address", v.base_address);
+            }
+            CedtStructure::Unknown(_, v) => w.bytes("Data", v),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CxlHostBridge {
+    pub uid: u32,
+    pub cxl_version: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 4],
+    pub base: u64,
+    pub length: u64,
+}
+
+impl CxlHostBridge {
+    // Version 0 hosts are CXL 1.1 (RCRB base); version 1 hosts expose CXL 2.0+ component registers.
+    pub fn restricted_cxl_host(&self) -> bool {
+        self.cxl_version == 0
+    }
+}
+
+impl From<Bytes> for CxlHostBridge {
+    fn from(mut buf: Bytes) -> Self {
+        let uid = buf.get_u32_le();
+        let cxl_version = buf.get_u32_le();
+        let reserved = buf.split_to(4)[..].try_into().unwrap();
+        let base = buf.get_u64_le();
+        let length = buf.get_u64_le();
+
+        CxlHostBridge {
+            uid,
+            cxl_version,
+            reserved,
+            base,
+            length,
+        }
+    }
+}
+
+impl From<CxlHostBridge> for Bytes {
+    fn from(val: CxlHostBridge) -> Self {
+        let mut b = BytesMut::with_capacity(28);
+        b.put_u32_le(val.uid);
+        b.put_u32_le(val.cxl_version);
+        b.put_slice(&val.reserved);
+        b.put_u64_le(val.base);
+        b.put_u64_le(val.length);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InterleaveArithmetic {
+    Modulo,
+    Xor,
+    Unknown(u8),
+}
+
+impl From<u8> for InterleaveArithmetic {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => InterleaveArithmetic::Modulo,
+            1 => InterleaveArithmetic::Xor,
+            v => InterleaveArithmetic::Unknown(v),
+        }
+    }
+}
+
+impl From<InterleaveArithmetic> for u8 {
+    fn from(val: InterleaveArithmetic) -> Self {
+        match val {
+            InterleaveArithmetic::Modulo => 0,
+            InterleaveArithmetic::Xor => 1,
+            InterleaveArithmetic::Unknown(v) => v,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CxlFixedMemoryWindow {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved1: [u8; 4],
+    pub base_hpa: u64,
+    pub window_size: u64,
+    pub encoded_interleave_ways: u8,
+    pub interleave_arithmetic: InterleaveArithmetic,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved2: [u8; 2],
+    pub granularity: u32,
+    pub restrictions: u16,
+    pub qtg_id: u16,
+    pub targets: Vec<u32>,
+}
+
+impl CxlFixedMemoryWindow {
+    pub fn interleave_ways(&self) -> Option<usize> {
+        match self.encoded_interleave_ways {
+            v @ 0..=4 => Some(1 << v),
+            v @ 8..=10 => Some(3 << (v - 8)),
+            _ => None,
+        }
+    }
+
+    pub fn interleave_granularity(&self) -> Option<u64> {
+        (self.granularity <= 6).then(|| 256 << self.granularity)
+    }
+
+    pub fn device_coherent(&self) -> bool {
+        self.restrictions & 0x01 != 0
+    }
+
+    pub fn host_only_coherent(&self) -> bool {
+        self.restrictions & 0x02 != 0
+    }
+
+    pub fn volatile(&self) -> bool {
+        self.restrictions & 0x04 != 0
+    }
+
+    pub fn persistent(&self) -> bool {
+        self.restrictions & 0x08 != 0
+    }
+
+    pub fn fixed_device_configuration(&self) -> bool {
+        self.restrictions & 0x10 != 0
+    }
+
+    pub fn contains(&self, hpa: u64) -> bool {
+        hpa >= self.base_hpa && hpa - self.base_hpa < self.window_size
+    }
+
+    fn modulo_position(&self, hpa: u64) -> Option<usize> {
+        let ways = self.interleave_ways()? as u64;
+        let granularity = self.interleave_granularity()?;
+        Some(((hpa - self.base_hpa) / granularity % ways) as usize)
+    }
+
+    // Each XORMAP selects the HPA bits whose parity forms one bit of the target position. With 3,
+    // 6 or 12 ways the XORMAPs supply the low bits and HPA[51:IG+8+k] modulo 3 the top one, where
+    // 2^k is the power of two factor of the ways.
+    fn xor_position(&self, hpa: u64, xor: &CxlXorInterleaveMath) -> Option<usize> {
+        let ways = self.interleave_ways()?;
+        let bits = (ways & ways.wrapping_neg()).trailing_zeros() as usize;
+        if xor.xormaps.len() < bits {
+            return None;
+        }
+
+        let mut position = xor.xormaps[..bits]
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, map)| {
+                acc | (((hpa & map).count_ones() as usize) & 1) << i
+            });
+        if !ways.is_power_of_two() {
+            let shift = self.interleave_granularity()?.trailing_zeros() as usize + bits;
+            let modulo = (hpa & ((1 << 52) - 1)) >> shift;
+            position |= ((modulo % 3) as usize) << bits;
+        }
+        Some(position)
+    }
+}
+
+impl From<Bytes> for CxlFixedMemoryWindow {
+    fn from(mut buf: Bytes) -> Self {
+        let reserved1 = buf.split_to(4)[..].try_into().unwrap();
+        let base_hpa = buf.get_u64_le();
+        let window_size = buf.get_u64_le();
+        let encoded_interleave_ways = buf.get_u8();
+        let interleave_arithmetic = InterleaveArithmetic::from(buf.get_u8());
+        let reserved2 = buf.split_to(2)[..].try_into().unwrap();
+        let granularity = buf.get_u32_le();
+        let restrictions = buf.get_u16_le();
+        let qtg_id = buf.get_u16_le();
+        let targets = buf
+            .chunks_exact(4)
+            .map(|d| u32::from_le_bytes(d.try_into().unwrap()))
+            .collect();
+
+        CxlFixedMemoryWindow {
+            reserved1,
+            base_hpa,
+            window_size,
+            encoded_interleave_ways,
+            interleave_arithmetic,
+            reserved2,
+            granularity,
+            restrictions,
+            qtg_id,
+            targets,
+        }
+    }
+}
+
+impl From<CxlFixedMemoryWindow> for Bytes {
+    fn from(val: CxlFixedMemoryWindow) -> Self {
+        let mut b = BytesMut::with_capacity(32 + 4 * val.targets.len());
+        b.put_slice(&val.reserved1);
+        b.put_u64_le(val.base_hpa);
+        b.put_u64_le(val.window_size);
+        b.put_u8(val.encoded_interleave_ways);
+        b.put_u8(val.interleave_arithmetic.into());
+        b.put_slice(&val.reserved2);
+        b.put_u32_le(val.granularity);
+        b.put_u16_le(val.restrictions);
+        b.put_u16_le(val.qtg_id);
+        for target in val.targets {
+            b.put_u32_le(target);
+        }
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CxlXorInterleaveMath {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 2],
+    pub granularity: u8,
+    pub number_of_bitmaps: u8,
+    pub xormaps: Vec<u64>,
+}
+
+impl From<Bytes> for CxlXorInterleaveMath {
+    fn from(mut buf: Bytes) -> Self {
+        let reserved = buf.split_to(2)[..].try_into().unwrap();
+        let granularity = buf.get_u8();
+        let number_of_bitmaps = buf.get_u8();
+        let xormaps = buf
+            .chunks_exact(8)
+            .take(number_of_bitmaps as usize)
+            .map(|d| u64::from_le_bytes(d.try_into().unwrap()))
+            .collect();
+
+        CxlXorInterleaveMath {
+            reserved,
+            granularity,
+            number_of_bitmaps,
+            xormaps,
+        }
+    }
+}
+
+impl From<CxlXorInterleaveMath> for Bytes {
+    fn from(val: CxlXorInterleaveMath) -> Self {
+        let mut b = BytesMut::with_capacity(4 + 8 * val.xormaps.len());
+        b.put_slice(&val.reserved);
+        b.put_u8(val.granularity);
+        b.put_u8(val.number_of_bitmaps);
+        for map in val.xormaps {
+            b.put_u64_le(map);
+        }
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RcecDownstreamPortAssociation {
+    pub segment_number: u16,
+    pub bdf: u16,
+    pub protocol_type: u8,
+    pub base_address: u64,
+}
+
+impl RcecDownstreamPortAssociation {
+    pub fn bus(&self) -> u8 {
+        (self.bdf >> 8) as u8
+    }
+
+    pub fn device(&self) -> u8 {
+        ((self.bdf >> 3) & 0x1f) as u8
+    }
+
+    pub fn function(&self) -> u8 {
+        (self.bdf & 0x07) as u8
+    }
+}
+
+impl From<Bytes> for RcecDownstreamPortAssociation {
+    fn from(mut buf: Bytes) -> Self {
+        let segment_number = buf.get_u16_le();
+        let bdf = buf.get_u16_le();
+        let protocol_type = buf.get_u8();
+        let base_address = buf.get_u64_le();
+
+        RcecDownstreamPortAssociation {
+            segment_number,
+            bdf,
+            protocol_type,
+            base_address,
+        }
+    }
+}
+
+impl From<RcecDownstreamPortAssociation> for Bytes {
+    fn from(val: RcecDownstreamPortAssociation) -> Self {
+        let mut b = BytesMut::with_capacity(13);
+        b.put_u16_le(val.segment_number);
+        b.put_u16_le(val.bdf);
+        b.put_u8(val.protocol_type);
+        b.put_u64_le(val.base_address);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cxl_early_discovery() {
+        let data = CxlEarlyDiscovery {
+            signature: "CEDT".to_string(),
+            length: 36 + 32 * 2 + 44 + 44 + 24 + 17,
+            revision: 1,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            structures: vec![
+                CedtStructure::HostBridge(CxlHostBridge {
+                    uid: 7,
+                    cxl_version: 1,
+                    reserved: [0; 4],
+                    base: 0xfe00_0000,
+                    length: 0x10000,
+                }),
+                CedtStructure::HostBridge(CxlHostBridge {
+                    uid: 8,
+                    cxl_version: 1,
+                    reserved: [0; 4],
+                    base: 0xfe01_0000,
+                    length: 0x10000,
+                }),
+                CedtStructure::FixedMemoryWindow(CxlFixedMemoryWindow {
+                    reserved1: [0; 4],
+                    base_hpa: 0x10_0000_0000,
+                    window_size: 0x10_0000_0000,
+                    encoded_interleave_ways: 1,
+                    interleave_arithmetic: InterleaveArithmetic::Modulo,
+                    reserved2: [0; 2],
+                    granularity: 0,
+                    restrictions: 0x06,
+                    qtg_id: 0,
+                    targets: vec![7, 8],
+                }),
+                CedtStructure::FixedMemoryWindow(CxlFixedMemoryWindow {
+                    reserved1: [0; 4],
+                    base_hpa: 0x20_0000_0000,
+                    window_size: 0x10_0000_0000,
+                    encoded_interleave_ways: 1,
+                    interleave_arithmetic: InterleaveArithmetic::Xor,
+                    reserved2: [0; 2],
+                    granularity: 1,
+                    restrictions: 0x0a,
+                    qtg_id: 1,
+                    targets: vec![8, 7],
+                }),
+                CedtStructure::XorInterleaveMath(CxlXorInterleaveMath {
+                    reserved: [0; 2],
+                    granularity: 1,
+                    number_of_bitmaps: 2,
+                    xormaps: vec![0x0000_0000_0000_2200, 0x0000_0000_0000_4400],
+                }),
+                CedtStructure::RcecDownstreamPortAssociation(RcecDownstreamPortAssociation {
+                    segment_number: 0,
+                    bdf: 0x0108,
+                    protocol_type: 1,
+                    base_address: 0xfd00_0000,
+                }),
+            ],
+        };
+        let b = Bytes::from(data.clone());
+        assert_eq!(b.len(), data.length as usize);
+        let raw = RawAcpiData::from(b);
+        let ret = CxlEarlyDiscovery::from(raw);
+        assert_eq!(data, ret);
+
+        let target = ret.host_bridge_for(0x10_0000_0000).unwrap();
+        assert_eq!((target.position, target.uid), (0, 7));
+        assert_eq!(target.host_bridge.unwrap().base, 0xfe00_0000);
+        assert_eq!(ret.host_bridge_for(0x10_0000_0100).unwrap().uid, 8);
+        assert_eq!(ret.host_bridge_for(0x10_0000_0200).unwrap().uid, 7);
+
+        // 512B granularity, position bit 0 is the parity of HPA bits 9 and 13.
+        assert_eq!(ret.host_bridge_for(0x20_0000_0000).unwrap().uid, 8);
+        assert_eq!(ret.host_bridge_for(0x20_0000_0200).unwrap().uid, 7);
+        assert_eq!(ret.host_bridge_for(0x20_0000_2200).unwrap().uid, 8);
+
+        assert!(ret.host_bridge_for(0x30_0000_0000).is_none());
+    }
+
+    #[test]
+    fn xor_interleave_non_power_of_two() {
+        let data = CxlEarlyDiscovery {
+            structures: vec![
+                CedtStructure::FixedMemoryWindow(CxlFixedMemoryWindow {
+                    reserved1: [0; 4],
+                    base_hpa: 0x40_0000_0000,
+                    window_size: 0x30_0000_0000,
+                    encoded_interleave_ways: 9,
+                    interleave_arithmetic: InterleaveArithmetic::Xor,
+                    reserved2: [0; 2],
+                    granularity: 0,
+                    restrictions: 0x04,
+                    qtg_id: 0,
+                    targets: vec![1, 2, 3, 4, 5, 6],
+                }),
+                CedtStructure::XorInterleaveMath(CxlXorInterleaveMath {
+                    reserved: [0; 2],
+                    granularity: 0,
+                    number_of_bitmaps: 1,
+                    xormaps: vec![0x0000_0000_0000_0100],
+                }),
+            ],
+            ..Default::default()
+        };
+
+        // 6 ways at 256B: bit 0 is the parity of HPA bit 8, bits 1-2 are HPA[51:9] modulo 3.
+        assert_eq!(data.host_bridge_for(0x40_0000_0000).unwrap().position, 4);
+        assert_eq!(data.host_bridge_for(0x40_0000_0100).unwrap().uid, 6);
+        assert_eq!(data.host_bridge_for(0x40_0000_0200).unwrap().uid, 1);
+        assert_eq!(data.host_bridge_for(0x40_0000_0400).unwrap().uid, 3);
+        assert_eq!(data.host_bridge_for(0x40_0000_0500).unwrap().uid, 4);
+    }
+}
@@ -0,0 +1,331 @@
+use super::gas::{AddressSpace, GenericAddress};
+use super::guid::Guid;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+// Renders tables in the layout of `iasl -d`, e.g.
+// [024h 0036 008]                 Base Address : 00000000E0000000
+
+const LABEL_WIDTH: usize = 28;
+const PREFIX_WIDTH: usize = 16;
+const BYTES_PER_LINE: usize = 16;
+
+pub trait Disassemble {
+    fn disassemble(&self, w: &mut Disassembler);
+
+    fn disassembly(&self) -> Disassembly<'_, Self>
+    where
+        Self: Sized,
+    {
+        Disassembly(self)
+    }
+}
+
+pub struct Disassembly<'a, T>(&'a T);
+
+impl<T> fmt::Display for Disassembly<'_, T>
+where
+    T: Disassemble,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut w = Disassembler::default();
+        self.0.disassemble(&mut w);
+        f.write_str(&w.output)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Disassembler {
+    offset: usize,
+    output: String,
+    fields: Vec<DisassembledField>,
+    // Qualifies recorded names of fields nested in a structure, e.g. the parts of a GAS.
+    scope: Option<String>,
+}
+
+// A field as rendered, kept so tables can be compared field by field.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisassembledField {
+    pub offset: usize,
+    pub length: usize,
+    pub name: String,
+    pub value: String,
+}
+
+impl Disassembler {
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    // Moves to an absolute table offset, for fields located through an offset in the table.
+    pub fn seek(&mut self, offset: usize) {
+        self.offset = offset;
+    }
+
+    pub fn finish(self) -> String {
+        self.output
+    }
+
+    pub fn fields(&self) -> &[DisassembledField] {
+        &self.fields
+    }
+
+    pub fn field(&mut self, name: &str, length: usize, value: impl fmt::Display) {
+        let value = value.to_string();
+        self.record(name, length, &value);
+        self.write(name, length, &value);
+    }
+
+    fn record(&mut self, name: &str, length: usize, value: &str) {
+        self.fields.push(DisassembledField {
+            offset: self.offset,
+            length,
+            name: match &self.scope {
+                Some(scope) => format!("{} {}", scope, name),
+                None => name.to_string(),
+            },
+            value: value.to_string(),
+        });
+    }
+
+    fn write(&mut self, name: &str, length: usize, value: &str) {
+        writeln!(
+            self.output,
+            "[{:03X}h {:04} {:03}] {:>width$} : {}",
+            self.offset,
+            self.offset,
+            length,
+            name,
+            value,
+            width = LABEL_WIDTH
+        )
+        .unwrap();
+        self.offset += length;
+    }
+
+    pub fn u8(&mut self, name: &str, value: u8) {
+        self.field(name, 1, format_args!("{:02X}", value));
+    }
+
+    pub fn u16(&mut self, name: &str, value: u16) {
+        self.field(name, 2, format_args!("{:04X}", value));
+    }
+
+    pub fn u32(&mut self, name: &str, value: u32) {
+        self.field(name, 4, format_args!("{:08X}", value));
+    }
+
+    pub fn u64(&mut self, name: &str, value: u64) {
+        self.field(name, 8, format_args!("{:016X}", value));
+    }
+
+    // A value followed by its decoded meaning, e.g. `01 [SystemIO]`.
+    pub fn annotated(&mut self, name: &str, length: usize, value: u64, note: &str) {
+        let value = format!("{:0width$X}", value, width = length * 2);
+        self.field(name, length, format_args!("{} [{}]", value, note));
+    }
+
+    pub fn string(&mut self, name: &str, length: usize, value: &str) {
+        self.field(name, length, format_args!("\"{}\"", value));
+    }
+
+    pub fn guid(&mut self, name: &str, value: &Guid) {
+        self.field(name, 16, value);
+    }
+
+    pub fn bytes(&mut self, name: &str, value: &[u8]) {
+        let indent = PREFIX_WIDTH + LABEL_WIDTH + 3;
+        let lines = value
+            .chunks(BYTES_PER_LINE)
+            .map(|l| {
+                l.iter()
+                    .map(|b| format!("{:02X}", b))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            })
+            .collect::<Vec<String>>();
+        self.record(name, value.len(), &lines.join(" "));
+        let text = lines.join(&format!("\n{:indent$}", "", indent = indent));
+        self.write(name, value.len(), &text);
+    }
+
+    pub fn gas(&mut self, name: &str, value: &GenericAddress) {
+        let start = self.offset;
+        self.write(name, 12, "[Generic Address Structure]");
+        self.offset = start;
+        self.scope = Some(name.to_string());
+        self.annotated(
+            "Space ID",
+            1,
+            u8::from(value.address_space_id) as u64,
+            address_space_name(value.address_space_id),
+        );
+        self.u8("Bit Width", value.register_bit_width);
+        self.u8("Bit Offset", value.register_bit_offset);
+        self.annotated(
+            "Encoded Access Width",
+            1,
+            value.access_size as u64,
+            access_size_name(value.access_size),
+        );
+        self.u64("Address", value.address);
+        self.scope = None;
+    }
+
+    // Decoded bits of the preceding field; these do not advance the offset.
+    pub fn flag(&mut self, name: &str, value: impl fmt::Display) {
+        writeln!(
+            self.output,
+            "{:prefix$}{:>width$} : {}",
+            "",
+            name,
+            value,
+            prefix = PREFIX_WIDTH,
+            width = LABEL_WIDTH
+        )
+        .unwrap();
+    }
+
+    pub fn comment(&mut self, text: &str) {
+        writeln!(
+            self.output,
+            "{:prefix$}/* {} */",
+            "",
+            text,
+            prefix = PREFIX_WIDTH
+        )
+        .unwrap();
+    }
+
+    pub fn blank(&mut self) {
+        self.output.push('\n');
+    }
+
+    pub fn signature(&mut self, value: &str, description: &str) {
+        self.field(
+            "Signature",
+            4,
+            format_args!("\"{}\"    [{}]", value, description),
+        );
+    }
+
+    // iasl shows the creator ID as the compiler's four character name.
+    pub fn creator_id(&mut self, name: &str, value: u32) {
+        let id = value.to_le_bytes();
+        if id.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+            self.string(name, 4, &String::from_utf8_lossy(&id));
+        } else {
+            self.u32(name, value);
+        }
+    }
+}
+
+// Every table starts with the same 36 byte header, held in identically named fields.
+macro_rules! header {
+    ($w:expr, $table:expr, $description:expr) => {{
+        $w.signature(&$table.signature, $description);
+        $w.u32("Table Length", $table.length);
+        $w.u8("Revision", $table.revision);
+        $w.u8("Checksum", $table.checksum);
+        $w.string("Oem ID", 6, &$table.oem_id);
+        $w.string("Oem Table ID", 8, &$table.oem_table_id);
+        $w.u32("Oem Revision", $table.oem_revision);
+        $w.creator_id("Asl Compiler ID", $table.creator_id);
+        $w.u32("Asl Compiler Revision", $table.creator_revision);
+        $w.blank();
+    }};
+}
+
+pub(crate) use header;
+
+fn address_space_name(value: AddressSpace) -> &'static str {
+    match value {
+        AddressSpace::SystemMemory => "SystemMemory",
+        AddressSpace::SystemIo => "SystemIO",
+        AddressSpace::PciConfiguration => "PCI_Config",
+        AddressSpace::EmbeddedController => "EmbeddedControl",
+        AddressSpace::SmBus => "SMBus",
+        AddressSpace::SystemCmos => "SystemCMOS",
+        AddressSpace::PciBarTarget => "PCIBARTarget",
+        AddressSpace::Ipmi => "IPMI",
+        AddressSpace::GeneralPurposeIo => "GeneralPurposeIo",
+        AddressSpace::GenericSerialBus => "GenericSerialBus",
+        AddressSpace::PlatformCommunicationsChannel => "PCC",
+        AddressSpace::PlatformRuntimeMechanism => "PlatformRtMechanism",
+        AddressSpace::FunctionalFixedHardware => "FunctionalFixedHW",
+        AddressSpace::Reserved(_) => "Unknown",
+    }
+}
+
+fn access_size_name(value: u8) -> &'static str {
+    match value {
+        0 => "Undefined/Legacy",
+        1 => "Byte Access:8",
+        2 => "Word Access:16",
+        3 => "DWord Access:32",
+        4 => "QWord Access:64",
+        _ => "Unknown",
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disassembler() {
+        let mut w = Disassembler::default();
+        w.seek(0x24);
+        w.u64("Base Address", 0xe000_0000);
+        w.flag("Displayed", 1);
+        w.bytes("Reserved", &[0; 18]);
+        w.gas(
+            "Timer Block Register",
+            &GenericAddress {
+                address_space_id: AddressSpace::SystemIo,
+                register_bit_width: 32,
+                register_bit_offset: 0,
+                access_size: 3,
+                address: 0x408,
+            },
+        );
+        assert_eq!(w.offset(), 0x24 + 8 + 18 + 12);
+
+        let fields = w.fields();
+        assert_eq!(fields.len(), 2 + 5);
+        assert_eq!(fields[1].value, ["00"; 18].join(" "));
+        assert_eq!(fields[6].offset, 0x3e + 4);
+        assert_eq!(fields[6].name, "Timer Block Register Address");
+        assert_eq!(fields[6].value, "0000000000000408");
+
+        let out = w.finish();
+        let lines = out.lines().collect::<Vec<&str>>();
+        assert_eq!(
+            lines[0],
+            "[024h 0036 008]                 Base Address : 00000000E0000000"
+        );
+        assert_eq!(lines[1], "                                   Displayed : 1");
+        assert_eq!(
+            lines[2],
+            "[02Ch 0044 018]                     Reserved : 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00"
+        );
+        assert_eq!(lines[3].trim(), "00 00");
+        assert_eq!(
+            lines[4],
+            "[03Eh 0062 012]         Timer Block Register : [Generic Address Structure]"
+        );
+        assert_eq!(
+            lines[5],
+            "[03Eh 0062 001]                     Space ID : 01 [SystemIO]"
+        );
+        assert_eq!(
+            lines[8],
+            "[041h 0065 001]         Encoded Access Width : 03 [DWord Access:32]"
+        );
+    }
+}
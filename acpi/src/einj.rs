@@ -0,0 +1,378 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::erst::{ActionInterpreter, Instruction, InstructionEntry};
+use super::gas::GenericAddress;
+use super::{RawAcpiData, string_to_array};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://uefi.org/specs/ACPI/6.5/18_Platform_Error_Interfaces.html#error-injection
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorInjection {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub injection_header_size: u32,
+    pub injection_flags: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 3],
+    pub injection_entry_count: u32,
+    pub entries: Vec<InjectionInstructionEntry>,
+}
+
+impl From<RawAcpiData> for ErrorInjection {
+    fn from(mut data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+        let injection_header_size = data.acpi_table_data.get_u32_le();
+        let injection_flags = data.acpi_table_data.get_u8();
+        let reserved = data.acpi_table_data.split_to(3)[..].try_into().unwrap();
+        let injection_entry_count = data.acpi_table_data.get_u32_le();
+        let mut entries = vec![];
+
+        for d in data.acpi_table_data.chunks_exact(32) {
+            let entry = Bytes::copy_from_slice(d);
+            entries.push(InjectionInstructionEntry::from(entry));
+        }
+
+        ErrorInjection {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            injection_header_size,
+            injection_flags,
+            reserved,
+            injection_entry_count,
+            entries,
+        }
+    }
+}
+
+impl From<ErrorInjection> for Bytes {
+    fn from(val: ErrorInjection) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        b.put_u32_le(val.injection_header_size);
+        b.put_u8(val.injection_flags);
+        b.put_slice(&val.reserved);
+        b.put_u32_le(val.injection_entry_count);
+        for entry in val.entries {
+            b.put(Bytes::from(entry));
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for ErrorInjection {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Error Injection Table");
+        w.u32("Injection Header Size", self.injection_header_size);
+        w.u8("Flags", self.injection_flags);
+        w.bytes("Reserved", &self.reserved);
+        w.u32("Injection Entry Count", self.injection_entry_count);
+        for entry in &self.entries {
+            w.blank();
+            entry.disassemble(w);
+        }
+    }
+}
+
+impl ActionInterpreter for ErrorInjection {
+    type Action = InjectionAction;
+
+    fn instructions(&self, action: Self::Action) -> Vec<InstructionEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.injection_action == action)
+            .map(InstructionEntry::from)
+            .collect()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TriggerErrorAction {
+    pub header_size: u32,
+    pub revision: u32,
+    pub table_size: u32,
+    pub entry_count: u32,
+    pub entries: Vec<InjectionInstructionEntry>,
+}
+
+impl From<Bytes> for TriggerErrorAction {
+    fn from(mut buf: Bytes) -> Self {
+        let header_size = buf.get_u32_le();
+        let revision = buf.get_u32_le();
+        let table_size = buf.get_u32_le();
+        let entry_count = buf.get_u32_le();
+        let mut entries = vec![];
+
+        for d in buf.chunks_exact(32).take(entry_count as usize) {
+            let entry = Bytes::copy_from_slice(d);
+            entries.push(InjectionInstructionEntry::from(entry));
+        }
+
+        TriggerErrorAction {
+            header_size,
+            revision,
+            table_size,
+            entry_count,
+            entries,
+        }
+    }
+}
+
+impl From<TriggerErrorAction> for Bytes {
+    fn from(val: TriggerErrorAction) -> Self {
+        let mut b = BytesMut::with_capacity(val.table_size as usize);
+        b.put_u32_le(val.header_size);
+        b.put_u32_le(val.revision);
+        b.put_u32_le(val.table_size);
+        b.put_u32_le(val.entry_count);
+        for entry in val.entries {
+            b.put(Bytes::from(entry));
+        }
+        b.freeze()
+    }
+}
+
+impl ActionInterpreter for TriggerErrorAction {
+    type Action = InjectionAction;
+
+    fn instructions(&self, action: Self::Action) -> Vec<InstructionEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.injection_action == action)
+            .map(InstructionEntry::from)
+            .collect()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InjectionAction {
+    #[default]
+    BeginInjectionOperation,
+    GetTriggerErrorActionTable,
+    SetErrorType,
+    GetErrorType,
+    EndOperation,
+    ExecuteOperation,
+    CheckBusyStatus,
+    GetCommandStatus,
+    SetErrorTypeWithAddress,
+    GetExecuteOperationTimings,
+    TriggerError,
+    Reserved(u8),
+}
+
+impl From<u8> for InjectionAction {
+    fn from(value: u8) -> Self {
+        match value {
+            0x00 => InjectionAction::BeginInjectionOperation,
+            0x01 => InjectionAction::GetTriggerErrorActionTable,
+            0x02 => InjectionAction::SetErrorType,
+            0x03 => InjectionAction::GetErrorType,
+            0x04 => InjectionAction::EndOperation,
+            0x05 => InjectionAction::ExecuteOperation,
+            0x06 => InjectionAction::CheckBusyStatus,
+            0x07 => InjectionAction::GetCommandStatus,
+            0x08 => InjectionAction::SetErrorTypeWithAddress,
+            0x09 => InjectionAction::GetExecuteOperationTimings,
+            0xff => InjectionAction::TriggerError,
+            v => InjectionAction::Reserved(v),
+        }
+    }
+}
+
+impl From<InjectionAction> for u8 {
+    fn from(val: InjectionAction) -> Self {
+        match val {
+            InjectionAction::BeginInjectionOperation => 0x00,
+            InjectionAction::GetTriggerErrorActionTable => 0x01,
+            InjectionAction::SetErrorType => 0x02,
+            InjectionAction::GetErrorType => 0x03,
+            InjectionAction::EndOperation => 0x04,
+            InjectionAction::ExecuteOperation => 0x05,
+            InjectionAction::CheckBusyStatus => 0x06,
+            InjectionAction::GetCommandStatus => 0x07,
+            InjectionAction::SetErrorTypeWithAddress => 0x08,
+            InjectionAction::GetExecuteOperationTimings => 0x09,
+            InjectionAction::TriggerError => 0xff,
+            InjectionAction::Reserved(v) => v,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InjectionInstructionEntry {
+    pub injection_action: InjectionAction,
+    pub instruction: Instruction,
+    pub flags: u8,
+    pub reserved: u8,
+    pub register_region: GenericAddress,
+    pub value: u64,
+    pub mask: u64,
+}
+
+impl InjectionInstructionEntry {
+    pub fn preserve_register(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
+}
+
+impl From<Bytes> for InjectionInstructionEntry {
+    fn from(mut buf: Bytes) -> Self {
+        let injection_action = InjectionAction::from(buf.get_u8());
+        let instruction = Instruction::from(buf.get_u8());
+        let flags = buf.get_u8();
+        let reserved = buf.get_u8();
+        let register_region = GenericAddress::from(buf.split_to(12));
+        let value = buf.get_u64_le();
+        let mask = buf.get_u64_le();
+
+        InjectionInstructionEntry {
+            injection_action,
+            instruction,
+            flags,
+            reserved,
+            register_region,
+            value,
+            mask,
+        }
+    }
+}
+
+impl From<InjectionInstructionEntry> for Bytes {
+    fn from(val: InjectionInstructionEntry) -> Self {
+        let mut b = BytesMut::with_capacity(32);
+        b.put_u8(val.injection_action.into());
+        b.put_u8(val.instruction.into());
+        b.put_u8(val.flags);
+        b.put_u8(val.reserved);
+        b.put(Bytes::from(val.register_region));
+        b.put_u64_le(val.value);
+        b.put_u64_le(val.mask);
+        b.freeze()
+    }
+}
+
+impl Disassemble for InjectionInstructionEntry {
+    fn disassemble(&self, w: &mut Disassembler) {
+        let action = format!("{:?}", self.injection_action);
+        let instruction = format!("{:?}", self.instruction);
+        w.annotated("Action", 1, u8::from(self.injection_action) as u64, &action);
+        w.annotated(
+            "Instruction",
+            1,
+            u8::from(self.instruction) as u64,
+            &instruction,
+        );
+        w.u8("Flags (decoded below)", self.flags);
+        w.flag("Preserve Register Bits", self.flags & 0x01);
+        w.u8("Reserved", self.reserved);
+        w.gas("Register Region", &self.register_region);
+        w.u64("Value", self.value);
+        w.u64("Mask", self.mask);
+    }
+}
+
+impl From<&InjectionInstructionEntry> for InstructionEntry {
+    fn from(entry: &InjectionInstructionEntry) -> Self {
+        InstructionEntry {
+            instruction: entry.instruction,
+            flags: entry.flags,
+            register_region: entry.register_region,
+            value: entry.value,
+            mask: entry.mask,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AddressSpace;
+
+    #[test]
+    fn error_injection() {
+        let data = ErrorInjection {
+            signature: "EINJ".to_string(),
+            length: 80,
+            revision: 1,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            injection_header_size: 12,
+            injection_flags: 0,
+            reserved: [0; 3],
+            injection_entry_count: 1,
+            entries: vec![InjectionInstructionEntry {
+                injection_action: InjectionAction::SetErrorType,
+                instruction: Instruction::WriteRegister,
+                flags: 1,
+                reserved: 0,
+                register_region: GenericAddress {
+                    address_space_id: AddressSpace::SystemMemory,
+                    register_bit_width: 64,
+                    register_bit_offset: 0,
+                    access_size: 4,
+                    address: 0x3000,
+                },
+                value: 0,
+                mask: 0xffffffff,
+            }],
+        };
+        let b = Bytes::from(data.clone());
+        let raw = RawAcpiData::from(b);
+        let ret = ErrorInjection::from(raw);
+        assert_eq!(data, ret);
+    }
+}
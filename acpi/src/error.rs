@@ -1,17 +1,28 @@
 #[derive(Debug)]
 pub enum Error {
+    #[cfg(feature = "std")]
     Io(std::io::Error),
-    #[cfg(target_family = "windows")]
+    UnsupportedInstruction(u8),
+    InstructionLimit,
+    InvalidImage,
+    OutOfRange,
+    UnsupportedAddressSpace(u8),
+    ReadOnly,
+    InvalidSnapshot,
+    InvalidRsdp,
+    InvalidTable,
+    #[cfg(all(feature = "std", target_family = "windows"))]
     Win32(windows::core::Error),
 }
 
+#[cfg(feature = "std")]
 impl From<std::io::Error> for Error {
     fn from(error: std::io::Error) -> Self {
         Error::Io(error)
     }
 }
 
-#[cfg(target_family = "windows")]
+#[cfg(all(feature = "std", target_family = "windows"))]
 impl From<windows::core::Error> for Error {
     fn from(error: windows::core::Error) -> Self {
         Error::Win32(error)
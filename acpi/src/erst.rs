@@ -0,0 +1,819 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::error::Error;
+use super::gas::{AddressSpace, GenericAddress, RegisterAccess};
+use super::{RawAcpiData, string_to_array};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://uefi.org/specs/ACPI/6.5/18_Platform_Error_Interfaces.html#error-serialization
+
+const MAX_EXECUTION_STEPS: usize = 0x10000;
+// Error records are a few KiB; anything larger is a broken table rather than a real move.
+const MAX_MOVE_LENGTH: u64 = 1 << 20;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorRecordSerialization {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub serialization_header_size: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 4],
+    pub instruction_entry_count: u32,
+    pub entries: Vec<SerializationInstructionEntry>,
+}
+
+impl From<RawAcpiData> for ErrorRecordSerialization {
+    fn from(mut data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+        let serialization_header_size = data.acpi_table_data.get_u32_le();
+        let reserved = data.acpi_table_data.split_to(4)[..].try_into().unwrap();
+        let instruction_entry_count = data.acpi_table_data.get_u32_le();
+        let mut entries = vec![];
+
+        for d in data.acpi_table_data.chunks_exact(32) {
+            let entry = Bytes::copy_from_slice(d);
+            entries.push(SerializationInstructionEntry::from(entry));
+        }
+
+        ErrorRecordSerialization {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            serialization_header_size,
+            reserved,
+            instruction_entry_count,
+            entries,
+        }
+    }
+}
+
+impl From<ErrorRecordSerialization> for Bytes {
+    fn from(val: ErrorRecordSerialization) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        b.put_u32_le(val.serialization_header_size);
+        b.put_slice(&val.reserved);
+        b.put_u32_le(val.instruction_entry_count);
+        for entry in val.entries {
+            b.put(Bytes::from(entry));
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for ErrorRecordSerialization {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Error Record Serialization Table");
+        w.u32(
+            "Serialization Header Length",
+            self.serialization_header_size,
+        );
+        w.bytes("Reserved", &self.reserved);
+        w.u32("Instruction Entry Count", self.instruction_entry_count);
+        for entry in &self.entries {
+            w.blank();
+            entry.disassemble(w);
+        }
+    }
+}
+
+impl ActionInterpreter for ErrorRecordSerialization {
+    type Action = SerializationAction;
+
+    fn instructions(&self, action: Self::Action) -> Vec<InstructionEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.serialization_action == action)
+            .map(InstructionEntry::from)
+            .collect()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SerializationAction {
+    #[default]
+    BeginWriteOperation,
+    BeginReadOperation,
+    BeginClearOperation,
+    EndOperation,
+    SetRecordOffset,
+    ExecuteOperation,
+    CheckBusyStatus,
+    GetCommandStatus,
+    GetRecordIdentifier,
+    SetRecordIdentifier,
+    GetRecordCount,
+    BeginDummyWriteOperation,
+    GetErrorLogAddressRange,
+    GetErrorLogAddressRangeLength,
+    GetErrorLogAddressRangeAttributes,
+    GetExecuteOperationTimings,
+    Reserved(u8),
+}
+
+impl From<u8> for SerializationAction {
+    fn from(value: u8) -> Self {
+        match value {
+            0x00 => SerializationAction::BeginWriteOperation,
+            0x01 => SerializationAction::BeginReadOperation,
+            0x02 => SerializationAction::BeginClearOperation,
+            0x03 => SerializationAction::EndOperation,
+            0x04 => SerializationAction::SetRecordOffset,
+            0x05 => SerializationAction::ExecuteOperation,
+            0x06 => SerializationAction::CheckBusyStatus,
+            0x07 => SerializationAction::GetCommandStatus,
+            0x08 => SerializationAction::GetRecordIdentifier,
+            0x09 => SerializationAction::SetRecordIdentifier,
+            0x0a => SerializationAction::GetRecordCount,
+            0x0b => SerializationAction::BeginDummyWriteOperation,
+            0x0d => SerializationAction::GetErrorLogAddressRange,
+            0x0e => SerializationAction::GetErrorLogAddressRangeLength,
+            0x0f => SerializationAction::GetErrorLogAddressRangeAttributes,
+            0x10 => SerializationAction::GetExecuteOperationTimings,
+            v => SerializationAction::Reserved(v),
+        }
+    }
+}
+
+impl From<SerializationAction> for u8 {
+    fn from(val: SerializationAction) -> Self {
+        match val {
+            SerializationAction::BeginWriteOperation => 0x00,
+            SerializationAction::BeginReadOperation => 0x01,
+            SerializationAction::BeginClearOperation => 0x02,
+            SerializationAction::EndOperation => 0x03,
+            SerializationAction::SetRecordOffset => 0x04,
+            SerializationAction::ExecuteOperation => 0x05,
+            SerializationAction::CheckBusyStatus => 0x06,
+            SerializationAction::GetCommandStatus => 0x07,
+            SerializationAction::GetRecordIdentifier => 0x08,
+            SerializationAction::SetRecordIdentifier => 0x09,
+            SerializationAction::GetRecordCount => 0x0a,
+            SerializationAction::BeginDummyWriteOperation => 0x0b,
+            SerializationAction::GetErrorLogAddressRange => 0x0d,
+            SerializationAction::GetErrorLogAddressRangeLength => 0x0e,
+            SerializationAction::GetErrorLogAddressRangeAttributes => 0x0f,
+            SerializationAction::GetExecuteOperationTimings => 0x10,
+            SerializationAction::Reserved(v) => v,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Instruction {
+    #[default]
+    ReadRegister,
+    ReadRegisterValue,
+    WriteRegister,
+    WriteRegisterValue,
+    Noop,
+    LoadVar1,
+    LoadVar2,
+    StoreVar1,
+    Add,
+    Subtract,
+    AddValue,
+    SubtractValue,
+    Stall,
+    StallWhileTrue,
+    SkipNextInstructionIfTrue,
+    Goto,
+    SetSrcAddressBase,
+    SetDstAddressBase,
+    MoveData,
+    Reserved(u8),
+}
+
+impl From<u8> for Instruction {
+    fn from(value: u8) -> Self {
+        match value {
+            0x00 => Instruction::ReadRegister,
+            0x01 => Instruction::ReadRegisterValue,
+            0x02 => Instruction::WriteRegister,
+            0x03 => Instruction::WriteRegisterValue,
+            0x04 => Instruction::Noop,
+            0x05 => Instruction::LoadVar1,
+            0x06 => Instruction::LoadVar2,
+            0x07 => Instruction::StoreVar1,
+            0x08 => Instruction::Add,
+            0x09 => Instruction::Subtract,
+            0x0a => Instruction::AddValue,
+            0x0b => Instruction::SubtractValue,
+            0x0c => Instruction::Stall,
+            0x0d => Instruction::StallWhileTrue,
+            0x0e => Instruction::SkipNextInstructionIfTrue,
+            0x0f => Instruction::Goto,
+            0x10 => Instruction::SetSrcAddressBase,
+            0x11 => Instruction::SetDstAddressBase,
+            0x12 => Instruction::MoveData,
+            v => Instruction::Reserved(v),
+        }
+    }
+}
+
+impl From<Instruction> for u8 {
+    fn from(val: Instruction) -> Self {
+        match val {
+            Instruction::ReadRegister => 0x00,
+            Instruction::ReadRegisterValue => 0x01,
+            Instruction::WriteRegister => 0x02,
+            Instruction::WriteRegisterValue => 0x03,
+            Instruction::Noop => 0x04,
+            Instruction::LoadVar1 => 0x05,
+            Instruction::LoadVar2 => 0x06,
+            Instruction::StoreVar1 => 0x07,
+            Instruction::Add => 0x08,
+            Instruction::Subtract => 0x09,
+            Instruction::AddValue => 0x0a,
+            Instruction::SubtractValue => 0x0b,
+            Instruction::Stall => 0x0c,
+            Instruction::StallWhileTrue => 0x0d,
+            Instruction::SkipNextInstructionIfTrue => 0x0e,
+            Instruction::Goto => 0x0f,
+            Instruction::SetSrcAddressBase => 0x10,
+            Instruction::SetDstAddressBase => 0x11,
+            Instruction::MoveData => 0x12,
+            Instruction::Reserved(v) => v,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SerializationInstructionEntry {
+    pub serialization_action: SerializationAction,
+    pub instruction: Instruction,
+    pub flags: u8,
+    pub reserved: u8,
+    pub register_region: GenericAddress,
+    pub value: u64,
+    pub mask: u64,
+}
+
+impl SerializationInstructionEntry {
+    pub fn preserve_register(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
+}
+
+impl From<Bytes> for SerializationInstructionEntry {
+    fn from(mut buf: Bytes) -> Self {
+        let serialization_action = SerializationAction::from(buf.get_u8());
+        let instruction = Instruction::from(buf.get_u8());
+        let flags = buf.get_u8();
+        let reserved = buf.get_u8();
+        let register_region = GenericAddress::from(buf.split_to(12));
+        let value = buf.get_u64_le();
+        let mask = buf.get_u64_le();
+
+        SerializationInstructionEntry {
+            serialization_action,
+            instruction,
+            flags,
+            reserved,
+            register_region,
+            value,
+            mask,
+        }
+    }
+}
+
+impl From<SerializationInstructionEntry> for Bytes {
+    fn from(val: SerializationInstructionEntry) -> Self {
+        let mut b = BytesMut::with_capacity(32);
+        b.put_u8(val.serialization_action.into());
+        b.put_u8(val.instruction.into());
+        b.put_u8(val.flags);
+        b.put_u8(val.reserved);
+        b.put(Bytes::from(val.register_region));
+        b.put_u64_le(val.value);
+        b.put_u64_le(val.mask);
+        b.freeze()
+    }
+}
+
+impl Disassemble for SerializationInstructionEntry {
+    fn disassemble(&self, w: &mut Disassembler) {
+        let action = format!("{:?}", self.serialization_action);
+        let instruction = format!("{:?}", self.instruction);
+        w.annotated(
+            "Action",
+            1,
+            u8::from(self.serialization_action) as u64,
+            &action,
+        );
+        w.annotated(
+            "Instruction",
+            1,
+            u8::from(self.instruction) as u64,
+            &instruction,
+        );
+        w.u8("Flags (decoded below)", self.flags);
+        w.flag("Preserve Register Bits", self.flags & 0x01);
+        w.u8("Reserved", self.reserved);
+        w.gas("Register Region", &self.register_region);
+        w.u64("Value", self.value);
+        w.u64("Mask", self.mask);
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InstructionEntry {
+    pub instruction: Instruction,
+    pub flags: u8,
+    pub register_region: GenericAddress,
+    pub value: u64,
+    pub mask: u64,
+}
+
+impl From<&SerializationInstructionEntry> for InstructionEntry {
+    fn from(entry: &SerializationInstructionEntry) -> Self {
+        InstructionEntry {
+            instruction: entry.instruction,
+            flags: entry.flags,
+            register_region: entry.register_region,
+            value: entry.value,
+            mask: entry.mask,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionContext {
+    pub value: u64,
+    pub var1: u64,
+    pub var2: u64,
+    pub src_base: u64,
+    pub dst_base: u64,
+}
+
+pub trait ActionInterpreter {
+    type Action;
+
+    fn instructions(&self, action: Self::Action) -> Vec<InstructionEntry>;
+
+    fn execute<R>(
+        &self,
+        action: Self::Action,
+        register: &mut R,
+        context: &mut ExecutionContext,
+    ) -> Result<(), Error>
+    where
+        R: RegisterAccess,
+    {
+        let entries = self.instructions(action);
+
+        let mut ip = 0;
+        let mut steps = 0;
+        while let Some(entry) = entries.get(ip) {
+            steps += 1;
+            if steps > MAX_EXECUTION_STEPS {
+                return Err(Error::InstructionLimit);
+            }
+
+            ip += 1;
+
+            match entry.instruction {
+                Instruction::ReadRegister => {
+                    context.value = read_register(register, entry)?;
+                }
+                Instruction::ReadRegisterValue => {
+                    let value = read_register(register, entry)?;
+                    context.value = (value == entry.value) as u64;
+                }
+                Instruction::WriteRegister => {
+                    write_register(register, entry, context.value)?;
+                }
+                Instruction::WriteRegisterValue => {
+                    write_register(register, entry, entry.value)?;
+                }
+                Instruction::Noop => {}
+                Instruction::LoadVar1 => {
+                    context.var1 = read_register(register, entry)?;
+                }
+                Instruction::LoadVar2 => {
+                    context.var2 = read_register(register, entry)?;
+                }
+                Instruction::StoreVar1 => {
+                    write_register(register, entry, context.var1)?;
+                }
+                Instruction::Add => {
+                    context.var1 = context.var1.wrapping_add(context.var2);
+                }
+                Instruction::Subtract => {
+                    context.var1 = context.var1.wrapping_sub(context.var2);
+                }
+                Instruction::AddValue => {
+                    let value = read_register(register, entry)?;
+                    write_register(register, entry, value.wrapping_add(entry.value))?;
+                }
+                Instruction::SubtractValue => {
+                    let value = read_register(register, entry)?;
+                    write_register(register, entry, value.wrapping_sub(entry.value))?;
+                }
+                Instruction::Stall => {}
+                Instruction::StallWhileTrue => {
+                    while read_register(register, entry)? == entry.value {
+                        steps += 1;
+                        if steps > MAX_EXECUTION_STEPS {
+                            return Err(Error::InstructionLimit);
+                        }
+                    }
+                }
+                Instruction::SkipNextInstructionIfTrue => {
+                    if read_register(register, entry)? == entry.value {
+                        ip += 1;
+                    }
+                }
+                Instruction::Goto => {
+                    ip = entry.value as usize;
+                }
+                Instruction::SetSrcAddressBase => {
+                    context.src_base = read_register(register, entry)?;
+                }
+                Instruction::SetDstAddressBase => {
+                    context.dst_base = read_register(register, entry)?;
+                }
+                Instruction::MoveData => {
+                    let offset = read_register(register, entry)?;
+                    move_data(register, context, offset)?;
+                }
+                Instruction::Reserved(_) => {
+                    return Err(Error::UnsupportedInstruction(entry.instruction.into()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn read_register<R>(register: &mut R, entry: &InstructionEntry) -> Result<u64, Error>
+where
+    R: RegisterAccess,
+{
+    let value = register.read(&entry.register_region)?;
+    Ok((value >> entry.register_region.register_bit_offset) & entry.mask)
+}
+
+// Same as Linux `erst_exec_move_data`: VAR2 bytes go from SRC_BASE + offset to DST_BASE + offset.
+// The source is read in full first, so overlapping ranges behave like `memmove`.
+fn move_data<R>(register: &mut R, context: &ExecutionContext, offset: u64) -> Result<(), Error>
+where
+    R: RegisterAccess,
+{
+    if context.var2 > MAX_MOVE_LENGTH {
+        return Err(Error::InstructionLimit);
+    }
+
+    let byte = |address: u64| GenericAddress {
+        address_space_id: AddressSpace::SystemMemory,
+        register_bit_width: 8,
+        register_bit_offset: 0,
+        access_size: 1,
+        address,
+    };
+    let src = context.src_base.wrapping_add(offset);
+    let dst = context.dst_base.wrapping_add(offset);
+
+    let mut data = Vec::with_capacity(context.var2 as usize);
+    for i in 0..context.var2 {
+        data.push(register.read(&byte(src.wrapping_add(i)))?);
+    }
+    for (i, value) in data.into_iter().enumerate() {
+        register.write(&byte(dst.wrapping_add(i as u64)), value)?;
+    }
+
+    Ok(())
+}
+
+fn write_register<R>(register: &mut R, entry: &InstructionEntry, value: u64) -> Result<(), Error>
+where
+    R: RegisterAccess,
+{
+    let offset = entry.register_region.register_bit_offset;
+    let mut value = (value & entry.mask) << offset;
+    if entry.flags & 0x01 != 0 {
+        let current = register.read(&entry.register_region)?;
+        value |= current & !(entry.mask << offset);
+    }
+    register.write(&entry.register_region, value)
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct RegisterFile(HashMap<u64, u64>);
+
+    impl RegisterAccess for RegisterFile {
+        fn read(&mut self, register: &GenericAddress) -> Result<u64, Error> {
+            Ok(*self.0.get(&register.address).unwrap_or(&0))
+        }
+
+        fn write(&mut self, register: &GenericAddress, value: u64) -> Result<(), Error> {
+            self.0.insert(register.address, value);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn error_record_serialization() {
+        let data = ErrorRecordSerialization {
+            signature: "ERST".to_string(),
+            length: 80,
+            revision: 1,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            serialization_header_size: 48,
+            reserved: [0; 4],
+            instruction_entry_count: 1,
+            entries: vec![SerializationInstructionEntry {
+                serialization_action: SerializationAction::GetRecordCount,
+                instruction: Instruction::ReadRegister,
+                flags: 0,
+                reserved: 0,
+                register_region: GenericAddress {
+                    address_space_id: AddressSpace::SystemMemory,
+                    register_bit_width: 64,
+                    register_bit_offset: 0,
+                    access_size: 4,
+                    address: 0x1000,
+                },
+                value: 0,
+                mask: 0xffff,
+            }],
+        };
+        let b = Bytes::from(data.clone());
+        let raw = RawAcpiData::from(b);
+        let ret = ErrorRecordSerialization::from(raw);
+        assert_eq!(data, ret);
+    }
+
+    #[test]
+    fn action_interpreter() {
+        let data = ErrorRecordSerialization {
+            entries: vec![
+                SerializationInstructionEntry {
+                    serialization_action: SerializationAction::ExecuteOperation,
+                    instruction: Instruction::WriteRegisterValue,
+                    flags: 0x01,
+                    reserved: 0,
+                    register_region: GenericAddress {
+                        address_space_id: AddressSpace::SystemMemory,
+                        register_bit_width: 64,
+                        register_bit_offset: 0,
+                        access_size: 4,
+                        address: 0x2000,
+                    },
+                    value: 0x9c,
+                    mask: 0xff,
+                },
+                SerializationInstructionEntry {
+                    serialization_action: SerializationAction::CheckBusyStatus,
+                    instruction: Instruction::ReadRegisterValue,
+                    flags: 0,
+                    reserved: 0,
+                    register_region: GenericAddress {
+                        address_space_id: AddressSpace::SystemMemory,
+                        register_bit_width: 64,
+                        register_bit_offset: 0,
+                        access_size: 4,
+                        address: 0x2008,
+                    },
+                    value: 0x01,
+                    mask: 0x01,
+                },
+                SerializationInstructionEntry {
+                    serialization_action: SerializationAction::GetRecordCount,
+                    instruction: Instruction::LoadVar1,
+                    flags: 0,
+                    reserved: 0,
+                    register_region: GenericAddress {
+                        address_space_id: AddressSpace::SystemMemory,
+                        register_bit_width: 64,
+                        register_bit_offset: 0,
+                        access_size: 4,
+                        address: 0x2010,
+                    },
+                    value: 0,
+                    mask: 0xffff,
+                },
+                SerializationInstructionEntry {
+                    serialization_action: SerializationAction::GetRecordCount,
+                    instruction: Instruction::AddValue,
+                    flags: 0,
+                    reserved: 0,
+                    register_region: GenericAddress {
+                        address_space_id: AddressSpace::SystemMemory,
+                        register_bit_width: 64,
+                        register_bit_offset: 0,
+                        access_size: 4,
+                        address: 0x2010,
+                    },
+                    value: 2,
+                    mask: 0xffff,
+                },
+                SerializationInstructionEntry {
+                    serialization_action: SerializationAction::GetRecordCount,
+                    instruction: Instruction::ReadRegister,
+                    flags: 0,
+                    reserved: 0,
+                    register_region: GenericAddress {
+                        address_space_id: AddressSpace::SystemMemory,
+                        register_bit_width: 64,
+                        register_bit_offset: 0,
+                        access_size: 4,
+                        address: 0x2010,
+                    },
+                    value: 0,
+                    mask: 0xffff,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut register = RegisterFile::default();
+        register.0.insert(0x2000, 0xabcd_0000);
+        register.0.insert(0x2008, 0x01);
+        register.0.insert(0x2010, 5);
+
+        let mut context = ExecutionContext::default();
+        data.execute(
+            SerializationAction::ExecuteOperation,
+            &mut register,
+            &mut context,
+        )
+        .unwrap();
+        assert_eq!(register.0[&0x2000], 0xabcd_009c);
+
+        data.execute(
+            SerializationAction::CheckBusyStatus,
+            &mut register,
+            &mut context,
+        )
+        .unwrap();
+        assert_eq!(context.value, 1);
+
+        data.execute(
+            SerializationAction::GetRecordCount,
+            &mut register,
+            &mut context,
+        )
+        .unwrap();
+        assert_eq!(context.var1, 5);
+        assert_eq!(context.value, 7);
+    }
+
+    #[test]
+    fn move_data() {
+        let data = ErrorRecordSerialization {
+            entries: vec![
+                SerializationInstructionEntry {
+                    serialization_action: SerializationAction::BeginWriteOperation,
+                    instruction: Instruction::SetSrcAddressBase,
+                    flags: 0,
+                    reserved: 0,
+                    register_region: GenericAddress {
+                        address_space_id: AddressSpace::SystemMemory,
+                        register_bit_width: 64,
+                        register_bit_offset: 0,
+                        access_size: 4,
+                        address: 0x3000,
+                    },
+                    value: 0,
+                    mask: 0xffff,
+                },
+                SerializationInstructionEntry {
+                    serialization_action: SerializationAction::BeginWriteOperation,
+                    instruction: Instruction::SetDstAddressBase,
+                    flags: 0,
+                    reserved: 0,
+                    register_region: GenericAddress {
+                        address_space_id: AddressSpace::SystemMemory,
+                        register_bit_width: 64,
+                        register_bit_offset: 0,
+                        access_size: 4,
+                        address: 0x3008,
+                    },
+                    value: 0,
+                    mask: 0xffff,
+                },
+                SerializationInstructionEntry {
+                    serialization_action: SerializationAction::BeginWriteOperation,
+                    instruction: Instruction::LoadVar2,
+                    flags: 0,
+                    reserved: 0,
+                    register_region: GenericAddress {
+                        address_space_id: AddressSpace::SystemMemory,
+                        register_bit_width: 64,
+                        register_bit_offset: 0,
+                        access_size: 4,
+                        address: 0x3010,
+                    },
+                    value: 0,
+                    mask: 0xffff,
+                },
+                SerializationInstructionEntry {
+                    serialization_action: SerializationAction::BeginWriteOperation,
+                    instruction: Instruction::MoveData,
+                    flags: 0,
+                    reserved: 0,
+                    register_region: GenericAddress {
+                        address_space_id: AddressSpace::SystemMemory,
+                        register_bit_width: 64,
+                        register_bit_offset: 0,
+                        access_size: 4,
+                        address: 0x3018,
+                    },
+                    value: 0,
+                    mask: 0xffff,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut register = RegisterFile::default();
+        register.0.insert(0x3000, 0x100);
+        register.0.insert(0x3008, 0x200);
+        register.0.insert(0x3010, 3);
+        register.0.insert(0x3018, 0x10);
+        for (i, b) in [1, 2, 3, 4].into_iter().enumerate() {
+            register.0.insert(0x110 + i as u64, b);
+        }
+
+        let mut context = ExecutionContext::default();
+        data.execute(
+            SerializationAction::BeginWriteOperation,
+            &mut register,
+            &mut context,
+        )
+        .unwrap();
+        assert_eq!(register.0[&0x210], 1);
+        assert_eq!(register.0[&0x212], 3);
+        assert!(!register.0.contains_key(&0x213));
+
+        context.var2 = MAX_MOVE_LENGTH + 1;
+        assert!(matches!(
+            super::move_data(&mut register, &context, 0),
+            Err(Error::InstructionLimit)
+        ));
+    }
+}
@@ -0,0 +1,738 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::{RawAcpiData, extract_string, string_to_array};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://uefi.org/specs/ACPI/6.5/05_ACPI_Software_Programming_Model.html#firmware-performance-data-table-fpdt
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FirmwarePerformanceData {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub records: Vec<FirmwarePerformanceRecord>,
+}
+
+impl FirmwarePerformanceData {
+    pub fn basic_boot_pointer(&self) -> Option<u64> {
+        self.records.iter().find_map(|r| match r {
+            FirmwarePerformanceRecord::BasicBootPointer(p) => Some(p.address),
+            _ => None,
+        })
+    }
+
+    pub fn s3_pointer(&self) -> Option<u64> {
+        self.records.iter().find_map(|r| match r {
+            FirmwarePerformanceRecord::S3Pointer(p) => Some(p.address),
+            _ => None,
+        })
+    }
+}
+
+impl From<RawAcpiData> for FirmwarePerformanceData {
+    fn from(data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+        let records = split_records(data.acpi_table_data)
+            .into_iter()
+            .map(FirmwarePerformanceRecord::from)
+            .collect();
+
+        FirmwarePerformanceData {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            records,
+        }
+    }
+}
+
+impl From<FirmwarePerformanceData> for Bytes {
+    fn from(val: FirmwarePerformanceData) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        for record in val.records {
+            b.put(Bytes::from(record));
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for FirmwarePerformanceData {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Firmware Performance Data Table");
+        for record in &self.records {
+            record.disassemble(w);
+            w.blank();
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PerformanceRecord {
+    pub record_type: u16,
+    pub record_length: u8,
+    pub revision: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub data: Bytes,
+}
+
+impl From<PerformanceRecord> for Bytes {
+    fn from(val: PerformanceRecord) -> Self {
+        let mut b = BytesMut::with_capacity(val.record_length as usize);
+        b.put_u16_le(val.record_type);
+        b.put_u8(val.record_length);
+        b.put_u8(val.revision);
+        b.put(val.data);
+        b.freeze()
+    }
+}
+
+fn split_records(mut buf: Bytes) -> Vec<PerformanceRecord> {
+    let mut records = vec![];
+    while buf.len() >= 4 {
+        let record_type = buf.get_u16_le();
+        let record_length = buf.get_u8();
+        let revision = buf.get_u8();
+        if record_length < 4 {
+            break;
+        }
+
+        let data = buf.split_to((record_length as usize - 4).min(buf.len()));
+        records.push(PerformanceRecord {
+            record_type,
+            record_length,
+            revision,
+            data,
+        });
+    }
+    records
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FirmwarePerformanceRecord {
+    BasicBootPointer(PerformanceTablePointer),
+    S3Pointer(PerformanceTablePointer),
+    Unknown(PerformanceRecord),
+}
+
+impl From<PerformanceRecord> for FirmwarePerformanceRecord {
+    fn from(record: PerformanceRecord) -> Self {
+        match record.record_type {
+            0x0000 if record.data.len() >= 12 => {
+                FirmwarePerformanceRecord::BasicBootPointer(PerformanceTablePointer::from(record))
+            }
+            0x0001 if record.data.len() >= 12 => {
+                FirmwarePerformanceRecord::S3Pointer(PerformanceTablePointer::from(record))
+            }
+            _ => FirmwarePerformanceRecord::Unknown(record),
+        }
+    }
+}
+
+impl From<FirmwarePerformanceRecord> for Bytes {
+    fn from(val: FirmwarePerformanceRecord) -> Self {
+        match val {
+            FirmwarePerformanceRecord::BasicBootPointer(v) => v.into_record(0x0000).into(),
+            FirmwarePerformanceRecord::S3Pointer(v) => v.into_record(0x0001).into(),
+            FirmwarePerformanceRecord::Unknown(v) => v.into(),
+        }
+    }
+}
+
+impl Disassemble for FirmwarePerformanceRecord {
+    fn disassemble(&self, w: &mut Disassembler) {
+        let (record_type, name, pointer) = match self {
+            FirmwarePerformanceRecord::BasicBootPointer(v) => {
+                (0x0000, "Firmware Basic Boot Performance Record", v)
+            }
+            FirmwarePerformanceRecord::S3Pointer(v) => (0x0001, "S3 Performance Table Record", v),
+            FirmwarePerformanceRecord::Unknown(v) => {
+                w.annotated("Subtable Type", 2, v.record_type as u64, "Unknown");
+                w.u8("Length", v.record_length);
+                w.u8("Revision", v.revision);
+                w.bytes("Data", &v.data);
+                return;
+            }
+        };
+        w.annotated("Subtable Type", 2, record_type, name);
+        w.u8("Length", 16);
+        w.u8("Revision", pointer.revision);
+        w.bytes("Reserved", &pointer.reserved);
+        w.u64("Pointer", pointer.address);
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PerformanceTablePointer {
+    pub revision: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 4],
+    pub address: u64,
+}
+
+impl PerformanceTablePointer {
+    fn into_record(self, record_type: u16) -> PerformanceRecord {
+        let mut b = BytesMut::with_capacity(12);
+        b.put_slice(&self.reserved);
+        b.put_u64_le(self.address);
+
+        PerformanceRecord {
+            record_type,
+            record_length: 16,
+            revision: self.revision,
+            data: b.freeze(),
+        }
+    }
+}
+
+impl From<PerformanceRecord> for PerformanceTablePointer {
+    fn from(mut record: PerformanceRecord) -> Self {
+        let revision = record.revision;
+        let reserved = record.data.split_to(4)[..].try_into().unwrap();
+        let address = record.data.get_u64_le();
+
+        PerformanceTablePointer {
+            revision,
+            reserved,
+            address,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FirmwareBasicBootPerformance {
+    pub signature: String,
+    pub length: u32,
+    pub records: Vec<BootPerformanceRecord>,
+}
+
+impl FirmwareBasicBootPerformance {
+    pub fn basic_boot(&self) -> Option<&BasicBootPerformanceData> {
+        self.records.iter().find_map(|r| match r {
+            BootPerformanceRecord::BasicBoot(v) => Some(v),
+            _ => None,
+        })
+    }
+}
+
+impl From<Bytes> for FirmwareBasicBootPerformance {
+    fn from(mut buf: Bytes) -> Self {
+        let signature = extract_string::<4>(&mut buf);
+        let length = buf.get_u32_le();
+        let body = buf.split_to((length as usize).saturating_sub(8).min(buf.len()));
+        let records = split_records(body)
+            .into_iter()
+            .map(BootPerformanceRecord::from)
+            .collect();
+
+        FirmwareBasicBootPerformance {
+            signature,
+            length,
+            records,
+        }
+    }
+}
+
+impl From<FirmwareBasicBootPerformance> for Bytes {
+    fn from(val: FirmwareBasicBootPerformance) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        for record in val.records {
+            b.put(Bytes::from(record));
+        }
+        b.freeze()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BootPerformanceRecord {
+    BasicBoot(BasicBootPerformanceData),
+    Unknown(PerformanceRecord),
+}
+
+impl From<PerformanceRecord> for BootPerformanceRecord {
+    fn from(record: PerformanceRecord) -> Self {
+        match record.record_type {
+            0x0002 if record.data.len() >= 44 => {
+                BootPerformanceRecord::BasicBoot(BasicBootPerformanceData::from(record))
+            }
+            _ => BootPerformanceRecord::Unknown(record),
+        }
+    }
+}
+
+impl From<BootPerformanceRecord> for Bytes {
+    fn from(val: BootPerformanceRecord) -> Self {
+        match val {
+            BootPerformanceRecord::BasicBoot(v) => PerformanceRecord::from(v).into(),
+            BootPerformanceRecord::Unknown(v) => v.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BasicBootPerformanceData {
+    pub revision: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 4],
+    pub reset_end: u64,
+    pub os_loader_load_image_start: u64,
+    pub os_loader_start_image_start: u64,
+    pub exit_boot_services_entry: u64,
+    pub exit_boot_services_exit: u64,
+}
+
+impl From<PerformanceRecord> for BasicBootPerformanceData {
+    fn from(mut record: PerformanceRecord) -> Self {
+        let revision = record.revision;
+        let reserved = record.data.split_to(4)[..].try_into().unwrap();
+        let reset_end = record.data.get_u64_le();
+        let os_loader_load_image_start = record.data.get_u64_le();
+        let os_loader_start_image_start = record.data.get_u64_le();
+        let exit_boot_services_entry = record.data.get_u64_le();
+        let exit_boot_services_exit = record.data.get_u64_le();
+
+        BasicBootPerformanceData {
+            revision,
+            reserved,
+            reset_end,
+            os_loader_load_image_start,
+            os_loader_start_image_start,
+            exit_boot_services_entry,
+            exit_boot_services_exit,
+        }
+    }
+}
+
+impl From<BasicBootPerformanceData> for PerformanceRecord {
+    fn from(val: BasicBootPerformanceData) -> Self {
+        let mut b = BytesMut::with_capacity(44);
+        b.put_slice(&val.reserved);
+        b.put_u64_le(val.reset_end);
+        b.put_u64_le(val.os_loader_load_image_start);
+        b.put_u64_le(val.os_loader_start_image_start);
+        b.put_u64_le(val.exit_boot_services_entry);
+        b.put_u64_le(val.exit_boot_services_exit);
+
+        PerformanceRecord {
+            record_type: 0x0002,
+            record_length: 48,
+            revision: val.revision,
+            data: b.freeze(),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct S3Performance {
+    pub signature: String,
+    pub length: u32,
+    pub records: Vec<S3PerformanceRecord>,
+}
+
+impl S3Performance {
+    pub fn resume(&self) -> Option<&S3ResumePerformanceData> {
+        self.records.iter().find_map(|r| match r {
+            S3PerformanceRecord::Resume(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    pub fn suspend(&self) -> Option<&S3SuspendPerformanceData> {
+        self.records.iter().find_map(|r| match r {
+            S3PerformanceRecord::Suspend(v) => Some(v),
+            _ => None,
+        })
+    }
+}
+
+impl From<Bytes> for S3Performance {
+    fn from(mut buf: Bytes) -> Self {
+        let signature = extract_string::<4>(&mut buf);
+        let length = buf.get_u32_le();
+        let body = buf.split_to((length as usize).saturating_sub(8).min(buf.len()));
+        let records = split_records(body)
+            .into_iter()
+            .map(S3PerformanceRecord::from)
+            .collect();
+
+        S3Performance {
+            signature,
+            length,
+            records,
+        }
+    }
+}
+
+impl From<S3Performance> for Bytes {
+    fn from(val: S3Performance) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        for record in val.records {
+            b.put(Bytes::from(record));
+        }
+        b.freeze()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum S3PerformanceRecord {
+    Resume(S3ResumePerformanceData),
+    Suspend(S3SuspendPerformanceData),
+    Unknown(PerformanceRecord),
+}
+
+impl From<PerformanceRecord> for S3PerformanceRecord {
+    fn from(record: PerformanceRecord) -> Self {
+        match record.record_type {
+            0x0000 if record.data.len() >= 20 => {
+                S3PerformanceRecord::Resume(S3ResumePerformanceData::from(record))
+            }
+            0x0001 if record.data.len() >= 16 => {
+                S3PerformanceRecord::Suspend(S3SuspendPerformanceData::from(record))
+            }
+            _ => S3PerformanceRecord::Unknown(record),
+        }
+    }
+}
+
+impl From<S3PerformanceRecord> for Bytes {
+    fn from(val: S3PerformanceRecord) -> Self {
+        match val {
+            S3PerformanceRecord::Resume(v) => PerformanceRecord::from(v).into(),
+            S3PerformanceRecord::Suspend(v) => PerformanceRecord::from(v).into(),
+            S3PerformanceRecord::Unknown(v) => v.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct S3ResumePerformanceData {
+    pub revision: u8,
+    pub resume_count: u32,
+    pub full_resume: u64,
+    pub average_resume: u64,
+}
+
+impl From<PerformanceRecord> for S3ResumePerformanceData {
+    fn from(mut record: PerformanceRecord) -> Self {
+        let revision = record.revision;
+        let resume_count = record.data.get_u32_le();
+        let full_resume = record.data.get_u64_le();
+        let average_resume = record.data.get_u64_le();
+
+        S3ResumePerformanceData {
+            revision,
+            resume_count,
+            full_resume,
+            average_resume,
+        }
+    }
+}
+
+impl From<S3ResumePerformanceData> for PerformanceRecord {
+    fn from(val: S3ResumePerformanceData) -> Self {
+        let mut b = BytesMut::with_capacity(20);
+        b.put_u32_le(val.resume_count);
+        b.put_u64_le(val.full_resume);
+        b.put_u64_le(val.average_resume);
+
+        PerformanceRecord {
+            record_type: 0x0000,
+            record_length: 24,
+            revision: val.revision,
+            data: b.freeze(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct S3SuspendPerformanceData {
+    pub revision: u8,
+    pub suspend_start: u64,
+    pub suspend_end: u64,
+}
+
+impl From<PerformanceRecord> for S3SuspendPerformanceData {
+    fn from(mut record: PerformanceRecord) -> Self {
+        let revision = record.revision;
+        let suspend_start = record.data.get_u64_le();
+        let suspend_end = record.data.get_u64_le();
+
+        S3SuspendPerformanceData {
+            revision,
+            suspend_start,
+            suspend_end,
+        }
+    }
+}
+
+impl From<S3SuspendPerformanceData> for PerformanceRecord {
+    fn from(val: S3SuspendPerformanceData) -> Self {
+        let mut b = BytesMut::with_capacity(16);
+        b.put_u64_le(val.suspend_start);
+        b.put_u64_le(val.suspend_end);
+
+        PerformanceRecord {
+            record_type: 0x0001,
+            record_length: 20,
+            revision: val.revision,
+            data: b.freeze(),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+// All values are in nanoseconds.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BootTimeline {
+    pub reset_end: Option<u64>,
+    pub os_loader_load_image_start: Option<u64>,
+    pub os_loader_start_image_start: Option<u64>,
+    pub exit_boot_services_entry: Option<u64>,
+    pub exit_boot_services_exit: Option<u64>,
+    pub resume_count: Option<u32>,
+    pub full_resume: Option<u64>,
+    pub average_resume: Option<u64>,
+    pub suspend_start: Option<u64>,
+    pub suspend_end: Option<u64>,
+}
+
+impl BootTimeline {
+    pub fn new(fbpt: Option<&FirmwareBasicBootPerformance>, s3pt: Option<&S3Performance>) -> Self {
+        let mut timeline = BootTimeline::default();
+
+        if let Some(boot) = fbpt.and_then(|t| t.basic_boot()) {
+            timeline.reset_end = Some(boot.reset_end);
+            timeline.os_loader_load_image_start = Some(boot.os_loader_load_image_start);
+            timeline.os_loader_start_image_start = Some(boot.os_loader_start_image_start);
+            timeline.exit_boot_services_entry = Some(boot.exit_boot_services_entry);
+            timeline.exit_boot_services_exit = Some(boot.exit_boot_services_exit);
+        }
+
+        if let Some(resume) = s3pt.and_then(|t| t.resume()) {
+            timeline.resume_count = Some(resume.resume_count);
+            timeline.full_resume = Some(resume.full_resume);
+            timeline.average_resume = Some(resume.average_resume);
+        }
+
+        if let Some(suspend) = s3pt.and_then(|t| t.suspend()) {
+            timeline.suspend_start = Some(suspend.suspend_start);
+            timeline.suspend_end = Some(suspend.suspend_end);
+        }
+
+        timeline
+    }
+
+    pub fn firmware_duration(&self) -> Option<u64> {
+        self.os_loader_start_image_start?
+            .checked_sub(self.reset_end?)
+    }
+
+    pub fn os_loader_duration(&self) -> Option<u64> {
+        self.exit_boot_services_entry?
+            .checked_sub(self.os_loader_start_image_start?)
+    }
+
+    pub fn exit_boot_services_duration(&self) -> Option<u64> {
+        self.exit_boot_services_exit?
+            .checked_sub(self.exit_boot_services_entry?)
+    }
+
+    pub fn suspend_duration(&self) -> Option<u64> {
+        self.suspend_end?.checked_sub(self.suspend_start?)
+    }
+
+    pub fn events(&self) -> Vec<(&'static str, u64)> {
+        let mut events = vec![];
+        let candidates = [
+            ("ResetEnd", self.reset_end),
+            ("OsLoaderLoadImageStart", self.os_loader_load_image_start),
+            ("OsLoaderStartImageStart", self.os_loader_start_image_start),
+            ("ExitBootServicesEntry", self.exit_boot_services_entry),
+            ("ExitBootServicesExit", self.exit_boot_services_exit),
+        ];
+        for (name, value) in candidates {
+            if let Some(v) = value {
+                events.push((name, v));
+            }
+        }
+        events.sort_by_key(|(_, v)| *v);
+        events
+    }
+
+    // Resume times are durations rather than timestamps, so these are kept in record order
+    // instead of being mixed into `events`. The resume count is a plain field.
+    pub fn s3_events(&self) -> Vec<(&'static str, u64)> {
+        let candidates = [
+            ("FullResume", self.full_resume),
+            ("AverageResume", self.average_resume),
+            ("SuspendStart", self.suspend_start),
+            ("SuspendEnd", self.suspend_end),
+        ];
+        candidates
+            .into_iter()
+            .filter_map(|(name, value)| Some((name, value?)))
+            .collect()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn firmware_performance_data() {
+        let data = FirmwarePerformanceData {
+            signature: "FPDT".to_string(),
+            length: 68,
+            revision: 1,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            records: vec![
+                FirmwarePerformanceRecord::BasicBootPointer(PerformanceTablePointer {
+                    revision: 1,
+                    reserved: [0; 4],
+                    address: 0x7f000000,
+                }),
+                FirmwarePerformanceRecord::S3Pointer(PerformanceTablePointer {
+                    revision: 1,
+                    reserved: [0; 4],
+                    address: 0x7f001000,
+                }),
+            ],
+        };
+        let b = Bytes::from(data.clone());
+        let raw = RawAcpiData::from(b);
+        let ret = FirmwarePerformanceData::from(raw);
+        assert_eq!(data, ret);
+        assert_eq!(ret.basic_boot_pointer(), Some(0x7f000000));
+        assert_eq!(ret.s3_pointer(), Some(0x7f001000));
+    }
+
+    #[test]
+    fn boot_timeline() {
+        let fbpt = FirmwareBasicBootPerformance {
+            signature: "FBPT".to_string(),
+            length: 56,
+            records: vec![BootPerformanceRecord::BasicBoot(BasicBootPerformanceData {
+                revision: 2,
+                reserved: [0; 4],
+                reset_end: 100,
+                os_loader_load_image_start: 200,
+                os_loader_start_image_start: 300,
+                exit_boot_services_entry: 500,
+                exit_boot_services_exit: 550,
+            })],
+        };
+        let s3pt = S3Performance {
+            signature: "S3PT".to_string(),
+            length: 52,
+            records: vec![
+                S3PerformanceRecord::Resume(S3ResumePerformanceData {
+                    revision: 1,
+                    resume_count: 3,
+                    full_resume: 1000,
+                    average_resume: 900,
+                }),
+                S3PerformanceRecord::Suspend(S3SuspendPerformanceData {
+                    revision: 1,
+                    suspend_start: 10,
+                    suspend_end: 40,
+                }),
+            ],
+        };
+
+        let fbpt_ret = FirmwareBasicBootPerformance::from(Bytes::from(fbpt.clone()));
+        assert_eq!(fbpt, fbpt_ret);
+        let s3pt_ret = S3Performance::from(Bytes::from(s3pt.clone()));
+        assert_eq!(s3pt, s3pt_ret);
+
+        let timeline = BootTimeline::new(Some(&fbpt_ret), Some(&s3pt_ret));
+        assert_eq!(timeline.firmware_duration(), Some(200));
+        assert_eq!(timeline.exit_boot_services_duration(), Some(50));
+        assert_eq!(timeline.suspend_duration(), Some(30));
+        assert_eq!(timeline.resume_count, Some(3));
+        assert_eq!(timeline.events().len(), 5);
+        assert_eq!(
+            timeline.s3_events(),
+            vec![
+                ("FullResume", 1000),
+                ("AverageResume", 900),
+                ("SuspendStart", 10),
+                ("SuspendEnd", 40),
+            ]
+        );
+
+        let timeline = BootTimeline::new(Some(&fbpt_ret), None);
+        assert_eq!(timeline.resume_count, None);
+        assert_eq!(timeline.s3_events(), vec![]);
+    }
+}
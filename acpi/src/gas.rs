@@ -0,0 +1,478 @@
+use super::error::Error;
+use super::pci::PciConfigReader;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::io::{Read, Seek, SeekFrom, Write};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AddressSpace {
+    #[default]
+    SystemMemory,
+    SystemIo,
+    PciConfiguration,
+    EmbeddedController,
+    SmBus,
+    SystemCmos,
+    PciBarTarget,
+    Ipmi,
+    GeneralPurposeIo,
+    GenericSerialBus,
+    PlatformCommunicationsChannel,
+    PlatformRuntimeMechanism,
+    FunctionalFixedHardware,
+    Reserved(u8),
+}
+
+impl From<u8> for AddressSpace {
+    fn from(value: u8) -> Self {
+        match value {
+            0x00 => AddressSpace::SystemMemory,
+            0x01 => AddressSpace::SystemIo,
+            0x02 => AddressSpace::PciConfiguration,
+            0x03 => AddressSpace::EmbeddedController,
+            0x04 => AddressSpace::SmBus,
+            0x05 => AddressSpace::SystemCmos,
+            0x06 => AddressSpace::PciBarTarget,
+            0x07 => AddressSpace::Ipmi,
+            0x08 => AddressSpace::GeneralPurposeIo,
+            0x09 => AddressSpace::GenericSerialBus,
+            0x0a => AddressSpace::PlatformCommunicationsChannel,
+            0x0b => AddressSpace::PlatformRuntimeMechanism,
+            0x7f => AddressSpace::FunctionalFixedHardware,
+            v => AddressSpace::Reserved(v),
+        }
+    }
+}
+
+impl From<AddressSpace> for u8 {
+    fn from(val: AddressSpace) -> Self {
+        match val {
+            AddressSpace::SystemMemory => 0x00,
+            AddressSpace::SystemIo => 0x01,
+            AddressSpace::PciConfiguration => 0x02,
+            AddressSpace::EmbeddedController => 0x03,
+            AddressSpace::SmBus => 0x04,
+            AddressSpace::SystemCmos => 0x05,
+            AddressSpace::PciBarTarget => 0x06,
+            AddressSpace::Ipmi => 0x07,
+            AddressSpace::GeneralPurposeIo => 0x08,
+            AddressSpace::GenericSerialBus => 0x09,
+            AddressSpace::PlatformCommunicationsChannel => 0x0a,
+            AddressSpace::PlatformRuntimeMechanism => 0x0b,
+            AddressSpace::FunctionalFixedHardware => 0x7f,
+            AddressSpace::Reserved(v) => v,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenericAddress {
+    pub address_space_id: AddressSpace,
+    pub register_bit_width: u8,
+    pub register_bit_offset: u8,
+    pub access_size: u8,
+    pub address: u64,
+}
+
+impl GenericAddress {
+    pub const LENGTH: usize = 12;
+
+    // A zero address means the register is not implemented.
+    pub fn is_null(&self) -> bool {
+        self.address == 0
+    }
+
+    // Access width in bytes. Legacy tables leave access_size at 0, in which case the width is
+    // derived from the register bit width and offset.
+    pub fn access_width(&self) -> usize {
+        match self.access_size {
+            1..=4 => 1 << (self.access_size - 1),
+            _ => {
+                let bits = self.register_bit_width as usize + self.register_bit_offset as usize;
+                bits.div_ceil(8).clamp(1, 8).next_power_of_two()
+            }
+        }
+    }
+
+    // PCI configuration space registers are limited to segment 0, bus 0. The device and function
+    // fields are 16 bits wide, so values no device can have are rejected rather than truncated.
+    pub fn pci_location(&self) -> Option<(u8, u8, u16)> {
+        let device = u8::try_from(self.address >> 32).ok().filter(|d| *d < 32)?;
+        let function = (self.address >> 16) as u16;
+        let function = u8::try_from(function).ok().filter(|f| *f < 8)?;
+        let offset = self.address as u16;
+        Some((device, function, offset))
+    }
+}
+
+impl From<Bytes> for GenericAddress {
+    fn from(mut buf: Bytes) -> Self {
+        let address_space_id = AddressSpace::from(buf.get_u8());
+        let register_bit_width = buf.get_u8();
+        let register_bit_offset = buf.get_u8();
+        let access_size = buf.get_u8();
+        let address = buf.get_u64_le();
+
+        GenericAddress {
+            address_space_id,
+            register_bit_width,
+            register_bit_offset,
+            access_size,
+            address,
+        }
+    }
+}
+
+impl From<GenericAddress> for Bytes {
+    fn from(val: GenericAddress) -> Self {
+        let mut b = BytesMut::with_capacity(GenericAddress::LENGTH);
+        b.put_u8(val.address_space_id.into());
+        b.put_u8(val.register_bit_width);
+        b.put_u8(val.register_bit_offset);
+        b.put_u8(val.access_size);
+        b.put_u64_le(val.address);
+        b.freeze()
+    }
+}
+
+impl From<[u8; 12]> for GenericAddress {
+    fn from(value: [u8; 12]) -> Self {
+        GenericAddress::from(Bytes::copy_from_slice(&value))
+    }
+}
+
+impl From<GenericAddress> for [u8; 12] {
+    fn from(val: GenericAddress) -> Self {
+        Bytes::from(val)[..].try_into().unwrap()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+pub trait RegisterAccess {
+    fn read(&mut self, register: &GenericAddress) -> Result<u64, Error>;
+
+    fn write(&mut self, register: &GenericAddress, value: u64) -> Result<(), Error>;
+}
+
+fn check_space(register: &GenericAddress, space: AddressSpace) -> Result<usize, Error> {
+    if register.address_space_id != space {
+        return Err(Error::UnsupportedAddressSpace(
+            register.address_space_id.into(),
+        ));
+    }
+    Ok(register.access_width())
+}
+
+fn to_value(buf: &[u8]) -> u64 {
+    let mut value = [0u8; 8];
+    value[..buf.len()].copy_from_slice(buf);
+    u64::from_le_bytes(value)
+}
+
+// System memory registers backed by a seekable file, e.g. `/dev/mem` or an in-memory image.
+#[cfg(feature = "std")]
+pub struct SystemMemory<F> {
+    file: F,
+}
+
+#[cfg(feature = "std")]
+impl<F> SystemMemory<F>
+where
+    F: Read + Write + Seek,
+{
+    pub fn new(file: F) -> Self {
+        SystemMemory { file }
+    }
+
+    pub fn into_inner(self) -> F {
+        self.file
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F> RegisterAccess for SystemMemory<F>
+where
+    F: Read + Write + Seek,
+{
+    fn read(&mut self, register: &GenericAddress) -> Result<u64, Error> {
+        let width = check_space(register, AddressSpace::SystemMemory)?;
+        let mut buf = [0u8; 8];
+        self.file.seek(SeekFrom::Start(register.address))?;
+        self.file.read_exact(&mut buf[..width])?;
+        Ok(to_value(&buf[..width]))
+    }
+
+    fn write(&mut self, register: &GenericAddress, value: u64) -> Result<(), Error> {
+        let width = check_space(register, AddressSpace::SystemMemory)?;
+        self.file.seek(SeekFrom::Start(register.address))?;
+        self.file.write_all(&value.to_le_bytes()[..width])?;
+        Ok(())
+    }
+}
+
+// A 64 KiB port space held in memory, for exercising I/O registers without touching hardware.
+pub struct MockIoPorts {
+    pub ports: Vec<u8>,
+}
+
+impl Default for MockIoPorts {
+    fn default() -> Self {
+        MockIoPorts {
+            ports: vec![0; 0x10000],
+        }
+    }
+}
+
+impl MockIoPorts {
+    fn range(register: &GenericAddress) -> Result<Range<usize>, Error> {
+        let width = check_space(register, AddressSpace::SystemIo)?;
+        let start = register.address as usize;
+        if register.address > 0xffff || start + width > 0x10000 {
+            return Err(Error::OutOfRange);
+        }
+        Ok(start..start + width)
+    }
+}
+
+impl RegisterAccess for MockIoPorts {
+    fn read(&mut self, register: &GenericAddress) -> Result<u64, Error> {
+        let range = MockIoPorts::range(register)?;
+        Ok(to_value(&self.ports[range]))
+    }
+
+    fn write(&mut self, register: &GenericAddress, value: u64) -> Result<(), Error> {
+        let range = MockIoPorts::range(register)?;
+        let width = range.len();
+        self.ports[range].copy_from_slice(&value.to_le_bytes()[..width]);
+        Ok(())
+    }
+}
+
+// PCI configuration registers reached through any `PciConfigReader`.
+pub struct PciConfigRegisters<R> {
+    config: R,
+}
+
+impl<R> PciConfigRegisters<R>
+where
+    R: PciConfigReader,
+{
+    pub fn new(config: R) -> Self {
+        PciConfigRegisters { config }
+    }
+}
+
+impl<R> RegisterAccess for PciConfigRegisters<R>
+where
+    R: PciConfigReader,
+{
+    fn read(&mut self, register: &GenericAddress) -> Result<u64, Error> {
+        let width = check_space(register, AddressSpace::PciConfiguration)?;
+        let (device, function, offset) = register.pci_location().ok_or(Error::OutOfRange)?;
+        let mut buf = [0u8; 8];
+        self.config
+            .read_config(0, 0, device, function, offset, &mut buf[..width])?;
+        Ok(to_value(&buf[..width]))
+    }
+
+    fn write(&mut self, register: &GenericAddress, value: u64) -> Result<(), Error> {
+        let width = check_space(register, AddressSpace::PciConfiguration)?;
+        let (device, function, offset) = register.pci_location().ok_or(Error::OutOfRange)?;
+        self.config.write_config(
+            0,
+            0,
+            device,
+            function,
+            offset,
+            &value.to_le_bytes()[..width],
+        )
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use std::io::Cursor;
+
+    #[test]
+    fn generic_address() {
+        let data = GenericAddress {
+            address_space_id: AddressSpace::FunctionalFixedHardware,
+            register_bit_width: 8,
+            register_bit_offset: 4,
+            access_size: 0,
+            address: 0x0706050403020100,
+        };
+        let raw: [u8; 12] = data.into();
+        assert_eq!(raw, [0x7f, 8, 4, 0, 0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(GenericAddress::from(raw), data);
+        assert_eq!(data.access_width(), 2);
+
+        assert_eq!(AddressSpace::from(0x0c), AddressSpace::Reserved(0x0c));
+        assert_eq!(u8::from(AddressSpace::PlatformCommunicationsChannel), 0x0a);
+        assert_eq!(
+            GenericAddress {
+                address_space_id: AddressSpace::SystemMemory,
+                register_bit_width: 32,
+                register_bit_offset: 0,
+                access_size: 4,
+                address: 0
+            }
+            .access_width(),
+            8
+        );
+        assert_eq!(
+            GenericAddress {
+                address_space_id: AddressSpace::PciConfiguration,
+                register_bit_width: 32,
+                register_bit_offset: 0,
+                access_size: 2,
+                address: 0x0003_0001_0040
+            }
+            .pci_location(),
+            Some((3, 1, 0x40))
+        );
+        assert_eq!(
+            GenericAddress {
+                address_space_id: AddressSpace::PciConfiguration,
+                register_bit_width: 32,
+                register_bit_offset: 0,
+                access_size: 2,
+                address: 0x0100_0000_0040
+            }
+            .pci_location(),
+            None
+        );
+        assert_eq!(
+            GenericAddress {
+                address_space_id: AddressSpace::PciConfiguration,
+                register_bit_width: 32,
+                register_bit_offset: 0,
+                access_size: 2,
+                address: 0x0003_0008_0040
+            }
+            .pci_location(),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn register_access() {
+        let mut memory = SystemMemory::new(Cursor::new(vec![0u8; 0x100]));
+        let reg = GenericAddress {
+            address_space_id: AddressSpace::SystemMemory,
+            register_bit_width: 32,
+            register_bit_offset: 0,
+            access_size: 3,
+            address: 0x10,
+        };
+        memory.write(&reg, 0x1122_3344_5566).unwrap();
+        assert_eq!(memory.read(&reg).unwrap(), 0x3344_5566);
+        assert_eq!(
+            memory.into_inner().into_inner()[0x10..0x15],
+            [0x66, 0x55, 0x44, 0x33, 0]
+        );
+
+        let mut ports = MockIoPorts::default();
+        let reg = GenericAddress {
+            address_space_id: AddressSpace::SystemIo,
+            register_bit_width: 32,
+            register_bit_offset: 0,
+            access_size: 2,
+            address: 0xcf8,
+        };
+        ports.write(&reg, 0xabcd).unwrap();
+        assert_eq!(ports.ports[0xcf8..0xcfa], [0xcd, 0xab]);
+        assert_eq!(ports.read(&reg).unwrap(), 0xabcd);
+        assert!(
+            ports
+                .read(&GenericAddress {
+                    address_space_id: AddressSpace::SystemIo,
+                    register_bit_width: 32,
+                    register_bit_offset: 0,
+                    access_size: 2,
+                    address: 0xffff
+                })
+                .is_err()
+        );
+        assert!(matches!(
+            ports.read(&GenericAddress {
+                address_space_id: AddressSpace::SystemMemory,
+                register_bit_width: 32,
+                register_bit_offset: 0,
+                access_size: 1,
+                address: 0
+            }),
+            Err(Error::UnsupportedAddressSpace(0))
+        ));
+    }
+
+    #[test]
+    fn pci_config_registers() {
+        #[derive(Default)]
+        struct Config(Vec<u8>);
+
+        impl PciConfigReader for Config {
+            fn read_config(
+                &mut self,
+                _segment: u16,
+                _bus: u8,
+                device: u8,
+                function: u8,
+                offset: u16,
+                buf: &mut [u8],
+            ) -> Result<(), Error> {
+                let start =
+                    ((device as usize) << 15) | ((function as usize) << 12) | offset as usize;
+                buf.copy_from_slice(&self.0[start..start + buf.len()]);
+                Ok(())
+            }
+
+            fn write_config(
+                &mut self,
+                _segment: u16,
+                _bus: u8,
+                device: u8,
+                function: u8,
+                offset: u16,
+                buf: &[u8],
+            ) -> Result<(), Error> {
+                let start =
+                    ((device as usize) << 15) | ((function as usize) << 12) | offset as usize;
+                self.0[start..start + buf.len()].copy_from_slice(buf);
+                Ok(())
+            }
+        }
+
+        let mut config = PciConfigRegisters::new(Config(vec![0; 0x10000]));
+        let reg = GenericAddress {
+            address_space_id: AddressSpace::PciConfiguration,
+            register_bit_width: 32,
+            register_bit_offset: 0,
+            access_size: 3,
+            address: 0x0001_0000_0004,
+        };
+        config.write(&reg, 0x0000_0407).unwrap();
+        assert_eq!(config.read(&reg).unwrap(), 0x0407);
+        assert_eq!(config.config.0[0x8004..0x8006], [0x07, 0x04]);
+
+        // Device 0x100 would wrap onto device 0.
+        let reg = GenericAddress {
+            address: 0x0100_0000_0004,
+            ..reg
+        };
+        assert!(matches!(config.read(&reg), Err(Error::OutOfRange)));
+    }
+}
@@ -0,0 +1,588 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::{RawAcpiData, string_to_array};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://uefi.org/specs/ACPI/6.5/05_ACPI_Software_Programming_Model.html#generic-timer-description-table-gtdt
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenericTimerDescription {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub cnt_control_base: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 4],
+    pub secure_el1_timer_gsiv: u32,
+    pub secure_el1_timer_flags: TimerFlags,
+    pub non_secure_el1_timer_gsiv: u32,
+    pub non_secure_el1_timer_flags: TimerFlags,
+    pub virtual_el1_timer_gsiv: u32,
+    pub virtual_el1_timer_flags: TimerFlags,
+    pub el2_timer_gsiv: u32,
+    pub el2_timer_flags: TimerFlags,
+    pub cnt_read_base: u64,
+    pub platform_timer_count: u32,
+    pub platform_timer_offset: u32,
+    pub virtual_el2_timer_gsiv: Option<u32>,
+    pub virtual_el2_timer_flags: Option<TimerFlags>,
+    pub platform_timers: Vec<PlatformTimer>,
+}
+
+impl From<RawAcpiData> for GenericTimerDescription {
+    fn from(mut data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+        let total = data.acpi_table_data.len();
+        let cnt_control_base = data.acpi_table_data.get_u64_le();
+        let reserved = data.acpi_table_data.split_to(4)[..].try_into().unwrap();
+        let secure_el1_timer_gsiv = data.acpi_table_data.get_u32_le();
+        let secure_el1_timer_flags = TimerFlags(data.acpi_table_data.get_u32_le());
+        let non_secure_el1_timer_gsiv = data.acpi_table_data.get_u32_le();
+        let non_secure_el1_timer_flags = TimerFlags(data.acpi_table_data.get_u32_le());
+        let virtual_el1_timer_gsiv = data.acpi_table_data.get_u32_le();
+        let virtual_el1_timer_flags = TimerFlags(data.acpi_table_data.get_u32_le());
+        let el2_timer_gsiv = data.acpi_table_data.get_u32_le();
+        let el2_timer_flags = TimerFlags(data.acpi_table_data.get_u32_le());
+        let cnt_read_base = data.acpi_table_data.get_u64_le();
+        let platform_timer_count = data.acpi_table_data.get_u32_le();
+        let platform_timer_offset = data.acpi_table_data.get_u32_le();
+
+        let (virtual_el2_timer_gsiv, virtual_el2_timer_flags) =
+            if revision >= 3 && data.acpi_table_data.len() >= 8 {
+                let gsiv = data.acpi_table_data.get_u32_le();
+                let flags = TimerFlags(data.acpi_table_data.get_u32_le());
+                (Some(gsiv), Some(flags))
+            } else {
+                (None, None)
+            };
+
+        let consumed = total - data.acpi_table_data.len();
+        let skip = (platform_timer_offset as usize).saturating_sub(36 + consumed);
+        data.acpi_table_data
+            .advance(skip.min(data.acpi_table_data.len()));
+
+        let mut platform_timers = vec![];
+        for _ in 0..platform_timer_count {
+            if data.acpi_table_data.len() < 4 {
+                break;
+            }
+
+            let len = u16::from_le_bytes([data.acpi_table_data[1], data.acpi_table_data[2]]);
+            let len = (len as usize).min(data.acpi_table_data.len());
+            if len < 4 {
+                break;
+            }
+
+            let timer = data.acpi_table_data.split_to(len);
+            platform_timers.push(PlatformTimer::from(timer));
+        }
+
+        GenericTimerDescription {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            cnt_control_base,
+            reserved,
+            secure_el1_timer_gsiv,
+            secure_el1_timer_flags,
+            non_secure_el1_timer_gsiv,
+            non_secure_el1_timer_flags,
+            virtual_el1_timer_gsiv,
+            virtual_el1_timer_flags,
+            el2_timer_gsiv,
+            el2_timer_flags,
+            cnt_read_base,
+            platform_timer_count,
+            platform_timer_offset,
+            virtual_el2_timer_gsiv,
+            virtual_el2_timer_flags,
+            platform_timers,
+        }
+    }
+}
+
+impl From<GenericTimerDescription> for Bytes {
+    fn from(val: GenericTimerDescription) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        b.put_u64_le(val.cnt_control_base);
+        b.put_slice(&val.reserved);
+        b.put_u32_le(val.secure_el1_timer_gsiv);
+        b.put_u32_le(val.secure_el1_timer_flags.0);
+        b.put_u32_le(val.non_secure_el1_timer_gsiv);
+        b.put_u32_le(val.non_secure_el1_timer_flags.0);
+        b.put_u32_le(val.virtual_el1_timer_gsiv);
+        b.put_u32_le(val.virtual_el1_timer_flags.0);
+        b.put_u32_le(val.el2_timer_gsiv);
+        b.put_u32_le(val.el2_timer_flags.0);
+        b.put_u64_le(val.cnt_read_base);
+        b.put_u32_le(val.platform_timer_count);
+        b.put_u32_le(val.platform_timer_offset);
+        if let Some(v) = val.virtual_el2_timer_gsiv {
+            b.put_u32_le(v);
+        }
+        if let Some(v) = val.virtual_el2_timer_flags {
+            b.put_u32_le(v.0);
+        }
+        if !val.platform_timers.is_empty() && b.len() < val.platform_timer_offset as usize {
+            b.resize(val.platform_timer_offset as usize, 0);
+        }
+        for timer in val.platform_timers {
+            b.put(Bytes::from(timer));
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for GenericTimerDescription {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Generic Timer Description Table");
+        w.u64("Counter Block Address", self.cnt_control_base);
+        w.bytes("Reserved", &self.reserved);
+        w.blank();
+        w.u32("Secure EL1 Interrupt", self.secure_el1_timer_gsiv);
+        timer_flags(w, "EL1 Flags (decoded below)", self.secure_el1_timer_flags);
+        w.blank();
+        w.u32("Non-Secure EL1 Interrupt", self.non_secure_el1_timer_gsiv);
+        timer_flags(
+            w,
+            "NEL1 Flags (decoded below)",
+            self.non_secure_el1_timer_flags,
+        );
+        w.blank();
+        w.u32("Virtual Timer Interrupt", self.virtual_el1_timer_gsiv);
+        timer_flags(w, "VT Flags (decoded below)", self.virtual_el1_timer_flags);
+        w.blank();
+        w.u32("Non-Secure EL2 Interrupt", self.el2_timer_gsiv);
+        timer_flags(w, "NEL2 Flags (decoded below)", self.el2_timer_flags);
+        w.u64("Counter Read Block Address", self.cnt_read_base);
+        w.blank();
+        w.u32("Platform Timer Count", self.platform_timer_count);
+        w.u32("Platform Timer Offset", self.platform_timer_offset);
+        if let Some(v) = self.virtual_el2_timer_gsiv {
+            w.blank();
+            w.u32("Virtual EL2 Timer GSIV", v);
+        }
+        if let Some(v) = self.virtual_el2_timer_flags {
+            timer_flags(w, "Virtual EL2 Flags (decoded below)", v);
+        }
+
+        if !self.platform_timers.is_empty() {
+            w.seek(w.offset().max(self.platform_timer_offset as usize));
+        }
+        for timer in &self.platform_timers {
+            w.blank();
+            timer.disassemble(w);
+        }
+    }
+}
+
+fn timer_flags(w: &mut Disassembler, name: &str, flags: TimerFlags) {
+    w.u32(name, flags.0);
+    w.flag("Trigger Mode", flags.edge_triggered() as u8);
+    w.flag("Polarity", flags.active_low() as u8);
+    w.flag("Always On", flags.always_on() as u8);
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimerFlags(pub u32);
+
+impl TimerFlags {
+    pub fn edge_triggered(&self) -> bool {
+        self.0 & 0x01 != 0
+    }
+
+    pub fn active_low(&self) -> bool {
+        self.0 & 0x02 != 0
+    }
+
+    pub fn always_on(&self) -> bool {
+        self.0 & 0x04 != 0
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlatformTimer {
+    GtBlock(GtBlock),
+    SbsaGenericWatchdog(SbsaGenericWatchdog),
+    Unknown(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Bytes),
+}
+
+impl From<Bytes> for PlatformTimer {
+    fn from(buf: Bytes) -> Self {
+        match buf[0] {
+            0 if buf.len() >= GtBlock::HEADER_LENGTH => PlatformTimer::GtBlock(GtBlock::from(buf)),
+            1 if buf.len() >= SbsaGenericWatchdog::LENGTH => {
+                PlatformTimer::SbsaGenericWatchdog(SbsaGenericWatchdog::from(buf))
+            }
+            _ => PlatformTimer::Unknown(buf),
+        }
+    }
+}
+
+impl From<PlatformTimer> for Bytes {
+    fn from(val: PlatformTimer) -> Self {
+        match val {
+            PlatformTimer::GtBlock(v) => Bytes::from(v),
+            PlatformTimer::SbsaGenericWatchdog(v) => Bytes::from(v),
+            PlatformTimer::Unknown(v) => v,
+        }
+    }
+}
+
+impl Disassemble for PlatformTimer {
+    fn disassemble(&self, w: &mut Disassembler) {
+        match self {
+            PlatformTimer::GtBlock(v) => {
+                let start = w.offset();
+                w.annotated("Subtable Type", 1, 0, "Generic Timer Block");
+                w.u16("Length", v.length);
+                w.u8("Reserved", v.reserved);
+                w.u64("Block Address", v.cnt_ctl_base);
+                w.u32("Timer Count", v.timer_count);
+                w.u32("Timer Offset", v.timer_offset);
+                w.seek(w.offset().max(start + v.timer_offset as usize));
+                for timer in &v.timers {
+                    w.blank();
+                    w.u8("Frame Number", timer.frame_number);
+                    w.bytes("Reserved", &timer.reserved);
+                    w.u64("Base Address", timer.cnt_base);
+                    w.u64("EL0 Base Address", timer.cnt_el0_base);
+                    w.u32("Timer Interrupt", timer.physical_timer_gsiv);
+                    timer_flags(w, "Timer Flags (decoded below)", timer.physical_timer_flags);
+                    w.u32("Virtual Timer Interrupt", timer.virtual_timer_gsiv);
+                    timer_flags(
+                        w,
+                        "Virtual Timer Flags (decoded below)",
+                        timer.virtual_timer_flags,
+                    );
+                    w.u32("Common Flags (decoded below)", timer.common_flags);
+                    w.flag("Secure", timer.common_flags & 0x01);
+                    w.flag("Always On", (timer.common_flags >> 1) & 0x01);
+                }
+            }
+            PlatformTimer::SbsaGenericWatchdog(v) => {
+                w.annotated("Subtable Type", 1, 1, "Generic Watchdog Timer");
+                w.u16("Length", v.length);
+                w.u8("Reserved", v.reserved);
+                w.u64("Refresh Frame Address", v.refresh_frame);
+                w.u64("Control Frame Address", v.control_frame);
+                w.u32("Timer Interrupt", v.timer_gsiv);
+                w.u32("Timer Flags (decoded below)", v.timer_flags);
+                w.flag("Trigger Mode", v.timer_flags & 0x01);
+                w.flag("Polarity", (v.timer_flags >> 1) & 0x01);
+                w.flag("Security", (v.timer_flags >> 2) & 0x01);
+            }
+            PlatformTimer::Unknown(v) => {
+                let timer_type = v.first().copied().unwrap_or_default();
+                w.annotated("Subtable Type", 1, timer_type as u64, "Unknown");
+                w.bytes("Data", v.get(1..).unwrap_or_default());
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GtBlock {
+    pub length: u16,
+    pub reserved: u8,
+    pub cnt_ctl_base: u64,
+    pub timer_count: u32,
+    pub timer_offset: u32,
+    pub timers: Vec<GtBlockTimer>,
+}
+
+impl GtBlock {
+    const HEADER_LENGTH: usize = 20;
+}
+
+impl From<Bytes> for GtBlock {
+    fn from(mut buf: Bytes) -> Self {
+        let block = buf.clone();
+        let _ = buf.get_u8();
+        let length = buf.get_u16_le();
+        let reserved = buf.get_u8();
+        let cnt_ctl_base = buf.get_u64_le();
+        let timer_count = buf.get_u32_le();
+        let timer_offset = buf.get_u32_le();
+
+        let start = (timer_offset as usize).min(block.len());
+        let timers = block
+            .slice(start..)
+            .chunks_exact(GtBlockTimer::LENGTH)
+            .take(timer_count as usize)
+            .map(|d| GtBlockTimer::from(Bytes::copy_from_slice(d)))
+            .collect();
+
+        GtBlock {
+            length,
+            reserved,
+            cnt_ctl_base,
+            timer_count,
+            timer_offset,
+            timers,
+        }
+    }
+}
+
+impl From<GtBlock> for Bytes {
+    fn from(val: GtBlock) -> Self {
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_u8(0);
+        b.put_u16_le(val.length);
+        b.put_u8(val.reserved);
+        b.put_u64_le(val.cnt_ctl_base);
+        b.put_u32_le(val.timer_count);
+        b.put_u32_le(val.timer_offset);
+        if b.len() < val.timer_offset as usize {
+            b.resize(val.timer_offset as usize, 0);
+        }
+        for timer in val.timers {
+            b.put(Bytes::from(timer));
+        }
+        b.freeze()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GtBlockTimer {
+    pub frame_number: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 3],
+    pub cnt_base: u64,
+    pub cnt_el0_base: u64,
+    pub physical_timer_gsiv: u32,
+    pub physical_timer_flags: TimerFlags,
+    pub virtual_timer_gsiv: u32,
+    pub virtual_timer_flags: TimerFlags,
+    pub common_flags: u32,
+}
+
+impl GtBlockTimer {
+    const LENGTH: usize = 40;
+
+    pub fn secure(&self) -> bool {
+        self.common_flags & 0x01 != 0
+    }
+
+    pub fn always_on(&self) -> bool {
+        self.common_flags & 0x02 != 0
+    }
+}
+
+impl From<Bytes> for GtBlockTimer {
+    fn from(mut buf: Bytes) -> Self {
+        let frame_number = buf.get_u8();
+        let reserved = buf.split_to(3)[..].try_into().unwrap();
+        let cnt_base = buf.get_u64_le();
+        let cnt_el0_base = buf.get_u64_le();
+        let physical_timer_gsiv = buf.get_u32_le();
+        let physical_timer_flags = TimerFlags(buf.get_u32_le());
+        let virtual_timer_gsiv = buf.get_u32_le();
+        let virtual_timer_flags = TimerFlags(buf.get_u32_le());
+        let common_flags = buf.get_u32_le();
+
+        GtBlockTimer {
+            frame_number,
+            reserved,
+            cnt_base,
+            cnt_el0_base,
+            physical_timer_gsiv,
+            physical_timer_flags,
+            virtual_timer_gsiv,
+            virtual_timer_flags,
+            common_flags,
+        }
+    }
+}
+
+impl From<GtBlockTimer> for Bytes {
+    fn from(val: GtBlockTimer) -> Self {
+        let mut b = BytesMut::with_capacity(GtBlockTimer::LENGTH);
+        b.put_u8(val.frame_number);
+        b.put_slice(&val.reserved);
+        b.put_u64_le(val.cnt_base);
+        b.put_u64_le(val.cnt_el0_base);
+        b.put_u32_le(val.physical_timer_gsiv);
+        b.put_u32_le(val.physical_timer_flags.0);
+        b.put_u32_le(val.virtual_timer_gsiv);
+        b.put_u32_le(val.virtual_timer_flags.0);
+        b.put_u32_le(val.common_flags);
+        b.freeze()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SbsaGenericWatchdog {
+    pub length: u16,
+    pub reserved: u8,
+    pub refresh_frame: u64,
+    pub control_frame: u64,
+    pub timer_gsiv: u32,
+    pub timer_flags: u32,
+}
+
+impl SbsaGenericWatchdog {
+    const LENGTH: usize = 28;
+
+    pub fn edge_triggered(&self) -> bool {
+        self.timer_flags & 0x01 != 0
+    }
+
+    pub fn active_low(&self) -> bool {
+        self.timer_flags & 0x02 != 0
+    }
+
+    pub fn secure(&self) -> bool {
+        self.timer_flags & 0x04 != 0
+    }
+}
+
+impl From<Bytes> for SbsaGenericWatchdog {
+    fn from(mut buf: Bytes) -> Self {
+        let _ = buf.get_u8();
+        let length = buf.get_u16_le();
+        let reserved = buf.get_u8();
+        let refresh_frame = buf.get_u64_le();
+        let control_frame = buf.get_u64_le();
+        let timer_gsiv = buf.get_u32_le();
+        let timer_flags = buf.get_u32_le();
+
+        SbsaGenericWatchdog {
+            length,
+            reserved,
+            refresh_frame,
+            control_frame,
+            timer_gsiv,
+            timer_flags,
+        }
+    }
+}
+
+impl From<SbsaGenericWatchdog> for Bytes {
+    fn from(val: SbsaGenericWatchdog) -> Self {
+        let mut b = BytesMut::with_capacity(SbsaGenericWatchdog::LENGTH);
+        b.put_u8(1);
+        b.put_u16_le(val.length);
+        b.put_u8(val.reserved);
+        b.put_u64_le(val.refresh_frame);
+        b.put_u64_le(val.control_frame);
+        b.put_u32_le(val.timer_gsiv);
+        b.put_u32_le(val.timer_flags);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generic_timer_description() {
+        let data = GenericTimerDescription {
+            signature: "GTDT".to_string(),
+            length: 104 + 60 + 28,
+            revision: 3,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            cnt_control_base: 0xffffffffffffffff,
+            reserved: [0; 4],
+            secure_el1_timer_gsiv: 29,
+            secure_el1_timer_flags: TimerFlags(0x04),
+            non_secure_el1_timer_gsiv: 30,
+            non_secure_el1_timer_flags: TimerFlags(0x04),
+            virtual_el1_timer_gsiv: 27,
+            virtual_el1_timer_flags: TimerFlags(0x04),
+            el2_timer_gsiv: 26,
+            el2_timer_flags: TimerFlags(0x04),
+            cnt_read_base: 0xffffffffffffffff,
+            platform_timer_count: 2,
+            platform_timer_offset: 104,
+            virtual_el2_timer_gsiv: Some(28),
+            virtual_el2_timer_flags: Some(TimerFlags(0x04)),
+            platform_timers: vec![
+                PlatformTimer::GtBlock(GtBlock {
+                    length: 60,
+                    reserved: 0,
+                    cnt_ctl_base: 0x2a810000,
+                    timer_count: 1,
+                    timer_offset: 20,
+                    timers: vec![GtBlockTimer {
+                        frame_number: 0,
+                        reserved: [0; 3],
+                        cnt_base: 0x2a830000,
+                        cnt_el0_base: 0xffffffffffffffff,
+                        physical_timer_gsiv: 92,
+                        physical_timer_flags: TimerFlags(0),
+                        virtual_timer_gsiv: 0,
+                        virtual_timer_flags: TimerFlags(0),
+                        common_flags: 0x02,
+                    }],
+                }),
+                PlatformTimer::SbsaGenericWatchdog(SbsaGenericWatchdog {
+                    length: 28,
+                    reserved: 0,
+                    refresh_frame: 0x2a450000,
+                    control_frame: 0x2a440000,
+                    timer_gsiv: 93,
+                    timer_flags: 0,
+                }),
+            ],
+        };
+        let b = Bytes::from(data.clone());
+        let raw = RawAcpiData::from(b);
+        let ret = GenericTimerDescription::from(raw);
+        assert_eq!(data, ret);
+        assert!(ret.non_secure_el1_timer_flags.always_on());
+    }
+}
@@ -0,0 +1,161 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+// Stored in the on-disk mixed-endian layout: the first three fields are little-endian.
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct Guid(pub [u8; 16]);
+
+impl Guid {
+    pub const fn new(d1: u32, d2: u16, d3: u16, d4: [u8; 8]) -> Self {
+        let d1 = d1.to_le_bytes();
+        let d2 = d2.to_le_bytes();
+        let d3 = d3.to_le_bytes();
+        Guid([
+            d1[0], d1[1], d1[2], d1[3], d2[0], d2[1], d3[0], d3[1], d4[0], d4[1], d4[2], d4[3],
+            d4[4], d4[5], d4[6], d4[7],
+        ])
+    }
+
+    pub fn data1(&self) -> u32 {
+        u32::from_le_bytes(self.0[0..4].try_into().unwrap())
+    }
+
+    pub fn data2(&self) -> u16 {
+        u16::from_le_bytes(self.0[4..6].try_into().unwrap())
+    }
+
+    pub fn data3(&self) -> u16 {
+        u16::from_le_bytes(self.0[6..8].try_into().unwrap())
+    }
+
+    pub fn data4(&self) -> [u8; 8] {
+        self.0[8..16].try_into().unwrap()
+    }
+
+    pub fn is_nil(&self) -> bool {
+        self.0 == [0; 16]
+    }
+}
+
+impl From<[u8; 16]> for Guid {
+    fn from(value: [u8; 16]) -> Self {
+        Guid(value)
+    }
+}
+
+impl From<Guid> for [u8; 16] {
+    fn from(val: Guid) -> Self {
+        val.0
+    }
+}
+
+impl fmt::Display for Guid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let d4 = self.data4();
+        write!(
+            f,
+            "{:08x}-{:04x}-{:04x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+            self.data1(),
+            self.data2(),
+            self.data3(),
+            d4[0],
+            d4[1],
+            d4[2],
+            d4[3],
+            d4[4],
+            d4[5],
+            d4[6],
+            d4[7]
+        )
+    }
+}
+
+impl fmt::Debug for Guid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Guid({})", self)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParseGuidError;
+
+impl fmt::Display for ParseGuidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid GUID")
+    }
+}
+
+impl core::error::Error for ParseGuidError {}
+
+impl FromStr for Guid {
+    type Err = ParseGuidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim_start_matches('{').trim_end_matches('}');
+        let parts = s.split('-').collect::<Vec<&str>>();
+        let lengths = parts.iter().map(|p| p.len()).collect::<Vec<usize>>();
+        if lengths != [8, 4, 4, 4, 12] {
+            return Err(ParseGuidError);
+        }
+
+        let d1 = u32::from_str_radix(parts[0], 16).map_err(|_| ParseGuidError)?;
+        let d2 = u16::from_str_radix(parts[1], 16).map_err(|_| ParseGuidError)?;
+        let d3 = u16::from_str_radix(parts[2], 16).map_err(|_| ParseGuidError)?;
+        let tail = [parts[3], parts[4]].concat();
+        let mut d4 = [0u8; 8];
+        for (i, v) in d4.iter_mut().enumerate() {
+            *v = tail
+                .get(i * 2..i * 2 + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+                .ok_or(ParseGuidError)?;
+        }
+
+        Ok(Guid::new(d1, d2, d3, d4))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Guid {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Guid {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = <alloc::string::String as serde::Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guid_format() {
+        let guid = Guid::new(
+            0xc68ed8e2,
+            0x9dc6,
+            0x4cbd,
+            [0x9d, 0x94, 0xdb, 0x65, 0xac, 0xc5, 0xc3, 0x32],
+        );
+        assert_eq!(&guid.0[0..4], &[0xe2, 0xd8, 0x8e, 0xc6]);
+        assert_eq!(guid.to_string(), "c68ed8e2-9dc6-4cbd-9d94-db65acc5c332");
+        assert_eq!(
+            "{C68ED8E2-9DC6-4CBD-9D94-DB65ACC5C332}".parse::<Guid>(),
+            Ok(guid)
+        );
+        assert!("c68ed8e2-9dc6-4cbd-9d94".parse::<Guid>().is_err());
+    }
+}
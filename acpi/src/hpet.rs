@@ -0,0 +1,224 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::gas::GenericAddress;
+use super::{RawAcpiData, string_to_array};
+use alloc::string::String;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://www.intel.com/content/dam/www/public/us/en/documents/technical-specifications/software-developers-hpet-spec-1-0a.pdf
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HighPrecisionEventTimer {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub event_timer_block_id: u32,
+    pub base_address: GenericAddress,
+    pub hpet_number: u8,
+    pub minimum_clock_tick: u16,
+    pub page_protection: u8,
+}
+
+impl HighPrecisionEventTimer {
+    pub fn hardware_revision_id(&self) -> u8 {
+        (self.event_timer_block_id & 0xff) as u8
+    }
+
+    pub fn comparator_count(&self) -> u8 {
+        ((self.event_timer_block_id >> 8) & 0x1f) as u8 + 1
+    }
+
+    pub fn counter_size_64bit(&self) -> bool {
+        self.event_timer_block_id & (1 << 13) != 0
+    }
+
+    pub fn legacy_replacement_capable(&self) -> bool {
+        self.event_timer_block_id & (1 << 15) != 0
+    }
+
+    pub fn pci_vendor_id(&self) -> u16 {
+        (self.event_timer_block_id >> 16) as u16
+    }
+
+    pub fn page_protection(&self) -> PageProtection {
+        PageProtection::from(self.page_protection & 0x0f)
+    }
+
+    pub fn oem_attributes(&self) -> u8 {
+        self.page_protection >> 4
+    }
+}
+
+impl From<RawAcpiData> for HighPrecisionEventTimer {
+    fn from(mut data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+        let event_timer_block_id = data.acpi_table_data.get_u32_le();
+        let base_address = GenericAddress::from(data.acpi_table_data.split_to(12));
+        let hpet_number = data.acpi_table_data.get_u8();
+        let minimum_clock_tick = data.acpi_table_data.get_u16_le();
+        let page_protection = data.acpi_table_data.get_u8();
+
+        HighPrecisionEventTimer {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            event_timer_block_id,
+            base_address,
+            hpet_number,
+            minimum_clock_tick,
+            page_protection,
+        }
+    }
+}
+
+impl From<HighPrecisionEventTimer> for Bytes {
+    fn from(val: HighPrecisionEventTimer) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        b.put_u32_le(val.event_timer_block_id);
+        b.put(Bytes::from(val.base_address));
+        b.put_u8(val.hpet_number);
+        b.put_u16_le(val.minimum_clock_tick);
+        b.put_u8(val.page_protection);
+        b.freeze()
+    }
+}
+
+impl Disassemble for HighPrecisionEventTimer {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "High Precision Event Timer Table");
+        w.u32("Hardware Block ID", self.event_timer_block_id);
+        w.flag("Hardware Revision", self.hardware_revision_id());
+        w.flag("Comparator Count", self.comparator_count());
+        w.flag("64-bit Counter", self.counter_size_64bit() as u8);
+        w.flag(
+            "Legacy Replacement",
+            self.legacy_replacement_capable() as u8,
+        );
+        w.flag(
+            "PCI Vendor ID",
+            format_args!("{:04X}", self.pci_vendor_id()),
+        );
+        w.blank();
+        w.gas("Timer Block Register", &self.base_address);
+        w.blank();
+        w.u8("Sequence Number", self.hpet_number);
+        w.u16("Minimum Clock Ticks", self.minimum_clock_tick);
+        w.u8("Flags (decoded below)", self.page_protection);
+        w.flag(
+            "Page Protection",
+            format_args!("{:?}", self.page_protection()),
+        );
+        w.flag("OEM Attributes", self.oem_attributes());
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PageProtection {
+    #[default]
+    None,
+    Protected4KiB,
+    Protected64KiB,
+    Reserved(u8),
+}
+
+impl From<u8> for PageProtection {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => PageProtection::None,
+            1 => PageProtection::Protected4KiB,
+            2 => PageProtection::Protected64KiB,
+            v => PageProtection::Reserved(v),
+        }
+    }
+}
+
+impl From<PageProtection> for u8 {
+    fn from(val: PageProtection) -> Self {
+        match val {
+            PageProtection::None => 0,
+            PageProtection::Protected4KiB => 1,
+            PageProtection::Protected64KiB => 2,
+            PageProtection::Reserved(v) => v,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AddressSpace;
+
+    #[test]
+    fn high_precision_event_timer() {
+        let data = HighPrecisionEventTimer {
+            signature: "HPET".to_string(),
+            length: 56,
+            revision: 1,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            event_timer_block_id: 0x8086a201,
+            base_address: GenericAddress {
+                address_space_id: AddressSpace::SystemMemory,
+                register_bit_width: 64,
+                register_bit_offset: 0,
+                access_size: 0,
+                address: 0xfed00000,
+            },
+            hpet_number: 0,
+            minimum_clock_tick: 0x80,
+            page_protection: 0x01,
+        };
+        let b = Bytes::from(data.clone());
+        let raw = RawAcpiData::from(b);
+        let ret = HighPrecisionEventTimer::from(raw);
+        assert_eq!(data, ret);
+        assert_eq!(ret.hardware_revision_id(), 1);
+        assert_eq!(ret.comparator_count(), 3);
+        assert!(ret.counter_size_64bit());
+        assert!(ret.legacy_replacement_capable());
+        assert_eq!(ret.pci_vendor_id(), 0x8086);
+        assert_eq!(ret.page_protection(), PageProtection::Protected4KiB);
+    }
+}
@@ -0,0 +1,1060 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::{RawAcpiData, extract_cstring, put_at, string_to_array};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://developer.arm.com/documentation/den0049/latest/
+
+const MAX_TRANSLATION_DEPTH: usize = 16;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IoRemapping {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub node_count: u32,
+    pub node_offset: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 4],
+    pub nodes: Vec<IortNode>,
+}
+
+impl IoRemapping {
+    pub fn node_at(&self, offset: u32) -> Option<&IortNode> {
+        self.nodes.iter().find(|n| n.offset == offset)
+    }
+
+    pub fn root_complex(&self, segment: u32) -> Option<&IortNode> {
+        self.nodes.iter().find(|n| match &n.data {
+            IortNodeData::RootComplex(v) => v.pci_segment_number == segment,
+            _ => false,
+        })
+    }
+
+    pub fn named_component(&self, name: &str) -> Option<&IortNode> {
+        self.nodes.iter().find(|n| match &n.data {
+            IortNodeData::NamedComponent(v) => v.device_name == name,
+            _ => false,
+        })
+    }
+
+    pub fn map_id(&self, node: &IortNode, id: u32) -> Option<(u32, &IortNode)> {
+        let skip = match &node.data {
+            IortNodeData::SmmuV3(v) => v.device_id_mapping_index.map(|i| i as usize),
+            _ => None,
+        };
+
+        node.id_mappings
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != skip)
+            .find_map(|(_, m)| {
+                let output = m.map(id)?;
+                let next = self.node_at(m.output_reference)?;
+                Some((output, next))
+            })
+    }
+
+    pub fn translate(&self, node: &IortNode, id: u32) -> Option<IdTranslation> {
+        let mut translation = IdTranslation::default();
+
+        let mut current = node;
+        let mut id = id;
+        for _ in 0..MAX_TRANSLATION_DEPTH {
+            match &current.data {
+                IortNodeData::ItsGroup(v) => {
+                    translation.device_id = id;
+                    translation.its_node = current.offset;
+                    translation.its_identifiers = v.its_identifiers.clone();
+                    return Some(translation);
+                }
+                IortNodeData::SmmuV1V2(_) | IortNodeData::SmmuV3(_)
+                    if translation.stream_id.is_none() =>
+                {
+                    translation.stream_id = Some(id);
+                    translation.smmu_node = Some(current.offset);
+                }
+                _ => {}
+            }
+
+            let (next_id, next) = self.map_id(current, id)?;
+            id = next_id;
+            current = next;
+        }
+
+        None
+    }
+
+    pub fn translate_requester_id(&self, segment: u32, requester_id: u32) -> Option<IdTranslation> {
+        let node = self.root_complex(segment)?;
+        self.translate(node, requester_id)
+    }
+}
+
+impl From<RawAcpiData> for IoRemapping {
+    fn from(data: RawAcpiData) -> Self {
+        let table = data.acpi_table_data.clone();
+        let mut body = data.acpi_table_data;
+
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+        let node_count = body.get_u32_le();
+        let node_offset = body.get_u32_le();
+        let reserved = body.split_to(4)[..].try_into().unwrap();
+
+        let mut nodes = vec![];
+        let mut offset = node_offset as usize;
+        for _ in 0..node_count {
+            let start = offset.saturating_sub(36);
+            if table.len() < start + IortNode::HEADER_LENGTH {
+                break;
+            }
+
+            let len = u16::from_le_bytes([table[start + 1], table[start + 2]]) as usize;
+            if len < IortNode::HEADER_LENGTH || table.len() < start + len {
+                break;
+            }
+
+            let mut node = IortNode::from(table.slice(start..start + len));
+            node.offset = offset as u32;
+            nodes.push(node);
+            offset += len;
+        }
+
+        IoRemapping {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            node_count,
+            node_offset,
+            reserved,
+            nodes,
+        }
+    }
+}
+
+impl From<IoRemapping> for Bytes {
+    fn from(val: IoRemapping) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        b.put_u32_le(val.node_count);
+        b.put_u32_le(val.node_offset);
+        b.put_slice(&val.reserved);
+        for node in val.nodes {
+            let offset = (node.offset as usize).max(b.len());
+            put_at(&mut b, offset, &Bytes::from(node));
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for IoRemapping {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "IO Remapping Table");
+        w.u32("Node Count", self.node_count);
+        w.u32("Node Offset", self.node_offset);
+        w.bytes("Reserved", &self.reserved);
+        for node in &self.nodes {
+            w.blank();
+            w.seek(node.offset as usize);
+            node.disassemble(w);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdTranslation {
+    pub stream_id: Option<u32>,
+    pub smmu_node: Option<u32>,
+    pub device_id: u32,
+    pub its_node: u32,
+    pub its_identifiers: Vec<u32>,
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IortNode {
+    // Offset of this node from the start of the IORT, used by ID mapping output references.
+    pub offset: u32,
+    pub length: u16,
+    pub revision: u8,
+    pub identifier: u32,
+    pub id_mapping_count: u32,
+    pub id_mapping_offset: u32,
+    pub data: IortNodeData,
+    pub id_mappings: Vec<IdMapping>,
+}
+
+impl IortNode {
+    const HEADER_LENGTH: usize = 16;
+}
+
+impl From<Bytes> for IortNode {
+    fn from(node: Bytes) -> Self {
+        let mut buf = node.clone();
+        let node_type = buf.get_u8();
+        let length = buf.get_u16_le();
+        let revision = buf.get_u8();
+        let identifier = buf.get_u32_le();
+        let id_mapping_count = buf.get_u32_le();
+        let id_mapping_offset = buf.get_u32_le();
+
+        let fixed_end = if id_mapping_count > 0 {
+            (id_mapping_offset as usize).clamp(IortNode::HEADER_LENGTH, node.len())
+        } else {
+            node.len()
+        };
+        let data = IortNodeData::new(node_type, &node, fixed_end);
+
+        let start = (id_mapping_offset as usize).min(node.len());
+        let id_mappings = node
+            .slice(start..)
+            .chunks_exact(IdMapping::LENGTH)
+            .take(id_mapping_count as usize)
+            .map(|d| IdMapping::from(Bytes::copy_from_slice(d)))
+            .collect();
+
+        IortNode {
+            offset: 0,
+            length,
+            revision,
+            identifier,
+            id_mapping_count,
+            id_mapping_offset,
+            data,
+            id_mappings,
+        }
+    }
+}
+
+impl From<IortNode> for Bytes {
+    fn from(val: IortNode) -> Self {
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_u8(val.data.node_type());
+        b.put_u16_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u32_le(val.identifier);
+        b.put_u32_le(val.id_mapping_count);
+        b.put_u32_le(val.id_mapping_offset);
+        val.data.write(&mut b);
+
+        let mut mappings = BytesMut::new();
+        for mapping in val.id_mappings {
+            mappings.put(Bytes::from(mapping));
+        }
+        if !mappings.is_empty() {
+            put_at(&mut b, val.id_mapping_offset as usize, &mappings);
+        }
+
+        if b.len() < val.length as usize {
+            b.resize(val.length as usize, 0);
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for IortNode {
+    fn disassemble(&self, w: &mut Disassembler) {
+        let start = w.offset();
+        let name = match self.data {
+            IortNodeData::ItsGroup(_) => "ITS Group",
+            IortNodeData::NamedComponent(_) => "Named Component",
+            IortNodeData::RootComplex(_) => "Root Complex",
+            IortNodeData::SmmuV1V2(_) => "SMMU v1/v2",
+            IortNodeData::SmmuV3(_) => "SMMU v3",
+            IortNodeData::PerformanceMonitoringCounterGroup(_) => "PMCG",
+            IortNodeData::ReservedMemoryRange(_) => "RMR",
+            IortNodeData::Unknown(_, _) => "Unknown",
+        };
+        w.annotated("Type", 1, self.data.node_type() as u64, name);
+        w.u16("Length", self.length);
+        w.u8("Revision", self.revision);
+        w.u32("Identifier", self.identifier);
+        w.u32("Mapping Count", self.id_mapping_count);
+        w.u32("Mapping Offset", self.id_mapping_offset);
+        w.blank();
+        self.data.disassemble_at(w, start);
+
+        if !self.id_mappings.is_empty() {
+            w.seek(start + self.id_mapping_offset as usize);
+        }
+        for mapping in &self.id_mappings {
+            w.blank();
+            w.u32("Input base", mapping.input_base);
+            w.u32("ID Count", mapping.id_count);
+            w.u32("Output Base", mapping.output_base);
+            w.u32("Output Reference", mapping.output_reference);
+            w.u32("Flags (decoded below)", mapping.flags);
+            w.flag("Single Mapping", mapping.flags & 0x01);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdMapping {
+    pub input_base: u32,
+    pub id_count: u32,
+    pub output_base: u32,
+    pub output_reference: u32,
+    pub flags: u32,
+}
+
+impl IdMapping {
+    const LENGTH: usize = 20;
+
+    pub fn single_mapping(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
+
+    // `id_count` is the number of IDs in the range minus one.
+    pub fn map(&self, id: u32) -> Option<u32> {
+        if self.single_mapping() {
+            return Some(self.output_base);
+        }
+
+        if id < self.input_base || id - self.input_base > self.id_count {
+            return None;
+        }
+
+        Some(self.output_base.wrapping_add(id - self.input_base))
+    }
+}
+
+impl From<Bytes> for IdMapping {
+    fn from(mut buf: Bytes) -> Self {
+        let input_base = buf.get_u32_le();
+        let id_count = buf.get_u32_le();
+        let output_base = buf.get_u32_le();
+        let output_reference = buf.get_u32_le();
+        let flags = buf.get_u32_le();
+
+        IdMapping {
+            input_base,
+            id_count,
+            output_base,
+            output_reference,
+            flags,
+        }
+    }
+}
+
+impl From<IdMapping> for Bytes {
+    fn from(val: IdMapping) -> Self {
+        let mut b = BytesMut::with_capacity(IdMapping::LENGTH);
+        b.put_u32_le(val.input_base);
+        b.put_u32_le(val.id_count);
+        b.put_u32_le(val.output_base);
+        b.put_u32_le(val.output_reference);
+        b.put_u32_le(val.flags);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IortNodeData {
+    ItsGroup(ItsGroup),
+    NamedComponent(NamedComponent),
+    RootComplex(RootComplex),
+    SmmuV1V2(SmmuV1V2),
+    SmmuV3(SmmuV3),
+    PerformanceMonitoringCounterGroup(PerformanceMonitoringCounterGroup),
+    ReservedMemoryRange(ReservedMemoryRange),
+    Unknown(
+        u8,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Bytes,
+    ),
+}
+
+impl Default for IortNodeData {
+    fn default() -> Self {
+        IortNodeData::Unknown(0xff, Bytes::new())
+    }
+}
+
+impl IortNodeData {
+    fn new(node_type: u8, node: &Bytes, fixed_end: usize) -> Self {
+        let fixed = node.slice(IortNode::HEADER_LENGTH..fixed_end);
+        match node_type {
+            0 if fixed.len() >= 4 => IortNodeData::ItsGroup(ItsGroup::from(fixed)),
+            1 if fixed.len() >= 13 => IortNodeData::NamedComponent(NamedComponent::from(fixed)),
+            2 if fixed.len() >= 20 => IortNodeData::RootComplex(RootComplex::from(fixed)),
+            3 if fixed.len() >= 44 => IortNodeData::SmmuV1V2(SmmuV1V2::new(node, fixed)),
+            4 if fixed.len() >= 44 => IortNodeData::SmmuV3(SmmuV3::from(fixed)),
+            5 if fixed.len() >= 16 => IortNodeData::PerformanceMonitoringCounterGroup(
+                PerformanceMonitoringCounterGroup::from(fixed),
+            ),
+            6 if fixed.len() >= 12 => {
+                IortNodeData::ReservedMemoryRange(ReservedMemoryRange::new(node, fixed))
+            }
+            t => IortNodeData::Unknown(t, fixed),
+        }
+    }
+
+    pub fn node_type(&self) -> u8 {
+        match self {
+            IortNodeData::ItsGroup(_) => 0,
+            IortNodeData::NamedComponent(_) => 1,
+            IortNodeData::RootComplex(_) => 2,
+            IortNodeData::SmmuV1V2(_) => 3,
+            IortNodeData::SmmuV3(_) => 4,
+            IortNodeData::PerformanceMonitoringCounterGroup(_) => 5,
+            IortNodeData::ReservedMemoryRange(_) => 6,
+            IortNodeData::Unknown(t, _) => *t,
+        }
+    }
+
+    // Offsets inside the node data count from `start`, the beginning of the node.
+    fn disassemble_at(&self, w: &mut Disassembler, start: usize) {
+        match self {
+            IortNodeData::ItsGroup(v) => {
+                w.u32("ItsCount", v.its_count);
+                for id in &v.its_identifiers {
+                    w.u32("Identifiers", *id);
+                }
+            }
+            IortNodeData::NamedComponent(v) => {
+                w.u32("Node Flags", v.flags);
+                w.u64("Memory Properties", v.memory_access_properties);
+                w.u8("Memory Size Limit", v.memory_address_size_limit);
+                w.string("Device Name", v.device_name.len() + 1, &v.device_name);
+            }
+            IortNodeData::RootComplex(v) => {
+                w.u64("Memory Properties", v.memory_access_properties);
+                w.u32("ATS Attribute", v.ats_attribute);
+                w.u32("PCI Segment Number", v.pci_segment_number);
+                w.u8("Memory Size Limit", v.memory_address_size_limit);
+                w.u16("PASID Capabilities", v.pasid_capabilities);
+                w.u8("Reserved", v.reserved);
+                if let Some(flags) = v.flags {
+                    w.u32("Flags", flags);
+                }
+            }
+            IortNodeData::SmmuV1V2(v) => {
+                w.u64("Base Address", v.base_address);
+                w.u64("Span", v.span);
+                w.u32("Model", v.model);
+                w.u32("Flags (decoded below)", v.flags);
+                w.flag("DVM Supported", v.flags & 0x01);
+                w.flag("Coherent Walk", (v.flags >> 1) & 0x01);
+                w.u32("Global Interrupt Offset", v.global_interrupt_offset);
+                w.u32("Context Interrupt Count", v.context_interrupt_count);
+                w.u32("Context Interrupt Offset", v.context_interrupt_offset);
+                w.u32("PMU Interrupt Count", v.pmu_interrupt_count);
+                w.u32("PMU Interrupt Offset", v.pmu_interrupt_offset);
+
+                let interrupts = [
+                    (v.global_interrupt_offset, &v.global_interrupts),
+                    (v.context_interrupt_offset, &v.context_interrupts),
+                    (v.pmu_interrupt_offset, &v.pmu_interrupts),
+                ];
+                for (offset, interrupts) in interrupts {
+                    if !interrupts.is_empty() {
+                        w.seek(start + offset as usize);
+                    }
+                    for (gsiv, flags) in interrupts {
+                        w.u32("Interrupt", *gsiv);
+                        w.u32("Flags", *flags);
+                    }
+                }
+            }
+            IortNodeData::SmmuV3(v) => {
+                w.u64("Base Address", v.base_address);
+                w.u32("Flags (decoded below)", v.flags);
+                w.flag("COHACC Override", v.flags & 0x01);
+                w.flag("HTTU Override", (v.flags >> 1) & 0x03);
+                w.flag("Proximity Domain Valid", (v.flags >> 3) & 0x01);
+                w.flag("DeviceID Valid", (v.flags >> 4) & 0x01);
+                w.bytes("Reserved", &v.reserved);
+                w.u64("VATOS Address", v.vatos_address);
+                w.u32("Model", v.model);
+                w.u32("Event GSIV", v.event_gsiv);
+                w.u32("PRI GSIV", v.pri_gsiv);
+                w.u32("GERR GSIV", v.gerr_gsiv);
+                w.u32("Sync GSIV", v.sync_gsiv);
+                if let Some(proximity_domain) = v.proximity_domain {
+                    w.u32("Proximity Domain", proximity_domain);
+                }
+                if let Some(index) = v.device_id_mapping_index {
+                    w.u32("Device ID Mapping Index", index);
+                }
+            }
+            IortNodeData::PerformanceMonitoringCounterGroup(v) => {
+                w.u64("Page 0 Base Address", v.page0_base_address);
+                w.u32("Overflow Interrupt GSIV", v.overflow_interrupt_gsiv);
+                w.u32("Node Reference", v.node_reference);
+                if let Some(page1) = v.page1_base_address {
+                    w.u64("Page 1 Base Address", page1);
+                }
+            }
+            IortNodeData::ReservedMemoryRange(v) => {
+                w.u32("Flags (decoded below)", v.flags);
+                w.flag("Remapping Permitted", v.flags & 0x01);
+                w.u32("Number Of RMR Descriptors", v.memory_range_count);
+                w.u32("RMR Descriptor Offset", v.memory_range_offset);
+                if !v.memory_ranges.is_empty() {
+                    w.seek(start + v.memory_range_offset as usize);
+                }
+                for range in &v.memory_ranges {
+                    w.blank();
+                    w.u64("Base Address of RMR", range.base_address);
+                    w.u64("Length of RMR", range.length);
+                    w.bytes("Reserved", &range.reserved);
+                }
+            }
+            IortNodeData::Unknown(_, v) => w.bytes("Data", v),
+        }
+    }
+
+    fn write(self, b: &mut BytesMut) {
+        match self {
+            IortNodeData::ItsGroup(v) => {
+                b.put_u32_le(v.its_count);
+                for id in v.its_identifiers {
+                    b.put_u32_le(id);
+                }
+            }
+            IortNodeData::NamedComponent(v) => {
+                b.put_u32_le(v.flags);
+                b.put_u64_le(v.memory_access_properties);
+                b.put_u8(v.memory_address_size_limit);
+                b.put_slice(v.device_name.as_bytes());
+                b.put_u8(0);
+            }
+            IortNodeData::RootComplex(v) => {
+                b.put_u64_le(v.memory_access_properties);
+                b.put_u32_le(v.ats_attribute);
+                b.put_u32_le(v.pci_segment_number);
+                b.put_u8(v.memory_address_size_limit);
+                b.put_u16_le(v.pasid_capabilities);
+                b.put_u8(v.reserved);
+                if let Some(flags) = v.flags {
+                    b.put_u32_le(flags);
+                }
+            }
+            IortNodeData::SmmuV1V2(v) => {
+                b.put_u64_le(v.base_address);
+                b.put_u64_le(v.span);
+                b.put_u32_le(v.model);
+                b.put_u32_le(v.flags);
+                b.put_u32_le(v.global_interrupt_offset);
+                b.put_u32_le(v.context_interrupt_count);
+                b.put_u32_le(v.context_interrupt_offset);
+                b.put_u32_le(v.pmu_interrupt_count);
+                b.put_u32_le(v.pmu_interrupt_offset);
+
+                let mut global = BytesMut::new();
+                for (gsiv, flags) in v.global_interrupts {
+                    global.put_u32_le(gsiv);
+                    global.put_u32_le(flags);
+                }
+                put_at(b, v.global_interrupt_offset as usize, &global);
+
+                let mut context = BytesMut::new();
+                for (gsiv, flags) in v.context_interrupts {
+                    context.put_u32_le(gsiv);
+                    context.put_u32_le(flags);
+                }
+                if !context.is_empty() {
+                    put_at(b, v.context_interrupt_offset as usize, &context);
+                }
+
+                let mut pmu = BytesMut::new();
+                for (gsiv, flags) in v.pmu_interrupts {
+                    pmu.put_u32_le(gsiv);
+                    pmu.put_u32_le(flags);
+                }
+                if !pmu.is_empty() {
+                    put_at(b, v.pmu_interrupt_offset as usize, &pmu);
+                }
+            }
+            IortNodeData::SmmuV3(v) => {
+                b.put_u64_le(v.base_address);
+                b.put_u32_le(v.flags);
+                b.put_slice(&v.reserved);
+                b.put_u64_le(v.vatos_address);
+                b.put_u32_le(v.model);
+                b.put_u32_le(v.event_gsiv);
+                b.put_u32_le(v.pri_gsiv);
+                b.put_u32_le(v.gerr_gsiv);
+                b.put_u32_le(v.sync_gsiv);
+                if let Some(proximity_domain) = v.proximity_domain {
+                    b.put_u32_le(proximity_domain);
+                }
+                if let Some(index) = v.device_id_mapping_index {
+                    b.put_u32_le(index);
+                }
+            }
+            IortNodeData::PerformanceMonitoringCounterGroup(v) => {
+                b.put_u64_le(v.page0_base_address);
+                b.put_u32_le(v.overflow_interrupt_gsiv);
+                b.put_u32_le(v.node_reference);
+                if let Some(page1) = v.page1_base_address {
+                    b.put_u64_le(page1);
+                }
+            }
+            IortNodeData::ReservedMemoryRange(v) => {
+                b.put_u32_le(v.flags);
+                b.put_u32_le(v.memory_range_count);
+                b.put_u32_le(v.memory_range_offset);
+
+                let mut ranges = BytesMut::new();
+                for range in v.memory_ranges {
+                    ranges.put_u64_le(range.base_address);
+                    ranges.put_u64_le(range.length);
+                    ranges.put_slice(&range.reserved);
+                }
+                if !ranges.is_empty() {
+                    put_at(b, v.memory_range_offset as usize, &ranges);
+                }
+            }
+            IortNodeData::Unknown(_, v) => {
+                b.put(v);
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItsGroup {
+    pub its_count: u32,
+    pub its_identifiers: Vec<u32>,
+}
+
+impl From<Bytes> for ItsGroup {
+    fn from(mut buf: Bytes) -> Self {
+        let its_count = buf.get_u32_le();
+        let its_identifiers = buf
+            .chunks_exact(4)
+            .take(its_count as usize)
+            .map(|d| u32::from_le_bytes(d.try_into().unwrap()))
+            .collect();
+
+        ItsGroup {
+            its_count,
+            its_identifiers,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NamedComponent {
+    pub flags: u32,
+    pub memory_access_properties: u64,
+    pub memory_address_size_limit: u8,
+    pub device_name: String,
+}
+
+impl From<Bytes> for NamedComponent {
+    fn from(mut buf: Bytes) -> Self {
+        let flags = buf.get_u32_le();
+        let memory_access_properties = buf.get_u64_le();
+        let memory_address_size_limit = buf.get_u8();
+        let device_name = extract_cstring(&buf);
+
+        NamedComponent {
+            flags,
+            memory_access_properties,
+            memory_address_size_limit,
+            device_name,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RootComplex {
+    pub memory_access_properties: u64,
+    pub ats_attribute: u32,
+    pub pci_segment_number: u32,
+    pub memory_address_size_limit: u8,
+    pub pasid_capabilities: u16,
+    pub reserved: u8,
+    pub flags: Option<u32>,
+}
+
+impl RootComplex {
+    pub fn ats_supported(&self) -> bool {
+        self.ats_attribute & 0x01 != 0
+    }
+}
+
+impl From<Bytes> for RootComplex {
+    fn from(mut buf: Bytes) -> Self {
+        let memory_access_properties = buf.get_u64_le();
+        let ats_attribute = buf.get_u32_le();
+        let pci_segment_number = buf.get_u32_le();
+        let memory_address_size_limit = buf.get_u8();
+        let pasid_capabilities = buf.get_u16_le();
+        let reserved = buf.get_u8();
+        let flags = if buf.len() >= 4 {
+            Some(buf.get_u32_le())
+        } else {
+            None
+        };
+
+        RootComplex {
+            memory_access_properties,
+            ats_attribute,
+            pci_segment_number,
+            memory_address_size_limit,
+            pasid_capabilities,
+            reserved,
+            flags,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmmuV1V2 {
+    pub base_address: u64,
+    pub span: u64,
+    pub model: u32,
+    pub flags: u32,
+    pub global_interrupt_offset: u32,
+    pub context_interrupt_count: u32,
+    pub context_interrupt_offset: u32,
+    pub pmu_interrupt_count: u32,
+    pub pmu_interrupt_offset: u32,
+    pub global_interrupts: Vec<(u32, u32)>,
+    pub context_interrupts: Vec<(u32, u32)>,
+    pub pmu_interrupts: Vec<(u32, u32)>,
+}
+
+impl SmmuV1V2 {
+    fn new(node: &Bytes, mut buf: Bytes) -> Self {
+        let base_address = buf.get_u64_le();
+        let span = buf.get_u64_le();
+        let model = buf.get_u32_le();
+        let flags = buf.get_u32_le();
+        let global_interrupt_offset = buf.get_u32_le();
+        let context_interrupt_count = buf.get_u32_le();
+        let context_interrupt_offset = buf.get_u32_le();
+        let pmu_interrupt_count = buf.get_u32_le();
+        let pmu_interrupt_offset = buf.get_u32_le();
+
+        let interrupts = |offset: u32, count: u32| -> Vec<(u32, u32)> {
+            let start = (offset as usize).min(node.len());
+            node.slice(start..)
+                .chunks_exact(8)
+                .take(count as usize)
+                .map(|d| {
+                    let gsiv = u32::from_le_bytes(d[0..4].try_into().unwrap());
+                    let flags = u32::from_le_bytes(d[4..8].try_into().unwrap());
+                    (gsiv, flags)
+                })
+                .collect()
+        };
+        let global_interrupts = interrupts(global_interrupt_offset, 2);
+        let context_interrupts = interrupts(context_interrupt_offset, context_interrupt_count);
+        let pmu_interrupts = interrupts(pmu_interrupt_offset, pmu_interrupt_count);
+
+        SmmuV1V2 {
+            base_address,
+            span,
+            model,
+            flags,
+            global_interrupt_offset,
+            context_interrupt_count,
+            context_interrupt_offset,
+            pmu_interrupt_count,
+            pmu_interrupt_offset,
+            global_interrupts,
+            context_interrupts,
+            pmu_interrupts,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmmuV3 {
+    pub base_address: u64,
+    pub flags: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 4],
+    pub vatos_address: u64,
+    pub model: u32,
+    pub event_gsiv: u32,
+    pub pri_gsiv: u32,
+    pub gerr_gsiv: u32,
+    pub sync_gsiv: u32,
+    pub proximity_domain: Option<u32>,
+    pub device_id_mapping_index: Option<u32>,
+}
+
+impl From<Bytes> for SmmuV3 {
+    fn from(mut buf: Bytes) -> Self {
+        let base_address = buf.get_u64_le();
+        let flags = buf.get_u32_le();
+        let reserved = buf.split_to(4)[..].try_into().unwrap();
+        let vatos_address = buf.get_u64_le();
+        let model = buf.get_u32_le();
+        let event_gsiv = buf.get_u32_le();
+        let pri_gsiv = buf.get_u32_le();
+        let gerr_gsiv = buf.get_u32_le();
+        let sync_gsiv = buf.get_u32_le();
+        let proximity_domain = if buf.len() >= 4 {
+            Some(buf.get_u32_le())
+        } else {
+            None
+        };
+        let device_id_mapping_index = if buf.len() >= 4 {
+            Some(buf.get_u32_le())
+        } else {
+            None
+        };
+
+        SmmuV3 {
+            base_address,
+            flags,
+            reserved,
+            vatos_address,
+            model,
+            event_gsiv,
+            pri_gsiv,
+            gerr_gsiv,
+            sync_gsiv,
+            proximity_domain,
+            device_id_mapping_index,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PerformanceMonitoringCounterGroup {
+    pub page0_base_address: u64,
+    pub overflow_interrupt_gsiv: u32,
+    pub node_reference: u32,
+    pub page1_base_address: Option<u64>,
+}
+
+impl From<Bytes> for PerformanceMonitoringCounterGroup {
+    fn from(mut buf: Bytes) -> Self {
+        let page0_base_address = buf.get_u64_le();
+        let overflow_interrupt_gsiv = buf.get_u32_le();
+        let node_reference = buf.get_u32_le();
+        let page1_base_address = if buf.len() >= 8 {
+            Some(buf.get_u64_le())
+        } else {
+            None
+        };
+
+        PerformanceMonitoringCounterGroup {
+            page0_base_address,
+            overflow_interrupt_gsiv,
+            node_reference,
+            page1_base_address,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReservedMemoryRange {
+    pub flags: u32,
+    pub memory_range_count: u32,
+    pub memory_range_offset: u32,
+    pub memory_ranges: Vec<MemoryRangeDescriptor>,
+}
+
+impl ReservedMemoryRange {
+    fn new(node: &Bytes, mut buf: Bytes) -> Self {
+        let flags = buf.get_u32_le();
+        let memory_range_count = buf.get_u32_le();
+        let memory_range_offset = buf.get_u32_le();
+
+        let start = (memory_range_offset as usize).min(node.len());
+        let memory_ranges = node
+            .slice(start..)
+            .chunks_exact(20)
+            .take(memory_range_count as usize)
+            .map(|d| MemoryRangeDescriptor {
+                base_address: u64::from_le_bytes(d[0..8].try_into().unwrap()),
+                length: u64::from_le_bytes(d[8..16].try_into().unwrap()),
+                reserved: d[16..20].try_into().unwrap(),
+            })
+            .collect();
+
+        ReservedMemoryRange {
+            flags,
+            memory_range_count,
+            memory_range_offset,
+            memory_ranges,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryRangeDescriptor {
+    pub base_address: u64,
+    pub length: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 4],
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn io_remapping() {
+        // ITS group (48..72), SMMUv3 (72..180), root complex (180..240).
+        let data = IoRemapping {
+            signature: "IORT".to_string(),
+            length: 240,
+            revision: 5,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            node_count: 3,
+            node_offset: 48,
+            reserved: [0; 4],
+            nodes: vec![
+                IortNode {
+                    offset: 48,
+                    length: 24,
+                    revision: 4,
+                    identifier: 48,
+                    id_mapping_count: 0,
+                    id_mapping_offset: 0,
+                    data: IortNodeData::ItsGroup(ItsGroup {
+                        its_count: 1,
+                        its_identifiers: vec![7],
+                    }),
+                    id_mappings: vec![],
+                },
+                IortNode {
+                    offset: 72,
+                    length: 108,
+                    revision: 4,
+                    identifier: 72,
+                    id_mapping_count: 2,
+                    id_mapping_offset: 68,
+                    data: IortNodeData::SmmuV3(SmmuV3 {
+                        base_address: 0x2b400000,
+                        flags: 0,
+                        reserved: [0; 4],
+                        vatos_address: 0,
+                        model: 0,
+                        event_gsiv: 106,
+                        pri_gsiv: 107,
+                        gerr_gsiv: 108,
+                        sync_gsiv: 109,
+                        proximity_domain: Some(0),
+                        device_id_mapping_index: Some(0),
+                    }),
+                    id_mappings: vec![
+                        IdMapping {
+                            input_base: 0,
+                            id_count: 0,
+                            output_base: 0x10000,
+                            output_reference: 48,
+                            flags: 1,
+                        },
+                        IdMapping {
+                            input_base: 0,
+                            id_count: 0xffff,
+                            output_base: 0x20000,
+                            output_reference: 48,
+                            flags: 0,
+                        },
+                    ],
+                },
+                IortNode {
+                    offset: 180,
+                    length: 60,
+                    revision: 4,
+                    identifier: 180,
+                    id_mapping_count: 1,
+                    id_mapping_offset: 40,
+                    data: IortNodeData::RootComplex(RootComplex {
+                        memory_access_properties: 0x0000000000000001,
+                        ats_attribute: 1,
+                        pci_segment_number: 0,
+                        memory_address_size_limit: 48,
+                        pasid_capabilities: 0,
+                        reserved: 0,
+                        flags: Some(0),
+                    }),
+                    id_mappings: vec![IdMapping {
+                        input_base: 0,
+                        id_count: 0xffff,
+                        output_base: 0x100,
+                        output_reference: 72,
+                        flags: 0,
+                    }],
+                },
+            ],
+        };
+        let b = Bytes::from(data.clone());
+        let raw = RawAcpiData::from(b);
+        let ret = IoRemapping::from(raw);
+        assert_eq!(data, ret);
+
+        let translated = ret.translate_requester_id(0, 0x0208).unwrap();
+        assert_eq!(translated.stream_id, Some(0x0308));
+        assert_eq!(translated.smmu_node, Some(72));
+        assert_eq!(translated.device_id, 0x20308);
+        assert_eq!(translated.its_node, 48);
+        assert_eq!(translated.its_identifiers, vec![7]);
+
+        assert!(ret.translate_requester_id(1, 0x0208).is_none());
+    }
+}
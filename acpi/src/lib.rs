@@ -1,17 +1,147 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 pub mod error;
 
-#[cfg(target_family = "unix")]
+mod bert;
+mod bmp;
+mod cedt;
+mod disasm;
+mod einj;
+mod erst;
+mod fpdt;
+mod gas;
+mod gtdt;
+mod guid;
+mod hpet;
+mod iort;
+mod lint;
+mod lpit;
+mod memory;
+mod nfit;
+mod pcct;
+mod pci;
+mod pptt;
+mod rsdp;
+mod security;
+#[cfg(feature = "serde")]
+mod serde_hex;
+mod serial;
+mod snapshot;
+mod table;
+mod tpm;
+mod uefi;
+#[cfg(all(feature = "std", target_family = "unix"))]
 mod unix;
-#[cfg(target_family = "windows")]
+mod view;
+#[cfg(all(feature = "std", target_family = "windows"))]
 mod windows;
 
-#[cfg(target_family = "unix")]
-pub use self::unix::{get_raw_table, table_types};
-#[cfg(target_family = "windows")]
+pub use self::bert::{
+    ARM_PROCESSOR_ERROR_SECTION, ArmErrorInformation, ArmProcessorError, BootErrorRecord,
+    ErrorSection, ErrorSeverity, FIRMWARE_ERROR_RECORD_REFERENCE_SECTION,
+    FirmwareErrorRecordReference, GenericErrorDataEntry, GenericErrorStatusBlock,
+    MEMORY_ERROR_SECTION, MemoryError, PCI_EXPRESS_ERROR_SECTION, PROCESSOR_GENERIC_ERROR_SECTION,
+    PciExpressError, ProcessorGenericError,
+};
+pub use self::bmp::Bitmap;
+pub use self::cedt::{
+    CedtStructure, CxlEarlyDiscovery, CxlFixedMemoryWindow, CxlHostBridge, CxlXorInterleaveMath,
+    HostBridgeTarget, InterleaveArithmetic, RcecDownstreamPortAssociation,
+};
+pub use self::disasm::{Disassemble, DisassembledField, Disassembler, Disassembly};
+pub use self::einj::{
+    ErrorInjection, InjectionAction, InjectionInstructionEntry, TriggerErrorAction,
+};
+pub use self::erst::{
+    ActionInterpreter, ErrorRecordSerialization, ExecutionContext, Instruction, InstructionEntry,
+    SerializationAction, SerializationInstructionEntry,
+};
+pub use self::fpdt::{
+    BasicBootPerformanceData, BootPerformanceRecord, BootTimeline, FirmwareBasicBootPerformance,
+    FirmwarePerformanceData, FirmwarePerformanceRecord, PerformanceRecord, PerformanceTablePointer,
+    S3Performance, S3PerformanceRecord, S3ResumePerformanceData, S3SuspendPerformanceData,
+};
+#[cfg(feature = "std")]
+pub use self::gas::SystemMemory;
+pub use self::gas::{
+    AddressSpace, GenericAddress, MockIoPorts, PciConfigRegisters, RegisterAccess,
+};
+pub use self::gtdt::{
+    GenericTimerDescription, GtBlock, GtBlockTimer, PlatformTimer, SbsaGenericWatchdog, TimerFlags,
+};
+pub use self::guid::{Guid, ParseGuidError};
+pub use self::hpet::{HighPrecisionEventTimer, PageProtection};
+pub use self::iort::{
+    IdMapping, IdTranslation, IoRemapping, IortNode, IortNodeData, ItsGroup, MemoryRangeDescriptor,
+    NamedComponent, PerformanceMonitoringCounterGroup, ReservedMemoryRange, RootComplex, SmmuV1V2,
+    SmmuV3,
+};
+pub use self::lint::{
+    BgrtRule, ChecksumRule, FadtMadtRule, Finding, LengthRule, LintRule, Linter, McfgRule,
+    ReservedFieldRule, Severity, UniqueSignatureRule, XsdtRule,
+};
+pub use self::lpit::{LowPowerIdle, LowPowerIdleState, LowPowerIdleSummary};
+pub use self::memory::{IdentityMapper, MappedMemory, PhysicalMemoryMapper, PhysicalMemoryReader};
+pub use self::nfit::{
+    BLOCK_DATA_WINDOW_REGION, BlockControlWindow, BlockDataWindow, CONTROL_REGION, ControlRegion,
+    FlushHintAddress, Interleave, NfitDeviceHandle, NfitStructure, NvdimmFirmwareInterface,
+    NvdimmRegion, PERSISTENT_MEMORY_REGION, PERSISTENT_VIRTUAL_CD_REGION,
+    PERSISTENT_VIRTUAL_DISK_REGION, PlatformCapabilities, RAM_DISK_VIRTUAL_CD_REGION,
+    RAM_DISK_VIRTUAL_DISK_REGION, RegionMapping, SmbiosManagementInformation, SpaRange,
+    SpaRangeType, VOLATILE_MEMORY_REGION,
+};
+pub use self::pcct::{
+    ExtendedSubspace, GenericSubspace, HwReducedSubspace, HwRegistersSubspace, PcctSubspace,
+    PlatformCommunicationsChannel, PlatformInterruptAck,
+};
+pub use self::pci::{EcamReader, PciConfigReader};
+pub use self::pptt::{
+    AllocationType, CacheLevel, CacheProperties, CacheType, IdStructure, PpttStructure,
+    PpttStructureData, ProcessorHierarchyNode, ProcessorPropertiesTopology, TopologyNode,
+};
+pub use self::rsdp::{RootSystemDescriptionPointer, read_table};
+pub use self::security::{
+    IdentificationComponent, MemoryComponent, NamespaceDevice, PcieEndpoint, SecureAccessComponent,
+    SecureDeviceEntry, SecureDevices, WindowsSmmSecurityMitigations,
+};
+pub use self::serial::{
+    DebugDeviceInformation, DebugPort2, DebugPortType, SerialInterface,
+    SerialPortConsoleRedirection,
+};
+pub use self::snapshot::{
+    FieldChange, SnapshotMetadata, SnapshotTable, TableChanges, TableDiff, TableSnapshot,
+};
+pub use self::table::AcpiTable;
+pub use self::tpm::{
+    StartMethod, StartMethodParameters, TrustedComputingClient, TrustedComputingPlatform,
+    TrustedComputingPlatformAlliance, TrustedComputingServer, TrustedPlatformModule2,
+};
+pub use self::uefi::{
+    MemoryDescriptor, PI_SMM_COMMUNICATION_REGION, PiSmmCommunicationRegion, SMM_COMMUNICATION,
+    SmmCommunication, SystemManagementModeCommunication, UefiTableData, UefiTableIdentifier,
+};
+#[cfg(all(feature = "std", target_family = "unix"))]
+pub use self::unix::{
+    SysfsPciConfig, get_boot_error_region, get_boot_graphics_image, get_boot_timeline,
+    get_raw_table, table_types,
+};
+#[cfg(all(feature = "std", target_family = "unix"))]
+use self::unix::{get_firmware_version, get_hostname};
+pub use self::view::{CedtRef, CedtStructureRef, CedtStructures, McfgRef, McfgSpaces, RawTableRef};
+#[cfg(all(feature = "std", target_family = "windows"))]
+use self::windows::{get_firmware_version, get_hostname};
+#[cfg(all(feature = "std", target_family = "windows"))]
 pub use self::windows::{get_raw_table, table_types};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
 use bytes::{Buf, BufMut, Bytes, BytesMut};
+use disasm::header;
 use error::Error;
 
+#[cfg(feature = "std")]
 pub fn get<T>(signature: &str) -> Result<T, Error>
 where
     T: From<RawAcpiData>,
@@ -31,6 +161,19 @@ fn extract_string<const N: usize>(value: &mut Bytes) -> String {
     String::from_utf8_lossy(v).to_string()
 }
 
+fn extract_cstring(value: &[u8]) -> String {
+    let end = value.iter().position(|b| *b == 0).unwrap_or(value.len());
+    String::from_utf8_lossy(&value[..end]).to_string()
+}
+
+fn put_at(b: &mut BytesMut, offset: usize, data: &[u8]) {
+    let end = offset + data.len();
+    if b.len() < end {
+        b.resize(end, 0);
+    }
+    b[offset..end].copy_from_slice(data);
+}
+
 fn string_to_array<const N: usize>(value: &str) -> [u8; N] {
     let mut v = [value.as_bytes(), &[0u8; N]].concat();
     v.truncate(N);
@@ -40,6 +183,7 @@ fn string_to_array<const N: usize>(value: &str) -> [u8; N] {
 // -----------------------------------------------------------------------------------------------
 
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RawAcpiData {
     pub signature: String,
     pub length: u32,
@@ -50,6 +194,7 @@ pub struct RawAcpiData {
     pub oem_revision: u32,
     pub creator_id: u32,
     pub creator_revision: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
     pub acpi_table_data: Bytes,
 }
 
@@ -102,9 +247,27 @@ impl From<RawAcpiData> for Bytes {
     }
 }
 
+impl RawAcpiData {
+    // The whole table, header included, must sum to zero.
+    pub fn is_checksum_valid(&self) -> bool {
+        Bytes::from(self.clone())
+            .iter()
+            .fold(0u8, |sum, b| sum.wrapping_add(*b))
+            == 0
+    }
+}
+
+impl Disassemble for RawAcpiData {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Unknown Table");
+        w.bytes("Table Data", &self.acpi_table_data);
+    }
+}
+
 // -----------------------------------------------------------------------------------------------
 
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BootGraphicsResource {
     pub signature: String,
     pub length: u32,
@@ -116,13 +279,26 @@ pub struct BootGraphicsResource {
     pub creator_id: u32,
     pub creator_revision: u32,
     pub version: u16,
-    pub status: u8,
-    pub image_type: u8,
-    pub image_address: [u8; 8],
+    pub status: BgrtStatus,
+    pub image_type: BgrtImageType,
+    pub image_address: u64,
     pub image_offset_x: u32,
     pub image_offset_y: u32,
 }
 
+impl BootGraphicsResource {
+    pub fn read_image<M>(&self, memory: &mut M) -> Result<Bitmap, Error>
+    where
+        M: PhysicalMemoryReader,
+    {
+        if self.image_type != BgrtImageType::Bitmap {
+            return Err(Error::InvalidImage);
+        }
+
+        Bitmap::read_from(memory, self.image_address)
+    }
+}
+
 impl From<RawAcpiData> for BootGraphicsResource {
     fn from(mut data: RawAcpiData) -> Self {
         let signature = data.signature;
@@ -135,9 +311,9 @@ impl From<RawAcpiData> for BootGraphicsResource {
         let creator_id = data.creator_id;
         let creator_revision = data.creator_revision;
         let version = data.acpi_table_data.get_u16_le();
-        let status = data.acpi_table_data.get_u8();
-        let image_type = data.acpi_table_data.get_u8();
-        let image_address = data.acpi_table_data.split_to(8)[..].try_into().unwrap();
+        let status = BgrtStatus(data.acpi_table_data.get_u8());
+        let image_type = BgrtImageType::from(data.acpi_table_data.get_u8());
+        let image_address = data.acpi_table_data.get_u64_le();
         let image_offset_x = data.acpi_table_data.get_u32_le();
         let image_offset_y = data.acpi_table_data.get_u32_le();
 
@@ -178,18 +354,83 @@ impl From<BootGraphicsResource> for Bytes {
         b.put_u32_le(val.creator_id);
         b.put_u32_le(val.creator_revision);
         b.put_u16_le(val.version);
-        b.put_u8(val.status);
-        b.put_u8(val.image_type);
-        b.put_slice(&val.image_address);
+        b.put_u8(val.status.0);
+        b.put_u8(val.image_type.into());
+        b.put_u64_le(val.image_address);
         b.put_u32_le(val.image_offset_x);
         b.put_u32_le(val.image_offset_y);
         b.freeze()
     }
 }
 
+impl Disassemble for BootGraphicsResource {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Boot Graphics Resource Table");
+        w.u16("Version", self.version);
+        w.u8("Status (decoded below)", self.status.0);
+        w.flag("Displayed", self.status.displayed() as u8);
+        w.flag("Orientation Offset", (self.status.0 >> 1) & 0x03);
+        let image_type = match self.image_type {
+            BgrtImageType::Bitmap => "Bitmap",
+            BgrtImageType::Unknown(_) => "Unknown",
+        };
+        w.annotated(
+            "Image Type",
+            1,
+            u8::from(self.image_type) as u64,
+            image_type,
+        );
+        w.u64("Image Address", self.image_address);
+        w.u32("Image OffsetX", self.image_offset_x);
+        w.u32("Image OffsetY", self.image_offset_y);
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BgrtStatus(pub u8);
+
+impl BgrtStatus {
+    pub fn displayed(&self) -> bool {
+        self.0 & 0x01 != 0
+    }
+
+    // Clockwise rotation in degrees applied to the image relative to the panel's native orientation.
+    pub fn orientation(&self) -> u16 {
+        ((self.0 >> 1) & 0x03) as u16 * 90
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BgrtImageType {
+    #[default]
+    Bitmap,
+    Unknown(u8),
+}
+
+impl From<u8> for BgrtImageType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => BgrtImageType::Bitmap,
+            v => BgrtImageType::Unknown(v),
+        }
+    }
+}
+
+impl From<BgrtImageType> for u8 {
+    fn from(val: BgrtImageType) -> Self {
+        match val {
+            BgrtImageType::Bitmap => 0,
+            BgrtImageType::Unknown(v) => v,
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------------------------
 
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MemoryMappedConfiguration {
     pub signature: String,
     pub length: u32,
@@ -200,10 +441,73 @@ pub struct MemoryMappedConfiguration {
     pub oem_revision: u32,
     pub creator_id: u32,
     pub creator_revision: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
     pub reserved: [u8; 8],
     pub spaces: Vec<MemoryMappedConfigurationSpace>,
 }
 
+impl MemoryMappedConfiguration {
+    pub fn space(&self, segment: u16, bus: u8) -> Option<&MemoryMappedConfigurationSpace> {
+        self.spaces.iter().find(|s| s.contains(segment, bus))
+    }
+
+    pub fn ecam_address(
+        &self,
+        segment: u16,
+        bus: u8,
+        device: u8,
+        function: u8,
+        offset: u16,
+    ) -> Option<u64> {
+        self.space(segment, bus)?
+            .ecam_address(bus, device, function, offset)
+    }
+
+    pub fn validate(&self) -> Vec<ConfigurationSpaceIssue> {
+        let mut issues = vec![];
+
+        for (i, space) in self.spaces.iter().enumerate() {
+            if space.bus_number_start > space.bus_number_end {
+                issues.push(ConfigurationSpaceIssue::InvalidBusRange(i));
+            }
+
+            // Each bus decodes 1 MiB, so the base must be aligned to at least that.
+            if space.base_address & 0xf_ffff != 0 {
+                issues.push(ConfigurationSpaceIssue::Misaligned(i));
+            }
+
+            for (j, other) in self.spaces.iter().enumerate().skip(i + 1) {
+                if space.segment_number == other.segment_number
+                    && space.bus_number_start <= space.bus_number_end
+                    && other.bus_number_start <= other.bus_number_end
+                    && space.bus_number_start <= other.bus_number_end
+                    && other.bus_number_start <= space.bus_number_end
+                {
+                    issues.push(ConfigurationSpaceIssue::Overlap(i, j));
+                }
+            }
+
+            if let Some(next) = self.spaces.get(i + 1) {
+                let key = (space.segment_number, space.bus_number_start);
+                if key > (next.segment_number, next.bus_number_start) {
+                    issues.push(ConfigurationSpaceIssue::OutOfOrder(i + 1));
+                }
+            }
+        }
+
+        // A partial entry at the end is not parsed into `spaces`, but the header still counts it.
+        let parsed = 44 + 16 * self.spaces.len();
+        if let Some(trailing) = (self.length as usize)
+            .checked_sub(parsed)
+            .filter(|n| *n > 0)
+        {
+            issues.push(ConfigurationSpaceIssue::TrailingBytes(trailing));
+        }
+
+        issues
+    }
+}
+
 impl From<RawAcpiData> for MemoryMappedConfiguration {
     fn from(mut data: RawAcpiData) -> Self {
         let signature = data.signature;
@@ -218,9 +522,8 @@ impl From<RawAcpiData> for MemoryMappedConfiguration {
         let reserved = data.acpi_table_data.split_to(8)[..].try_into().unwrap();
         let mut spaces = vec![];
 
-        for d in data.acpi_table_data.chunks(16) {
-            let space = Bytes::from(d.to_owned());
-            spaces.push(MemoryMappedConfigurationSpace::from(space));
+        for d in data.acpi_table_data.chunks_exact(16) {
+            spaces.push(MemoryMappedConfigurationSpace::from(d));
         }
 
         MemoryMappedConfiguration {
@@ -263,24 +566,67 @@ impl From<MemoryMappedConfiguration> for Bytes {
     }
 }
 
+impl Disassemble for MemoryMappedConfiguration {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Memory Mapped Configuration Table");
+        w.bytes("Reserved", &self.reserved);
+        for space in &self.spaces {
+            w.blank();
+            space.disassemble(w);
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------------------------
 
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MemoryMappedConfigurationSpace {
-    pub base_address: [u8; 8],
+    pub base_address: u64,
     pub segment_number: u16,
     pub bus_number_start: u8,
     pub bus_number_end: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
     pub reserved: [u8; 4],
 }
 
+impl MemoryMappedConfigurationSpace {
+    pub fn contains(&self, segment: u16, bus: u8) -> bool {
+        self.segment_number == segment
+            && (self.bus_number_start..=self.bus_number_end).contains(&bus)
+    }
+
+    // The base address corresponds to bus 0 even when the decoded range starts later.
+    pub fn ecam_address(&self, bus: u8, device: u8, function: u8, offset: u16) -> Option<u64> {
+        if !(self.bus_number_start..=self.bus_number_end).contains(&bus)
+            || device >= 32
+            || function >= 8
+            || offset >= 4096
+        {
+            return None;
+        }
+
+        let offset = ((bus as u64) << 20)
+            | ((device as u64) << 15)
+            | ((function as u64) << 12)
+            | offset as u64;
+        self.base_address.checked_add(offset)
+    }
+}
+
 impl From<Bytes> for MemoryMappedConfigurationSpace {
-    fn from(mut buf: Bytes) -> Self {
-        let base_address: [u8; 8] = buf.split_to(8)[..].try_into().unwrap();
+    fn from(buf: Bytes) -> Self {
+        MemoryMappedConfigurationSpace::from(&buf[..])
+    }
+}
+
+impl From<&[u8]> for MemoryMappedConfigurationSpace {
+    fn from(mut buf: &[u8]) -> Self {
+        let base_address = buf.get_u64_le();
         let segment_number = buf.get_u16_le();
         let bus_number_start = buf.get_u8();
         let bus_number_end = buf.get_u8();
-        let reserved = buf.split_to(4)[..].try_into().unwrap();
+        let reserved = buf[..4].try_into().unwrap();
 
         MemoryMappedConfigurationSpace {
             base_address,
@@ -295,7 +641,7 @@ impl From<Bytes> for MemoryMappedConfigurationSpace {
 impl From<MemoryMappedConfigurationSpace> for Bytes {
     fn from(val: MemoryMappedConfigurationSpace) -> Self {
         let mut b = BytesMut::with_capacity(16);
-        b.put_slice(&val.base_address);
+        b.put_u64_le(val.base_address);
         b.put_u16_le(val.segment_number);
         b.put_u8(val.bus_number_start);
         b.put_u8(val.bus_number_end);
@@ -304,91 +650,26 @@ impl From<MemoryMappedConfigurationSpace> for Bytes {
     }
 }
 
-// -----------------------------------------------------------------------------------------------
-
-#[derive(Clone, Debug, Default, PartialEq)]
-pub struct SystemManagementModeCommunication {
-    pub signature: String,
-    pub length: u32,
-    pub revision: u8,
-    pub checksum: u8,
-    pub oem_id: String,
-    pub oem_table_id: String,
-    pub oem_revision: u32,
-    pub creator_id: u32,
-    pub creator_revision: u32,
-    pub identifier: [u8; 16],
-    pub data_offset: u16,
-    pub sw_smi_number: u32,
-    pub buffer_prt_address: [u8; 8],
-    pub invocation_register: Option<[u8; 12]>,
-}
-
-impl From<RawAcpiData> for SystemManagementModeCommunication {
-    fn from(mut data: RawAcpiData) -> Self {
-        let signature = data.signature;
-        let length = data.length;
-        let revision = data.revision;
-        let checksum = data.checksum;
-        let oem_id = data.oem_id;
-        let oem_table_id = data.oem_table_id;
-        let oem_revision = data.oem_revision;
-        let creator_id = data.creator_id;
-        let creator_revision = data.creator_revision;
-        let identifier = data.acpi_table_data.split_to(16)[..].try_into().unwrap();
-        let data_offset = data.acpi_table_data.get_u16_le();
-        let sw_smi_number = data.acpi_table_data.get_u32_le();
-        let buffer_prt_address = data.acpi_table_data.split_to(8)[..].try_into().unwrap();
-        let invocation_register = if !data.acpi_table_data.is_empty() {
-            Some(data.acpi_table_data.split_to(12)[..].try_into().unwrap())
-        } else {
-            None
-        };
-
-        SystemManagementModeCommunication {
-            signature,
-            length,
-            revision,
-            checksum,
-            oem_id,
-            oem_table_id,
-            oem_revision,
-            creator_id,
-            creator_revision,
-            identifier,
-            data_offset,
-            sw_smi_number,
-            buffer_prt_address,
-            invocation_register,
-        }
+impl Disassemble for MemoryMappedConfigurationSpace {
+    fn disassemble(&self, w: &mut Disassembler) {
+        w.u64("Base Address", self.base_address);
+        w.u16("Segment Group Number", self.segment_number);
+        w.u8("Start Bus Number", self.bus_number_start);
+        w.u8("End Bus Number", self.bus_number_end);
+        w.bytes("Reserved", &self.reserved);
     }
 }
 
-impl From<SystemManagementModeCommunication> for Bytes {
-    fn from(val: SystemManagementModeCommunication) -> Self {
-        let signature = string_to_array::<4>(&val.signature);
-        let oem_id = string_to_array::<6>(&val.oem_id);
-        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
-
-        let mut b = BytesMut::with_capacity(val.length as usize);
-        b.put_slice(&signature);
-        b.put_u32_le(val.length);
-        b.put_u8(val.revision);
-        b.put_u8(val.checksum);
-        b.put_slice(&oem_id);
-        b.put_slice(&oem_table_id);
-        b.put_u32_le(val.oem_revision);
-        b.put_u32_le(val.creator_id);
-        b.put_u32_le(val.creator_revision);
-        b.put_slice(&val.identifier);
-        b.put_u16_le(val.data_offset);
-        b.put_u32_le(val.sw_smi_number);
-        b.put_slice(&val.buffer_prt_address);
-        if let Some(v) = val.invocation_register {
-            b.put_slice(&v);
-        }
-        b.freeze()
-    }
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConfigurationSpaceIssue {
+    // Indexes into `MemoryMappedConfiguration::spaces`.
+    InvalidBusRange(usize),
+    Misaligned(usize),
+    Overlap(usize, usize),
+    OutOfOrder(usize),
+    // Bytes after the last complete space.
+    TrailingBytes(usize),
 }
 
 // -----------------------------------------------------------------------------------------------
@@ -414,6 +695,16 @@ mod tests {
         let b = Bytes::from(data.clone());
         let ret = RawAcpiData::from(b);
         assert_eq!(data, ret);
+        assert!(!ret.is_checksum_valid());
+
+        let sum = Bytes::from(data.clone())
+            .iter()
+            .fold(0u8, |sum, b| sum.wrapping_add(*b));
+        let fixed = RawAcpiData {
+            checksum: data.checksum.wrapping_sub(sum),
+            ..data
+        };
+        assert!(fixed.is_checksum_valid());
     }
 
     #[test]
@@ -429,9 +720,9 @@ mod tests {
             creator_id: 4,
             creator_revision: 5,
             version: 6,
-            status: 7,
-            image_type: 8,
-            image_address: [0, 1, 2, 3, 4, 5, 6, 7],
+            status: BgrtStatus(7),
+            image_type: BgrtImageType::Unknown(8),
+            image_address: 0x0706050403020100,
             image_offset_x: 9,
             image_offset_y: 10,
         };
@@ -439,6 +730,13 @@ mod tests {
         let raw = RawAcpiData::from(b);
         let ret = BootGraphicsResource::from(raw);
         assert_eq!(data, ret);
+        assert!(ret.status.displayed());
+        assert_eq!(ret.status.orientation(), 270);
+        assert!(
+            ret.disassembly()
+                .to_string()
+                .contains("Orientation Offset : 3\n")
+        );
     }
 
     #[test]
@@ -455,7 +753,7 @@ mod tests {
             creator_revision: 5,
             reserved: [0, 1, 2, 3, 4, 5, 6, 7],
             spaces: vec![MemoryMappedConfigurationSpace {
-                base_address: [6, 7, 8, 9, 10, 11, 12, 13],
+                base_address: 0x0d0c0b0a09080706,
                 segment_number: 6,
                 bus_number_start: 7,
                 bus_number_end: 8,
@@ -469,26 +767,124 @@ mod tests {
     }
 
     #[test]
-    fn system_management_mode_communication() {
-        let data = SystemManagementModeCommunication {
-            signature: "ABCD".to_string(),
-            length: 36,
+    fn memory_mapped_configuration_disassembly() {
+        let data = MemoryMappedConfiguration {
+            signature: "MCFG".to_string(),
+            length: 60,
             revision: 1,
             checksum: 2,
-            oem_id: "EF".to_string(),
-            oem_table_id: "GHI".to_string(),
+            oem_id: "OEMID".to_string(),
+            oem_table_id: "OEMTABLE".to_string(),
             oem_revision: 3,
-            creator_id: 4,
+            creator_id: u32::from_le_bytes(*b"INTL"),
             creator_revision: 5,
-            identifier: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
-            data_offset: 6,
-            sw_smi_number: 7,
-            buffer_prt_address: [16, 17, 18, 19, 20, 21, 22, 23],
-            invocation_register: Some([1u8; 12]),
+            reserved: [0; 8],
+            spaces: vec![MemoryMappedConfigurationSpace {
+                base_address: 0xe000_0000,
+                segment_number: 0,
+                bus_number_start: 0,
+                bus_number_end: 0xff,
+                reserved: [0; 4],
+            }],
         };
-        let b = Bytes::from(data.clone());
-        let raw = RawAcpiData::from(b);
-        let ret = SystemManagementModeCommunication::from(raw);
+        let out = data.disassembly().to_string();
+        let lines = out.lines().collect::<Vec<&str>>();
+        assert_eq!(
+            lines[0],
+            "[000h 0000 004]                    Signature : \"MCFG\"    [Memory Mapped Configuration Table]"
+        );
+        assert_eq!(
+            lines[7],
+            "[01Ch 0028 004]              Asl Compiler ID : \"INTL\""
+        );
+        assert!(out.contains("[02Ch 0044 008]                 Base Address : 00000000E0000000\n"));
+        assert!(out.contains("[037h 0055 001]               End Bus Number : FF\n"));
+    }
+
+    #[test]
+    fn memory_mapped_configuration_ecam() {
+        let space = |base_address, segment_number, bus_number_start, bus_number_end| {
+            MemoryMappedConfigurationSpace {
+                base_address,
+                segment_number,
+                bus_number_start,
+                bus_number_end,
+                reserved: [0; 4],
+            }
+        };
+        let mut data = MemoryMappedConfiguration {
+            spaces: vec![
+                space(0xe000_0000, 0, 0, 0x7f),
+                space(0x4_0000_0000, 1, 0x80, 0xff),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(data.ecam_address(0, 0, 0, 0, 0), Some(0xe000_0000));
+        assert_eq!(data.ecam_address(0, 1, 2, 3, 0x10), Some(0xe011_3010));
+        assert_eq!(data.ecam_address(1, 0x80, 0, 0, 0), Some(0x4_0800_0000));
+        assert_eq!(data.ecam_address(0, 0x80, 0, 0, 0), None);
+        assert_eq!(data.ecam_address(0, 0, 32, 0, 0), None);
+        assert!(data.validate().is_empty());
+
+        data.spaces.push(space(0xd000_0800, 0, 0x70, 0x60));
+        data.spaces.push(space(0xd000_0000, 1, 0xc0, 0xc1));
+        assert_eq!(
+            data.validate(),
+            vec![
+                ConfigurationSpaceIssue::Overlap(1, 3),
+                ConfigurationSpaceIssue::OutOfOrder(2),
+                ConfigurationSpaceIssue::InvalidBusRange(2),
+                ConfigurationSpaceIssue::Misaligned(2),
+            ]
+        );
+
+        let raw = RawAcpiData {
+            signature: "MCFG".to_string(),
+            length: 44 + 16 + 3,
+            acpi_table_data: Bytes::from_static(&[
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xe0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0, 0, 0, 0, 1, 2,
+                3,
+            ]),
+            ..Default::default()
+        };
+        let data = MemoryMappedConfiguration::from(raw);
+        assert_eq!(data.spaces.len(), 1);
+        assert_eq!(
+            data.validate(),
+            vec![ConfigurationSpaceIssue::TrailingBytes(3)]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_json() {
+        let data = MemoryMappedConfiguration {
+            signature: "MCFG".to_string(),
+            reserved: [0, 1, 2, 3, 4, 5, 6, 0xff],
+            spaces: vec![MemoryMappedConfigurationSpace {
+                base_address: 0xe000_0000,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&data).unwrap();
+        assert_eq!(json["reserved"], "00010203040506ff");
+        assert_eq!(json["spaces"][0]["base_address"], 0xe000_0000u64);
+        let ret: MemoryMappedConfiguration = serde_json::from_value(json).unwrap();
         assert_eq!(data, ret);
+
+        let raw = RawAcpiData {
+            signature: "UEFI".to_string(),
+            acpi_table_data: Bytes::from_static(&[0xde, 0xad]),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&raw).unwrap();
+        assert!(json.contains(r#""acpi_table_data":"dead""#));
+        assert_eq!(serde_json::from_str::<RawAcpiData>(&json).unwrap(), raw);
+
+        let guid = serde_json::to_string(&uefi::SMM_COMMUNICATION).unwrap();
+        assert_eq!(guid, r#""c68ed8e2-9dc6-4cbd-9d94-db65acc5c332""#);
+        assert!(serde_json::from_str::<MemoryMappedConfiguration>(r#"{"reserved":"0"}"#).is_err());
     }
 }
@@ -0,0 +1,1064 @@
+use super::disasm::{Disassemble, Disassembler};
+use super::snapshot::SnapshotTable;
+use super::{AcpiTable, ConfigurationSpaceIssue, RawAcpiData, TableSnapshot};
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+// Checks in the spirit of fwts: https://wiki.ubuntu.com/FirmwareTestSuite
+
+const HEADER_LENGTH: usize = 36;
+
+// Tables the specifications allow only one instance of.
+const UNIQUE_SIGNATURES: &[&str] = &[
+    "APIC", "BERT", "BGRT", "CEDT", "DSDT", "EINJ", "ERST", "FACP", "FACS", "FPDT", "GTDT", "IORT",
+    "LPIT", "MCFG", "PPTT", "RSDT", "SPCR", "TCPA", "TPM2", "WSMT", "XSDT",
+];
+
+// Tables without the standard header, so there is no checksum or revision to check.
+const HEADERLESS_SIGNATURES: &[&str] = &["FACS"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Finding {
+    pub rule: String,
+    pub severity: Severity,
+    pub signature: String,
+    pub instance: u32,
+    pub message: String,
+}
+
+impl Finding {
+    fn new(
+        rule: &dyn LintRule,
+        severity: Severity,
+        table: &SnapshotTable,
+        message: String,
+    ) -> Self {
+        Finding {
+            rule: rule.name().to_string(),
+            severity,
+            signature: table.signature.clone(),
+            instance: table.instance,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} [{}] {} {}: {}",
+            self.severity, self.rule, self.signature, self.instance, self.message
+        )
+    }
+}
+
+pub trait LintRule {
+    fn name(&self) -> &'static str;
+
+    fn description(&self) -> &'static str;
+
+    fn check(&self, tables: &[SnapshotTable]) -> Vec<Finding>;
+}
+
+// -----------------------------------------------------------------------------------------------
+
+pub struct Linter {
+    rules: Vec<(Box<dyn LintRule>, bool)>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        let mut linter = Linter::empty();
+        linter.add_rule(Box::new(ChecksumRule));
+        linter.add_rule(Box::new(LengthRule));
+        linter.add_rule(Box::new(ReservedFieldRule));
+        linter.add_rule(Box::new(McfgRule));
+        linter.add_rule(Box::new(BgrtRule));
+        linter.add_rule(Box::new(FadtMadtRule));
+        linter.add_rule(Box::new(XsdtRule));
+        linter.add_rule(Box::new(UniqueSignatureRule));
+        linter
+    }
+}
+
+impl Linter {
+    pub fn empty() -> Self {
+        Linter { rules: vec![] }
+    }
+
+    pub fn add_rule(&mut self, rule: Box<dyn LintRule>) {
+        self.rules.push((rule, true));
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = (&dyn LintRule, bool)> {
+        self.rules.iter().map(|(r, enabled)| (r.as_ref(), *enabled))
+    }
+
+    // Returns false if no rule has that name.
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        match self.rules.iter_mut().find(|(r, _)| r.name() == name) {
+            Some(rule) => {
+                rule.1 = enabled;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn run(&self, snapshot: &TableSnapshot) -> Vec<Finding> {
+        let mut findings = vec![];
+        for (rule, enabled) in &self.rules {
+            if *enabled {
+                findings.extend(rule.check(&snapshot.tables));
+            }
+        }
+        findings
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+pub struct ChecksumRule;
+
+impl LintRule for ChecksumRule {
+    fn name(&self) -> &'static str {
+        "checksum"
+    }
+
+    fn description(&self) -> &'static str {
+        "Table bytes, header included, sum to zero"
+    }
+
+    fn check(&self, tables: &[SnapshotTable]) -> Vec<Finding> {
+        tables
+            .iter()
+            .filter(|t| has_header(t) && t.data.len() >= HEADER_LENGTH)
+            .filter(|t| !t.raw().is_checksum_valid())
+            .map(|t| Finding::new(self, Severity::Error, t, "checksum mismatch".to_string()))
+            .collect()
+    }
+}
+
+pub struct LengthRule;
+
+impl LintRule for LengthRule {
+    fn name(&self) -> &'static str {
+        "length"
+    }
+
+    fn description(&self) -> &'static str {
+        "Header length matches the size of the table data"
+    }
+
+    fn check(&self, tables: &[SnapshotTable]) -> Vec<Finding> {
+        let mut findings = vec![];
+        for table in tables.iter().filter(|t| has_header(t)) {
+            if table.data.len() < HEADER_LENGTH {
+                let message = format!("only {} bytes, shorter than the header", table.data.len());
+                findings.push(Finding::new(self, Severity::Error, table, message));
+            } else if table.raw().length as usize != table.data.len() {
+                let message = format!(
+                    "header length {} but {} bytes of data",
+                    table.raw().length,
+                    table.data.len()
+                );
+                findings.push(Finding::new(self, Severity::Error, table, message));
+            }
+        }
+        findings
+    }
+}
+
+pub struct ReservedFieldRule;
+
+impl LintRule for ReservedFieldRule {
+    fn name(&self) -> &'static str {
+        "reserved"
+    }
+
+    fn description(&self) -> &'static str {
+        "Reserved fields of typed tables are zero"
+    }
+
+    fn check(&self, tables: &[SnapshotTable]) -> Vec<Finding> {
+        let mut findings = vec![];
+        for table in tables.iter().filter(|t| is_well_formed(t)) {
+            let typed = AcpiTable::from(table.raw());
+            if !typed.is_typed() {
+                continue;
+            }
+
+            let mut w = Disassembler::default();
+            typed.disassemble(&mut w);
+            for field in w.fields() {
+                let nonzero = field
+                    .value
+                    .chars()
+                    .any(|c| c.is_ascii_hexdigit() && c != '0');
+                if field.name.starts_with("Reserved") && nonzero {
+                    let message = format!(
+                        "{} at offset {:#x} is {}",
+                        field.name, field.offset, field.value
+                    );
+                    findings.push(Finding::new(self, Severity::Warning, table, message));
+                }
+            }
+        }
+        findings
+    }
+}
+
+pub struct McfgRule;
+
+impl LintRule for McfgRule {
+    fn name(&self) -> &'static str {
+        "mcfg"
+    }
+
+    fn description(&self) -> &'static str {
+        "MCFG bus ranges are valid, aligned, sorted and do not overlap"
+    }
+
+    fn check(&self, tables: &[SnapshotTable]) -> Vec<Finding> {
+        let mut findings = vec![];
+        for table in tables.iter().filter(|t| is_well_formed(t)) {
+            let AcpiTable::Mcfg(mcfg) = AcpiTable::from(table.raw()) else {
+                continue;
+            };
+
+            for issue in mcfg.validate() {
+                let (severity, message) = match issue {
+                    ConfigurationSpaceIssue::InvalidBusRange(i) => {
+                        let space = &mcfg.spaces[i];
+                        let message = format!(
+                            "space {} bus range {:02x}-{:02x} is inverted",
+                            i, space.bus_number_start, space.bus_number_end
+                        );
+                        (Severity::Error, message)
+                    }
+                    ConfigurationSpaceIssue::Misaligned(i) => {
+                        let message = format!(
+                            "space {} base address {:#x} is not 1 MiB aligned",
+                            i, mcfg.spaces[i].base_address
+                        );
+                        (Severity::Error, message)
+                    }
+                    ConfigurationSpaceIssue::Overlap(i, j) => {
+                        let message = format!("spaces {} and {} decode the same buses", i, j);
+                        (Severity::Error, message)
+                    }
+                    ConfigurationSpaceIssue::OutOfOrder(i) => {
+                        let message = format!("space {} is not sorted by segment and bus", i);
+                        (Severity::Info, message)
+                    }
+                    ConfigurationSpaceIssue::TrailingBytes(n) => {
+                        let message = format!("{} bytes after the last space", n);
+                        (Severity::Error, message)
+                    }
+                };
+                findings.push(Finding::new(self, severity, table, message));
+            }
+        }
+        findings
+    }
+}
+
+pub struct BgrtRule;
+
+impl LintRule for BgrtRule {
+    fn name(&self) -> &'static str {
+        "bgrt"
+    }
+
+    fn description(&self) -> &'static str {
+        "BGRT version, status and image type hold defined values"
+    }
+
+    fn check(&self, tables: &[SnapshotTable]) -> Vec<Finding> {
+        let mut findings = vec![];
+        for table in tables.iter().filter(|t| is_well_formed(t)) {
+            let AcpiTable::Bgrt(bgrt) = AcpiTable::from(table.raw()) else {
+                continue;
+            };
+
+            if bgrt.version != 1 {
+                let message = format!("version is {}, must be 1", bgrt.version);
+                findings.push(Finding::new(self, Severity::Error, table, message));
+            }
+
+            // Bit 0 is the displayed flag and bits 1-2 the orientation.
+            if bgrt.status.0 & 0xf8 != 0 {
+                let message = format!("status {:#04x} sets reserved bits", bgrt.status.0);
+                findings.push(Finding::new(self, Severity::Warning, table, message));
+            }
+
+            let image_type = u8::from(bgrt.image_type);
+            if image_type != 0 {
+                let message = format!("image type {} is not a bitmap", image_type);
+                findings.push(Finding::new(self, Severity::Error, table, message));
+            }
+
+            if bgrt.image_address == 0 {
+                let message = "image address is zero".to_string();
+                findings.push(Finding::new(self, Severity::Error, table, message));
+            }
+        }
+        findings
+    }
+}
+
+// There are no typed FADT and MADT parsers, so fields are read at their spec offsets.
+pub struct FadtMadtRule;
+
+impl FadtMadtRule {
+    const FADT_FIRMWARE_CTRL: usize = 36;
+    const FADT_DSDT: usize = 40;
+    const FADT_FLAGS: usize = 112;
+    const FADT_X_FIRMWARE_CTRL: usize = 132;
+    const FADT_X_DSDT: usize = 140;
+    const FADT_HW_REDUCED_ACPI: u32 = 1 << 20;
+
+    const MADT_FLAGS: usize = 40;
+    const MADT_STRUCTURES: usize = 44;
+    const MADT_PCAT_COMPAT: u32 = 1 << 0;
+    // Local APIC, Local SAPIC, Local x2APIC, GICC, Core PIC and RINTC.
+    const MADT_PROCESSOR_TYPES: &[u8] = &[0x00, 0x07, 0x09, 0x0b, 0x11, 0x18];
+
+    // Minimum FADT length of each revision, from ACPI 2.0 onwards.
+    fn fadt_minimum_length(revision: u8) -> usize {
+        match revision {
+            0..=2 => 116,
+            3 | 4 => 244,
+            5 => 268,
+            _ => 276,
+        }
+    }
+
+    fn check_fadt(&self, table: &SnapshotTable, findings: &mut Vec<Finding>) {
+        let data = &table.data;
+        // Tables shorter than the header are left to the length rule.
+        let Some(&revision) = data.get(8) else {
+            return;
+        };
+        let minimum = FadtMadtRule::fadt_minimum_length(revision);
+        if data.len() < minimum {
+            let message = format!(
+                "{} bytes, revision {} requires at least {}",
+                data.len(),
+                revision,
+                minimum
+            );
+            findings.push(Finding::new(self, Severity::Error, table, message));
+        }
+
+        let pairs = [
+            (
+                "FIRMWARE_CTRL",
+                FadtMadtRule::FADT_FIRMWARE_CTRL,
+                FadtMadtRule::FADT_X_FIRMWARE_CTRL,
+            ),
+            ("DSDT", FadtMadtRule::FADT_DSDT, FadtMadtRule::FADT_X_DSDT),
+        ];
+        for (name, offset, x_offset) in pairs {
+            let (Some(legacy), Some(extended)) = (read_u32(data, offset), read_u64(data, x_offset))
+            else {
+                continue;
+            };
+            if legacy != 0 && extended != 0 && legacy as u64 != extended {
+                let message = format!(
+                    "{} {:#x} and X_{} {:#x} differ",
+                    name, legacy, name, extended
+                );
+                findings.push(Finding::new(self, Severity::Error, table, message));
+            }
+        }
+    }
+
+    fn check_madt(&self, table: &SnapshotTable, findings: &mut Vec<Finding>) {
+        let data = &table.data;
+        let mut offset = FadtMadtRule::MADT_STRUCTURES;
+        let mut processors = 0;
+        while offset < data.len() {
+            let length = data.get(offset + 1).copied().unwrap_or_default() as usize;
+            if length < 2 || offset + length > data.len() {
+                let message = format!(
+                    "structure type {} at offset {:#x} has invalid length {}",
+                    data[offset], offset, length
+                );
+                findings.push(Finding::new(self, Severity::Error, table, message));
+                return;
+            }
+
+            if FadtMadtRule::MADT_PROCESSOR_TYPES.contains(&data[offset]) {
+                processors += 1;
+            }
+            offset += length;
+        }
+
+        if processors == 0 {
+            let message = "no processor interrupt controller structures".to_string();
+            findings.push(Finding::new(self, Severity::Error, table, message));
+        }
+    }
+}
+
+impl LintRule for FadtMadtRule {
+    fn name(&self) -> &'static str {
+        "fadt-madt"
+    }
+
+    fn description(&self) -> &'static str {
+        "FADT and MADT are well formed and agree with each other"
+    }
+
+    fn check(&self, tables: &[SnapshotTable]) -> Vec<Finding> {
+        let mut findings = vec![];
+        let fadt = tables.iter().find(|t| t.signature == "FACP");
+        let madt = tables.iter().find(|t| t.signature == "APIC");
+
+        if let Some(fadt) = fadt {
+            self.check_fadt(fadt, &mut findings);
+        }
+
+        if let Some(madt) = madt {
+            self.check_madt(madt, &mut findings);
+        }
+
+        if let (Some(fadt), Some(madt)) = (fadt, madt) {
+            let fadt_flags = read_u32(&fadt.data, FadtMadtRule::FADT_FLAGS).unwrap_or_default();
+            let madt_flags = read_u32(&madt.data, FadtMadtRule::MADT_FLAGS).unwrap_or_default();
+            if fadt_flags & FadtMadtRule::FADT_HW_REDUCED_ACPI != 0
+                && madt_flags & FadtMadtRule::MADT_PCAT_COMPAT != 0
+            {
+                let message = "PCAT_COMPAT is set but the FADT is hardware-reduced".to_string();
+                findings.push(Finding::new(self, Severity::Warning, madt, message));
+            }
+        }
+
+        findings
+    }
+}
+
+// Physical addresses are only known for some sources, so tables without one are not reported.
+pub struct XsdtRule;
+
+impl LintRule for XsdtRule {
+    fn name(&self) -> &'static str {
+        "xsdt"
+    }
+
+    fn description(&self) -> &'static str {
+        "RSDT and XSDT entries point to tables that exist"
+    }
+
+    fn check(&self, tables: &[SnapshotTable]) -> Vec<Finding> {
+        let mut findings = vec![];
+        let addresses = tables
+            .iter()
+            .filter_map(|t| t.address)
+            .collect::<Vec<u64>>();
+
+        for table in tables.iter().filter(|t| is_well_formed(t)) {
+            let entries = match table.signature.as_str() {
+                "XSDT" => table.data[HEADER_LENGTH..]
+                    .chunks_exact(8)
+                    .map(|e| u64::from_le_bytes(e.try_into().unwrap()))
+                    .collect::<Vec<u64>>(),
+                "RSDT" => table.data[HEADER_LENGTH..]
+                    .chunks_exact(4)
+                    .map(|e| u32::from_le_bytes(e.try_into().unwrap()) as u64)
+                    .collect::<Vec<u64>>(),
+                _ => continue,
+            };
+
+            for (i, entry) in entries.into_iter().enumerate() {
+                if entry == 0 {
+                    let message = format!("entry {} is null", i);
+                    findings.push(Finding::new(self, Severity::Error, table, message));
+                } else if !addresses.is_empty() && !addresses.contains(&entry) {
+                    let message = format!("entry {} points to {:#x}, no table there", i, entry);
+                    findings.push(Finding::new(self, Severity::Error, table, message));
+                }
+            }
+        }
+        findings
+    }
+}
+
+pub struct UniqueSignatureRule;
+
+impl LintRule for UniqueSignatureRule {
+    fn name(&self) -> &'static str {
+        "unique"
+    }
+
+    fn description(&self) -> &'static str {
+        "Tables that must be unique appear once"
+    }
+
+    fn check(&self, tables: &[SnapshotTable]) -> Vec<Finding> {
+        let mut findings = vec![];
+        for signature in UNIQUE_SIGNATURES {
+            let mut instances = tables.iter().filter(|t| t.signature == *signature);
+            let Some(first) = instances.next() else {
+                continue;
+            };
+
+            let count = instances.count() + 1;
+            if count > 1 {
+                let message = format!("{} tables found, only one is allowed", count);
+                findings.push(Finding::new(self, Severity::Error, first, message));
+            }
+        }
+        findings
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+// Typed parsers assume the header length matches the data, which the length rule reports on.
+fn is_well_formed(table: &SnapshotTable) -> bool {
+    has_header(table)
+        && table.data.len() >= HEADER_LENGTH
+        && RawAcpiData::from(table.data.clone()).length as usize == table.data.len()
+}
+
+fn has_header(table: &SnapshotTable) -> bool {
+    !HEADERLESS_SIGNATURES.contains(&table.signature.as_str())
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let value = data.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(value.try_into().unwrap()))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    let value = data.get(offset..offset + 8)?;
+    Some(u64::from_le_bytes(value.try_into().unwrap()))
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BgrtImageType, BgrtStatus, BootGraphicsResource, MemoryMappedConfiguration,
+        MemoryMappedConfigurationSpace,
+    };
+    use bytes::{BufMut, Bytes, BytesMut};
+
+    fn run(rule: &str, snapshot: &TableSnapshot) -> Vec<Finding> {
+        let mut linter = Linter::default();
+        let names = linter.rules().map(|(r, _)| r.name()).collect::<Vec<_>>();
+        for name in names {
+            assert!(linter.set_enabled(name, name == rule));
+        }
+        linter.run(snapshot)
+    }
+
+    #[test]
+    fn linter_clean() {
+        let mut fadt = BytesMut::zeroed(276 - HEADER_LENGTH);
+        fadt[4..8].copy_from_slice(&0x1000u32.to_le_bytes());
+        fadt[104..112].copy_from_slice(&0x1000u64.to_le_bytes());
+
+        let mut madt = BytesMut::new();
+        madt.put_u32_le(0xfee0_0000);
+        madt.put_u32_le(1);
+        madt.put_slice(&[0, 8, 0, 0, 1, 0, 0, 0]);
+
+        // The FACS has no checksum, so only its length is set.
+        let mut facs = BytesMut::zeroed(64);
+        facs[..4].copy_from_slice(b"FACS");
+        facs[4..8].copy_from_slice(&64u32.to_le_bytes());
+
+        let snapshot = TableSnapshot {
+            tables: vec![
+                SnapshotTable {
+                    signature: "FACP".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "FACP".to_string(),
+                        length: 276,
+                        revision: 6,
+                        checksum: 171,
+                        acpi_table_data: fadt.freeze(),
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "APIC".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "APIC".to_string(),
+                        length: 52,
+                        revision: 5,
+                        checksum: 194,
+                        acpi_table_data: madt.freeze(),
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "FACS".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: facs.freeze(),
+                },
+                SnapshotTable {
+                    signature: "SSDT".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "SSDT".to_string(),
+                        length: 39,
+                        revision: 2,
+                        checksum: 147,
+                        acpi_table_data: Bytes::from_static(&[1, 2, 3]),
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "SSDT".to_string(),
+                    instance: 2,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "SSDT".to_string(),
+                        length: 39,
+                        revision: 2,
+                        checksum: 138,
+                        acpi_table_data: Bytes::from_static(&[4, 5, 6]),
+                        ..Default::default()
+                    }),
+                },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(Linter::default().run(&snapshot), vec![]);
+    }
+
+    #[test]
+    fn linter_toggle() {
+        let mut linter = Linter::default();
+        assert!(linter.rules().all(|(_, enabled)| enabled));
+        assert!(linter.set_enabled("checksum", false));
+        assert!(!linter.set_enabled("missing", false));
+
+        // The checksum is for a body of [1].
+        let snapshot = TableSnapshot {
+            tables: vec![SnapshotTable {
+                signature: "SSDT".to_string(),
+                instance: 1,
+                address: None,
+                data: Bytes::from(RawAcpiData {
+                    signature: "SSDT".to_string(),
+                    length: 37,
+                    revision: 2,
+                    checksum: 154,
+                    acpi_table_data: Bytes::from_static(&[2]),
+                    ..Default::default()
+                }),
+            }],
+            ..Default::default()
+        };
+        assert_eq!(linter.run(&snapshot), vec![]);
+
+        linter.set_enabled("checksum", true);
+        let findings = linter.run(&snapshot);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, "checksum");
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(
+            findings[0].to_string(),
+            "Error [checksum] SSDT 1: checksum mismatch"
+        );
+    }
+
+    #[test]
+    fn length_rule() {
+        let snapshot = TableSnapshot {
+            tables: vec![
+                SnapshotTable {
+                    signature: "SSDT".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "SSDT".to_string(),
+                        length: 39,
+                        revision: 2,
+                        acpi_table_data: Bytes::from_static(&[1, 2]),
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "DSDT".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from_static(b"DSDT"),
+                },
+                SnapshotTable {
+                    signature: "FACS".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from_static(b"FACS"),
+                },
+            ],
+            ..Default::default()
+        };
+        let findings = run("length", &snapshot);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].message, "header length 39 but 38 bytes of data");
+        assert_eq!(findings[1].signature, "DSDT");
+    }
+
+    #[test]
+    fn reserved_and_mcfg_rules() {
+        let mcfg = MemoryMappedConfiguration {
+            signature: "MCFG".to_string(),
+            length: 44 + 16 * 2,
+            revision: 1,
+            reserved: [0, 0, 0, 1, 0, 0, 0, 0],
+            spaces: vec![
+                MemoryMappedConfigurationSpace {
+                    base_address: 0xe000_0000,
+                    segment_number: 0,
+                    bus_number_start: 0x10,
+                    bus_number_end: 0x0f,
+                    reserved: [0; 4],
+                },
+                MemoryMappedConfigurationSpace {
+                    base_address: 0xf000_8000,
+                    segment_number: 0,
+                    bus_number_start: 0x20,
+                    bus_number_end: 0x2f,
+                    reserved: [0; 4],
+                },
+            ],
+            ..Default::default()
+        };
+        let snapshot = TableSnapshot {
+            tables: vec![SnapshotTable {
+                signature: "MCFG".to_string(),
+                instance: 1,
+                address: None,
+                data: Bytes::from(mcfg),
+            }],
+            ..Default::default()
+        };
+        let findings = run("reserved", &snapshot);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].message,
+            "Reserved at offset 0x24 is 00 00 00 01 00 00 00 00"
+        );
+
+        let findings = run("mcfg", &snapshot);
+        let messages = findings
+            .iter()
+            .map(|f| f.message.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "space 0 bus range 10-0f is inverted",
+                "space 1 base address 0xf0008000 is not 1 MiB aligned",
+            ]
+        );
+    }
+
+    #[test]
+    fn bgrt_rule() {
+        let bgrt = BootGraphicsResource {
+            signature: "BGRT".to_string(),
+            length: 56,
+            revision: 1,
+            version: 2,
+            status: BgrtStatus(0x09),
+            image_type: BgrtImageType::Unknown(1),
+            image_address: 0x8000_0000,
+            ..Default::default()
+        };
+        let snapshot = TableSnapshot {
+            tables: vec![SnapshotTable {
+                signature: "BGRT".to_string(),
+                instance: 1,
+                address: None,
+                data: Bytes::from(bgrt),
+            }],
+            ..Default::default()
+        };
+        let findings = run("bgrt", &snapshot);
+        let messages = findings
+            .iter()
+            .map(|f| f.message.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "version is 2, must be 1",
+                "status 0x09 sets reserved bits",
+                "image type 1 is not a bitmap",
+            ]
+        );
+    }
+
+    #[test]
+    fn fadt_madt_rule() {
+        let mut fadt = BytesMut::zeroed(276 - HEADER_LENGTH);
+        fadt[4..8].copy_from_slice(&0x1000u32.to_le_bytes());
+        fadt[76..80].copy_from_slice(&FadtMadtRule::FADT_HW_REDUCED_ACPI.to_le_bytes());
+        fadt[104..112].copy_from_slice(&0x2000u64.to_le_bytes());
+
+        let mut madt = BytesMut::new();
+        madt.put_u32_le(0xfee0_0000);
+        madt.put_u32_le(1);
+        madt.put_slice(&[1, 12, 0, 0, 0, 0, 0xc0, 0xfe, 0, 0, 0, 0, 0, 1]);
+
+        let snapshot = TableSnapshot {
+            tables: vec![
+                SnapshotTable {
+                    signature: "FACP".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "FACP".to_string(),
+                        length: 276,
+                        revision: 6,
+                        acpi_table_data: fadt.freeze(),
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "APIC".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "APIC".to_string(),
+                        length: 58,
+                        revision: 5,
+                        acpi_table_data: madt.freeze(),
+                        ..Default::default()
+                    }),
+                },
+            ],
+            ..Default::default()
+        };
+        let findings = run("fadt-madt", &snapshot);
+        let messages = findings
+            .iter()
+            .map(|f| f.message.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "DSDT 0x1000 and X_DSDT 0x2000 differ",
+                "structure type 0 at offset 0x38 has invalid length 1",
+                "PCAT_COMPAT is set but the FADT is hardware-reduced",
+            ]
+        );
+
+        let snapshot = TableSnapshot {
+            tables: vec![SnapshotTable {
+                signature: "FACP".to_string(),
+                instance: 1,
+                address: None,
+                data: Bytes::from(RawAcpiData {
+                    signature: "FACP".to_string(),
+                    length: 244,
+                    revision: 6,
+                    acpi_table_data: Bytes::from_static(&[0; 244 - HEADER_LENGTH]),
+                    ..Default::default()
+                }),
+            }],
+            ..Default::default()
+        };
+        let findings = run("fadt-madt", &snapshot);
+        assert_eq!(
+            findings[0].message,
+            "244 bytes, revision 6 requires at least 276"
+        );
+    }
+
+    #[test]
+    fn xsdt_rule() {
+        let mut xsdt = BytesMut::new();
+        xsdt.put_u64_le(0x1000);
+        xsdt.put_u64_le(0);
+        xsdt.put_u64_le(0x3000);
+
+        let mut snapshot = TableSnapshot {
+            tables: vec![
+                SnapshotTable {
+                    signature: "XSDT".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "XSDT".to_string(),
+                        length: 60,
+                        revision: 1,
+                        acpi_table_data: xsdt.freeze(),
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "FACP".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "FACP".to_string(),
+                        length: 276,
+                        revision: 6,
+                        acpi_table_data: Bytes::from_static(&[0; 276 - HEADER_LENGTH]),
+                        ..Default::default()
+                    }),
+                },
+            ],
+            ..Default::default()
+        };
+
+        let findings = run("xsdt", &snapshot);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].message, "entry 1 is null");
+
+        snapshot.tables[1].address = Some(0x1000);
+        let findings = run("xsdt", &snapshot);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(
+            findings[1].message,
+            "entry 2 points to 0x3000, no table there"
+        );
+    }
+
+    #[test]
+    fn unique_signature_rule() {
+        let snapshot = TableSnapshot {
+            tables: vec![
+                SnapshotTable {
+                    signature: "SSDT".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "SSDT".to_string(),
+                        length: 36,
+                        revision: 2,
+                        acpi_table_data: Bytes::new(),
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "SSDT".to_string(),
+                    instance: 2,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "SSDT".to_string(),
+                        length: 36,
+                        revision: 2,
+                        acpi_table_data: Bytes::new(),
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "FACP".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "FACP".to_string(),
+                        length: 36,
+                        revision: 6,
+                        acpi_table_data: Bytes::new(),
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "FACP".to_string(),
+                    instance: 2,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "FACP".to_string(),
+                        length: 36,
+                        revision: 6,
+                        acpi_table_data: Bytes::new(),
+                        ..Default::default()
+                    }),
+                },
+            ],
+            ..Default::default()
+        };
+        let findings = run("unique", &snapshot);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].signature, "FACP");
+        assert_eq!(findings[0].message, "2 tables found, only one is allowed");
+    }
+
+    #[test]
+    fn truncated_tables() {
+        // Header-only tables are too short for their typed parsers and the FACP is too short for
+        // its header; neither may panic the rules.
+        let snapshot = TableSnapshot {
+            tables: vec![
+                SnapshotTable {
+                    signature: "WSMT".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "WSMT".to_string(),
+                        length: 36,
+                        revision: 1,
+                        acpi_table_data: Bytes::new(),
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "MCFG".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "MCFG".to_string(),
+                        length: 36,
+                        revision: 1,
+                        acpi_table_data: Bytes::new(),
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "BGRT".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "BGRT".to_string(),
+                        length: 36,
+                        revision: 1,
+                        acpi_table_data: Bytes::new(),
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "FACP".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from_static(b"FACP\x08\x00\x00\x00"),
+                },
+            ],
+            ..Default::default()
+        };
+        let findings = Linter::default().run(&snapshot);
+        let rules = findings.iter().map(|f| f.rule.as_str()).collect::<Vec<_>>();
+        assert_eq!(rules, vec!["checksum", "checksum", "checksum", "length"]);
+    }
+}
@@ -0,0 +1,329 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::gas::GenericAddress;
+use super::{RawAcpiData, string_to_array};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://uefi.org/sites/default/files/resources/Intel_ACPI_Low_Power_S0_Idle.pdf
+
+// Offset of the FADT Flags field within the table, including the 36 byte header.
+const FADT_FLAGS_OFFSET: usize = 112;
+const FADT_LOW_POWER_S0_IDLE_CAPABLE: u32 = 1 << 21;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LowPowerIdle {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub states: Vec<LowPowerIdleState>,
+}
+
+impl LowPowerIdle {
+    // `fadt` is the raw FACP table; when given, its LOW_POWER_S0_IDLE_CAPABLE flag is reported too.
+    pub fn summary(&self, fadt: Option<&RawAcpiData>) -> LowPowerIdleSummary {
+        let enabled = self
+            .states
+            .iter()
+            .filter(|s| !s.disabled())
+            .collect::<Vec<&LowPowerIdleState>>();
+
+        LowPowerIdleSummary {
+            s0ix_advertised: !enabled.is_empty(),
+            low_power_s0_idle_capable: fadt.and_then(low_power_s0_idle_capable),
+            enabled_states: enabled.iter().map(|s| s.unique_id).collect(),
+            residency_counters: enabled
+                .iter()
+                .filter(|s| s.residency_counter_available())
+                .map(|s| {
+                    (
+                        s.unique_id,
+                        s.residency_counter,
+                        s.residency_counter_frequency,
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+fn low_power_s0_idle_capable(fadt: &RawAcpiData) -> Option<bool> {
+    if fadt.signature != "FACP" {
+        return None;
+    }
+
+    let offset = FADT_FLAGS_OFFSET - 36;
+    let flags = fadt.acpi_table_data.get(offset..offset + 4)?;
+    let flags = u32::from_le_bytes(flags.try_into().unwrap());
+    Some(flags & FADT_LOW_POWER_S0_IDLE_CAPABLE != 0)
+}
+
+impl From<RawAcpiData> for LowPowerIdle {
+    fn from(data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+
+        let mut body = data.acpi_table_data;
+        let mut states = vec![];
+        while body.len() >= 8 {
+            let len = u32::from_le_bytes(body[4..8].try_into().unwrap()) as usize;
+            if len < LowPowerIdleState::LENGTH || body.len() < len {
+                break;
+            }
+            states.push(LowPowerIdleState::from(body.split_to(len)));
+        }
+
+        LowPowerIdle {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            states,
+        }
+    }
+}
+
+impl From<LowPowerIdle> for Bytes {
+    fn from(val: LowPowerIdle) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        for state in val.states {
+            b.put(Bytes::from(state));
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for LowPowerIdle {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Low Power Idle Table");
+        for state in &self.states {
+            state.disassemble(w);
+            w.blank();
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LowPowerIdleSummary {
+    pub s0ix_advertised: bool,
+    pub low_power_s0_idle_capable: Option<bool>,
+    pub enabled_states: Vec<u16>,
+    // (unique ID, counter register, counter frequency in Hz; 0 means the TSC frequency)
+    pub residency_counters: Vec<(u16, GenericAddress, u64)>,
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LowPowerIdleState {
+    pub state_type: u32,
+    pub length: u32,
+    pub unique_id: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 2],
+    pub flags: u32,
+    pub entry_trigger: GenericAddress,
+    pub residency: u32,
+    pub latency: u32,
+    pub residency_counter: GenericAddress,
+    pub residency_counter_frequency: u64,
+}
+
+impl LowPowerIdleState {
+    const LENGTH: usize = 56;
+
+    pub fn native_c_state(&self) -> bool {
+        self.state_type == 0
+    }
+
+    pub fn disabled(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
+
+    pub fn residency_counter_available(&self) -> bool {
+        self.flags & 0x02 == 0
+    }
+}
+
+impl From<Bytes> for LowPowerIdleState {
+    fn from(mut buf: Bytes) -> Self {
+        let state_type = buf.get_u32_le();
+        let length = buf.get_u32_le();
+        let unique_id = buf.get_u16_le();
+        let reserved = buf.split_to(2)[..].try_into().unwrap();
+        let flags = buf.get_u32_le();
+        let entry_trigger = GenericAddress::from(buf.split_to(12));
+        let residency = buf.get_u32_le();
+        let latency = buf.get_u32_le();
+        let residency_counter = GenericAddress::from(buf.split_to(12));
+        let residency_counter_frequency = buf.get_u64_le();
+
+        LowPowerIdleState {
+            state_type,
+            length,
+            unique_id,
+            reserved,
+            flags,
+            entry_trigger,
+            residency,
+            latency,
+            residency_counter,
+            residency_counter_frequency,
+        }
+    }
+}
+
+impl From<LowPowerIdleState> for Bytes {
+    fn from(val: LowPowerIdleState) -> Self {
+        let mut b = BytesMut::with_capacity(LowPowerIdleState::LENGTH);
+        b.put_u32_le(val.state_type);
+        b.put_u32_le(val.length);
+        b.put_u16_le(val.unique_id);
+        b.put_slice(&val.reserved);
+        b.put_u32_le(val.flags);
+        b.put(Bytes::from(val.entry_trigger));
+        b.put_u32_le(val.residency);
+        b.put_u32_le(val.latency);
+        b.put(Bytes::from(val.residency_counter));
+        b.put_u64_le(val.residency_counter_frequency);
+        b.freeze()
+    }
+}
+
+impl Disassemble for LowPowerIdleState {
+    fn disassemble(&self, w: &mut Disassembler) {
+        let name = if self.native_c_state() {
+            "Native C-state Idle Structure"
+        } else {
+            "Unknown"
+        };
+        w.annotated("Subtable Type", 4, self.state_type as u64, name);
+        w.u32("Length", self.length);
+        w.u16("Unique ID", self.unique_id);
+        w.bytes("Reserved", &self.reserved);
+        w.u32("Flags (decoded below)", self.flags);
+        w.flag("State Disabled", self.disabled() as u8);
+        w.flag("No Counter", !self.residency_counter_available() as u8);
+        w.blank();
+        w.gas("Entry Trigger", &self.entry_trigger);
+        w.blank();
+        w.u32("Residency", self.residency);
+        w.u32("Latency", self.latency);
+        w.gas("Residency Counter", &self.residency_counter);
+        w.blank();
+        w.u64("Counter Frequency", self.residency_counter_frequency);
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AddressSpace;
+
+    #[test]
+    fn low_power_idle() {
+        let state = LowPowerIdleState {
+            state_type: 0,
+            length: 56,
+            unique_id: 0,
+            reserved: [0; 2],
+            flags: 0,
+            entry_trigger: GenericAddress {
+                address_space_id: AddressSpace::FunctionalFixedHardware,
+                register_bit_width: 1,
+                register_bit_offset: 2,
+                access_size: 0,
+                address: 0x60,
+            },
+            residency: 30000,
+            latency: 3000,
+            residency_counter: GenericAddress {
+                address_space_id: AddressSpace::FunctionalFixedHardware,
+                register_bit_width: 64,
+                register_bit_offset: 0,
+                access_size: 0,
+                address: 0x632,
+            },
+            residency_counter_frequency: 0,
+        };
+        let data = LowPowerIdle {
+            signature: "LPIT".to_string(),
+            length: 36 + 56 * 2,
+            revision: 1,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            states: vec![
+                state.clone(),
+                LowPowerIdleState {
+                    unique_id: 1,
+                    flags: 0x02,
+                    ..state
+                },
+            ],
+        };
+        let b = Bytes::from(data.clone());
+        assert_eq!(b.len(), data.length as usize);
+        let raw = RawAcpiData::from(b);
+        let ret = LowPowerIdle::from(raw);
+        assert_eq!(data, ret);
+
+        let summary = ret.summary(None);
+        assert!(summary.s0ix_advertised);
+        assert_eq!(summary.low_power_s0_idle_capable, None);
+        assert_eq!(summary.enabled_states, vec![0, 1]);
+        assert_eq!(summary.residency_counters.len(), 1);
+        assert_eq!(summary.residency_counters[0].1.address, 0x632);
+
+        let mut fadt = BytesMut::new();
+        fadt.put_slice(b"FACP");
+        fadt.put_u32_le(116);
+        fadt.resize(FADT_FLAGS_OFFSET, 0);
+        fadt.put_u32_le(FADT_LOW_POWER_S0_IDLE_CAPABLE);
+        let fadt = RawAcpiData::from(fadt.freeze());
+        assert_eq!(
+            ret.summary(Some(&fadt)).low_power_s0_idle_capable,
+            Some(true)
+        );
+    }
+}
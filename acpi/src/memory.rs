@@ -0,0 +1,106 @@
+use super::error::Error;
+#[cfg(feature = "std")]
+use std::io::{Read, Seek, SeekFrom};
+
+pub trait PhysicalMemoryReader {
+    fn read_physical(&mut self, address: u64, buf: &mut [u8]) -> Result<(), Error>;
+}
+
+// Covers `/dev/mem` opened as a `File` as well as in-memory images such as `Cursor<Vec<u8>>`.
+#[cfg(feature = "std")]
+impl<T> PhysicalMemoryReader for T
+where
+    T: Read + Seek,
+{
+    fn read_physical(&mut self, address: u64, buf: &mut [u8]) -> Result<(), Error> {
+        self.seek(SeekFrom::Start(address))?;
+        self.read_exact(buf)?;
+        Ok(())
+    }
+}
+
+// Maps physical memory on demand, for firmware and kernels that have no file to read it from.
+pub trait PhysicalMemoryMapper {
+    // The returned bytes stay valid until `unmap_physical` is called with the same range.
+    fn map_physical(&mut self, address: u64, length: usize) -> Result<&[u8], Error>;
+
+    fn unmap_physical(&mut self, address: u64, length: usize) {
+        let _ = (address, length);
+    }
+}
+
+// Reads through a `PhysicalMemoryMapper`, keeping each range mapped only while it is copied.
+pub struct MappedMemory<M> {
+    mapper: M,
+}
+
+impl<M> MappedMemory<M>
+where
+    M: PhysicalMemoryMapper,
+{
+    pub fn new(mapper: M) -> Self {
+        MappedMemory { mapper }
+    }
+
+    pub fn into_inner(self) -> M {
+        self.mapper
+    }
+}
+
+impl<M> PhysicalMemoryReader for MappedMemory<M>
+where
+    M: PhysicalMemoryMapper,
+{
+    fn read_physical(&mut self, address: u64, buf: &mut [u8]) -> Result<(), Error> {
+        let mapped = self.mapper.map_physical(address, buf.len())?;
+        let copied = match mapped.get(..buf.len()) {
+            Some(v) => {
+                buf.copy_from_slice(v);
+                Ok(())
+            }
+            None => Err(Error::OutOfRange),
+        };
+        self.mapper.unmap_physical(address, buf.len());
+        copied
+    }
+}
+
+// For environments where physical memory is identity mapped, such as UEFI boot services.
+pub struct IdentityMapper(());
+
+impl IdentityMapper {
+    /// # Safety
+    ///
+    /// Every physical address passed to `map_physical` must be mapped at the same virtual
+    /// address and readable for the requested length.
+    pub unsafe fn new() -> Self {
+        IdentityMapper(())
+    }
+}
+
+impl PhysicalMemoryMapper for IdentityMapper {
+    fn map_physical(&mut self, address: u64, length: usize) -> Result<&[u8], Error> {
+        let address = usize::try_from(address).map_err(|_| Error::OutOfRange)?;
+        if address == 0 {
+            return Err(Error::OutOfRange);
+        }
+        Ok(unsafe { core::slice::from_raw_parts(address as *const u8, length) })
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_mapper() {
+        let data = [1u8, 2, 3, 4];
+        let mut memory = unsafe { IdentityMapper::new() };
+        let ret = memory.map_physical(data.as_ptr() as u64, 4).unwrap();
+        assert_eq!(ret, data);
+
+        assert!(matches!(memory.map_physical(0, 4), Err(Error::OutOfRange)));
+    }
+}
@@ -0,0 +1,1218 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::guid::Guid;
+use super::{RawAcpiData, string_to_array};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://uefi.org/specs/ACPI/6.5/05_ACPI_Software_Programming_Model.html#nvdimm-firmware-interface-table-nfit
+
+pub const VOLATILE_MEMORY_REGION: Guid = Guid::new(
+    0x7305944f,
+    0xfdda,
+    0x44e3,
+    [0xb1, 0x6c, 0x3f, 0x22, 0xd2, 0x52, 0xe5, 0xd0],
+);
+pub const PERSISTENT_MEMORY_REGION: Guid = Guid::new(
+    0x66f0d379,
+    0xb4f3,
+    0x4074,
+    [0xac, 0x43, 0x0d, 0x33, 0x18, 0xb7, 0x8c, 0xdb],
+);
+pub const CONTROL_REGION: Guid = Guid::new(
+    0x92f701f6,
+    0x13b4,
+    0x405d,
+    [0x91, 0x0b, 0x29, 0x93, 0x67, 0xe8, 0x23, 0x4c],
+);
+pub const BLOCK_DATA_WINDOW_REGION: Guid = Guid::new(
+    0x91af0530,
+    0x5d86,
+    0x470e,
+    [0xa6, 0xb0, 0x0a, 0x2d, 0xb9, 0x40, 0x82, 0x49],
+);
+pub const RAM_DISK_VIRTUAL_DISK_REGION: Guid = Guid::new(
+    0x77ab535a,
+    0x45fc,
+    0x624b,
+    [0x55, 0x60, 0xf7, 0xb2, 0x81, 0xd1, 0xf9, 0x6e],
+);
+pub const RAM_DISK_VIRTUAL_CD_REGION: Guid = Guid::new(
+    0x3d5abd30,
+    0x4175,
+    0x87ce,
+    [0x6d, 0x64, 0xd2, 0xad, 0xe5, 0x23, 0xc4, 0xbb],
+);
+pub const PERSISTENT_VIRTUAL_DISK_REGION: Guid = Guid::new(
+    0x5cea02c9,
+    0x4d07,
+    0x69d3,
+    [0x26, 0x9f, 0x44, 0x96, 0xfb, 0xe0, 0x96, 0xf9],
+);
+pub const PERSISTENT_VIRTUAL_CD_REGION: Guid = Guid::new(
+    0x08018188,
+    0x42cd,
+    0xbb48,
+    [0x10, 0x0f, 0x53, 0x87, 0xd5, 0x3d, 0xed, 0x3d],
+);
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NvdimmFirmwareInterface {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 4],
+    pub structures: Vec<NfitStructure>,
+}
+
+impl NvdimmFirmwareInterface {
+    pub fn spa_ranges(&self) -> impl Iterator<Item = &SpaRange> {
+        self.structures.iter().filter_map(|s| match s {
+            NfitStructure::SpaRange(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    pub fn spa_range(&self, index: u16) -> Option<&SpaRange> {
+        self.spa_ranges().find(|s| s.spa_range_index == index)
+    }
+
+    pub fn region_mappings(&self) -> impl Iterator<Item = &RegionMapping> {
+        self.structures.iter().filter_map(|s| match s {
+            NfitStructure::RegionMapping(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    pub fn interleave(&self, index: u16) -> Option<&Interleave> {
+        self.structures.iter().find_map(|s| match s {
+            NfitStructure::Interleave(v) if v.interleave_index == index => Some(v),
+            _ => None,
+        })
+    }
+
+    pub fn control_region(&self, index: u16) -> Option<&ControlRegion> {
+        self.structures.iter().find_map(|s| match s {
+            NfitStructure::ControlRegion(v) if v.control_region_index == index => Some(v),
+            _ => None,
+        })
+    }
+
+    pub fn block_data_window(&self, control_region_index: u16) -> Option<&BlockDataWindow> {
+        self.structures.iter().find_map(|s| match s {
+            NfitStructure::BlockDataWindow(v) if v.control_region_index == control_region_index => {
+                Some(v)
+            }
+            _ => None,
+        })
+    }
+
+    pub fn flush_hint_address(&self, device_handle: NfitDeviceHandle) -> Option<&FlushHintAddress> {
+        self.structures.iter().find_map(|s| match s {
+            NfitStructure::FlushHintAddress(v) if v.device_handle == device_handle => Some(v),
+            _ => None,
+        })
+    }
+
+    pub fn platform_capabilities(&self) -> Option<&PlatformCapabilities> {
+        self.structures.iter().find_map(|s| match s {
+            NfitStructure::PlatformCapabilities(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    // Collects every NVDIMM contributing to the given SPA range along with its related structures.
+    pub fn regions(&self, spa_range_index: u16) -> Vec<NvdimmRegion<'_>> {
+        self.region_mappings()
+            .filter(|m| m.spa_range_index == spa_range_index)
+            .map(|mapping| NvdimmRegion {
+                mapping,
+                control_region: self.control_region(mapping.control_region_index),
+                interleave: if mapping.interleave_index != 0 {
+                    self.interleave(mapping.interleave_index)
+                } else {
+                    None
+                },
+                flush_hint_address: self.flush_hint_address(mapping.device_handle),
+            })
+            .collect()
+    }
+}
+
+impl From<RawAcpiData> for NvdimmFirmwareInterface {
+    fn from(data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+
+        let mut body = data.acpi_table_data;
+        let reserved = body.split_to(4)[..].try_into().unwrap();
+        let mut structures = vec![];
+        while body.len() >= 4 {
+            let len = u16::from_le_bytes([body[2], body[3]]) as usize;
+            if len < 4 || body.len() < len {
+                break;
+            }
+            structures.push(NfitStructure::from(body.split_to(len)));
+        }
+
+        NvdimmFirmwareInterface {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            reserved,
+            structures,
+        }
+    }
+}
+
+impl From<NvdimmFirmwareInterface> for Bytes {
+    fn from(val: NvdimmFirmwareInterface) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        b.put_slice(&val.reserved);
+        for structure in val.structures {
+            b.put(Bytes::from(structure));
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for NvdimmFirmwareInterface {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "NVDIMM Firmware Interface Table");
+        w.bytes("Reserved", &self.reserved);
+        for structure in &self.structures {
+            w.blank();
+            structure.disassemble(w);
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NvdimmRegion<'a> {
+    pub mapping: &'a RegionMapping,
+    pub control_region: Option<&'a ControlRegion>,
+    pub interleave: Option<&'a Interleave>,
+    pub flush_hint_address: Option<&'a FlushHintAddress>,
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NfitStructure {
+    SpaRange(SpaRange),
+    RegionMapping(RegionMapping),
+    Interleave(Interleave),
+    SmbiosManagementInformation(SmbiosManagementInformation),
+    ControlRegion(ControlRegion),
+    BlockDataWindow(BlockDataWindow),
+    FlushHintAddress(FlushHintAddress),
+    PlatformCapabilities(PlatformCapabilities),
+    Unknown(
+        u16,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Bytes,
+    ),
+}
+
+impl NfitStructure {
+    pub fn structure_type(&self) -> u16 {
+        match self {
+            NfitStructure::SpaRange(_) => 0,
+            NfitStructure::RegionMapping(_) => 1,
+            NfitStructure::Interleave(_) => 2,
+            NfitStructure::SmbiosManagementInformation(_) => 3,
+            NfitStructure::ControlRegion(_) => 4,
+            NfitStructure::BlockDataWindow(_) => 5,
+            NfitStructure::FlushHintAddress(_) => 6,
+            NfitStructure::PlatformCapabilities(_) => 7,
+            NfitStructure::Unknown(t, _) => *t,
+        }
+    }
+}
+
+impl From<Bytes> for NfitStructure {
+    fn from(mut buf: Bytes) -> Self {
+        let structure_type = buf.get_u16_le();
+        let _length = buf.get_u16_le();
+        match structure_type {
+            0 if buf.len() >= 52 => NfitStructure::SpaRange(SpaRange::from(buf)),
+            1 if buf.len() >= 44 => NfitStructure::RegionMapping(RegionMapping::from(buf)),
+            2 if buf.len() >= 12 => NfitStructure::Interleave(Interleave::from(buf)),
+            3 if buf.len() >= 4 => {
+                NfitStructure::SmbiosManagementInformation(SmbiosManagementInformation::from(buf))
+            }
+            4 if buf.len() >= 28 => NfitStructure::ControlRegion(ControlRegion::from(buf)),
+            5 if buf.len() >= 36 => NfitStructure::BlockDataWindow(BlockDataWindow::from(buf)),
+            6 if buf.len() >= 12 => NfitStructure::FlushHintAddress(FlushHintAddress::from(buf)),
+            7 if buf.len() >= 12 => {
+                NfitStructure::PlatformCapabilities(PlatformCapabilities::from(buf))
+            }
+            t => NfitStructure::Unknown(t, buf),
+        }
+    }
+}
+
+impl From<NfitStructure> for Bytes {
+    fn from(val: NfitStructure) -> Self {
+        let structure_type = val.structure_type();
+        let data = match val {
+            NfitStructure::SpaRange(v) => Bytes::from(v),
+            NfitStructure::RegionMapping(v) => Bytes::from(v),
+            NfitStructure::Interleave(v) => Bytes::from(v),
+            NfitStructure::SmbiosManagementInformation(v) => Bytes::from(v),
+            NfitStructure::ControlRegion(v) => Bytes::from(v),
+            NfitStructure::BlockDataWindow(v) => Bytes::from(v),
+            NfitStructure::FlushHintAddress(v) => Bytes::from(v),
+            NfitStructure::PlatformCapabilities(v) => Bytes::from(v),
+            NfitStructure::Unknown(_, v) => v,
+        };
+
+        let mut b = BytesMut::with_capacity(4 + data.len());
+        b.put_u16_le(structure_type);
+        b.put_u16_le(4 + data.len() as u16);
+        b.put(data);
+        b.freeze()
+    }
+}
+
+impl Disassemble for NfitStructure {
+    fn disassemble(&self, w: &mut Disassembler) {
+        let name = match self {
+            NfitStructure::SpaRange(_) => "System Physical Address Range",
+            NfitStructure::RegionMapping(_) => "Memory Range Map",
+            NfitStructure::Interleave(_) => "Interleave Info",
+            NfitStructure::SmbiosManagementInformation(_) => "SMBIOS Info",
+            NfitStructure::ControlRegion(_) => "NVDIMM Control Region",
+            NfitStructure::BlockDataWindow(_) => "NVDIMM Block Data Window Region",
+            NfitStructure::FlushHintAddress(_) => "Flush Hint Address",
+            NfitStructure::PlatformCapabilities(_) => "Platform Capabilities",
+            NfitStructure::Unknown(_, _) => "Unknown",
+        };
+        let length = Bytes::from(self.clone()).len();
+        w.annotated("Subtable Type", 2, self.structure_type() as u64, name);
+        w.u16("Length", length as u16);
+        w.blank();
+
+        match self {
+            NfitStructure::SpaRange(v) => {
+                w.u16("Range Index", v.spa_range_index);
+                w.u16("Flags (decoded below)", v.flags);
+                w.flag("Add/Online Operation Only", v.flags & 0x01);
+                w.flag("Proximity Domain Valid", (v.flags >> 1) & 0x01);
+                w.flag("Location Cookie Valid", (v.flags >> 2) & 0x01);
+                w.bytes("Reserved", &v.reserved);
+                w.u32("Proximity Domain", v.proximity_domain);
+                w.field(
+                    "Region Type GUID",
+                    16,
+                    format_args!(
+                        "{} [{}]",
+                        Guid::from(v.address_range_type),
+                        v.address_range_type.name()
+                    ),
+                );
+                w.u64("Address Range Base", v.base_address);
+                w.u64("Address Range Length", v.length);
+                w.u64("Memory Map Attribute", v.memory_mapping_attribute);
+                if let Some(cookie) = v.location_cookie {
+                    w.u64("LocationCookie", cookie);
+                }
+            }
+            NfitStructure::RegionMapping(v) => {
+                w.u32("Device Handle", v.device_handle.0);
+                w.u16("Physical Id", v.physical_id);
+                w.u16("Region Id", v.region_id);
+                w.u16("Range Index", v.spa_range_index);
+                w.u16("Control Region Index", v.control_region_index);
+                w.u64("Region Size", v.region_size);
+                w.u64("Region Offset", v.region_offset);
+                w.u64("Address Region Base", v.physical_address_region_base);
+                w.u16("Interleave Index", v.interleave_index);
+                w.u16("Interleave Ways", v.interleave_ways);
+                w.u16("Flags", v.state_flags);
+                w.bytes("Reserved", &v.reserved);
+            }
+            NfitStructure::Interleave(v) => {
+                w.u16("Interleave Index", v.interleave_index);
+                w.bytes("Reserved", &v.reserved);
+                w.u32("Line Count", v.number_of_lines);
+                w.u32("Line Size", v.line_size);
+                for offset in &v.line_offsets {
+                    w.u32("Line Offset", *offset);
+                }
+            }
+            NfitStructure::SmbiosManagementInformation(v) => {
+                w.bytes("Reserved", &v.reserved);
+                w.bytes("SMBIOS Table Entries", &v.data);
+            }
+            NfitStructure::ControlRegion(v) => {
+                w.u16("Region Index", v.control_region_index);
+                w.u16("Vendor Id", v.vendor_id);
+                w.u16("Device Id", v.device_id);
+                w.u16("Revision Id", v.revision_id);
+                w.u16("Subsystem Vendor Id", v.subsystem_vendor_id);
+                w.u16("Subsystem Device Id", v.subsystem_device_id);
+                w.u16("Subsystem Revision Id", v.subsystem_revision_id);
+                w.u8("Flags", v.valid_fields);
+                w.u8("Manufacturing Location", v.manufacturing_location);
+                w.u16("Manufacturing Date", v.manufacturing_date);
+                w.bytes("Reserved", &v.reserved1);
+                w.u32("Serial Number", v.serial_number);
+                w.u16("Code", v.region_format_interface_code);
+                w.u16("Window Count", v.number_of_block_control_windows);
+                if let Some(window) = &v.block_control_window {
+                    w.u64("Window Size", window.window_size);
+                    w.u64("Command Offset", window.command_register_offset);
+                    w.u64("Command Size", window.command_register_size);
+                    w.u64("Status Offset", window.status_register_offset);
+                    w.u64("Status Size", window.status_register_size);
+                    w.u16("Flags (decoded below)", window.flags);
+                    w.flag("Windows Buffered", window.flags & 0x01);
+                    w.bytes("Reserved1", &window.reserved);
+                }
+            }
+            NfitStructure::BlockDataWindow(v) => {
+                w.u16("Region Index", v.control_region_index);
+                w.u16("Window Count", v.number_of_block_data_windows);
+                w.u64("Offset", v.window_start_offset);
+                w.u64("Size", v.window_size);
+                w.u64("Capacity", v.accessible_memory_capacity);
+                w.u64("Start Address", v.first_block_address);
+            }
+            NfitStructure::FlushHintAddress(v) => {
+                w.u32("Device Handle", v.device_handle.0);
+                w.u16("Hint Count", v.number_of_addresses);
+                w.bytes("Reserved", &v.reserved);
+                for address in &v.addresses {
+                    w.u64("Hint Address", *address);
+                }
+            }
+            NfitStructure::PlatformCapabilities(v) => {
+                w.u8("Highest Capability", v.highest_valid_capability);
+                w.bytes("Reserved", &v.reserved1);
+                w.u32("Capabilities (decoded below)", v.capabilities);
+                w.flag("Cache Flush to NVDIMM", v.capabilities & 0x01);
+                w.flag("Memory Flush to NVDIMM", (v.capabilities >> 1) & 0x01);
+                w.flag("Memory Mirroring", (v.capabilities >> 2) & 0x01);
+                w.bytes("Reserved", &v.reserved2);
+            }
+            NfitStructure::Unknown(_, v) => w.bytes("Data", v),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpaRangeType {
+    VolatileMemory,
+    PersistentMemory,
+    ControlRegion,
+    BlockDataWindow,
+    RamDiskVirtualDisk,
+    RamDiskVirtualCd,
+    PersistentVirtualDisk,
+    PersistentVirtualCd,
+    Unknown(Guid),
+}
+
+impl SpaRangeType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SpaRangeType::VolatileMemory => "Volatile Memory",
+            SpaRangeType::PersistentMemory => "Persistent Memory",
+            SpaRangeType::ControlRegion => "NVDIMM Control Region",
+            SpaRangeType::BlockDataWindow => "NVDIMM Block Data Window",
+            SpaRangeType::RamDiskVirtualDisk => "RAM Disk (Virtual Disk)",
+            SpaRangeType::RamDiskVirtualCd => "RAM Disk (Virtual CD)",
+            SpaRangeType::PersistentVirtualDisk => "Persistent Virtual Disk",
+            SpaRangeType::PersistentVirtualCd => "Persistent Virtual CD",
+            SpaRangeType::Unknown(_) => "Unknown",
+        }
+    }
+}
+
+impl From<Guid> for SpaRangeType {
+    fn from(value: Guid) -> Self {
+        match value {
+            VOLATILE_MEMORY_REGION => SpaRangeType::VolatileMemory,
+            PERSISTENT_MEMORY_REGION => SpaRangeType::PersistentMemory,
+            CONTROL_REGION => SpaRangeType::ControlRegion,
+            BLOCK_DATA_WINDOW_REGION => SpaRangeType::BlockDataWindow,
+            RAM_DISK_VIRTUAL_DISK_REGION => SpaRangeType::RamDiskVirtualDisk,
+            RAM_DISK_VIRTUAL_CD_REGION => SpaRangeType::RamDiskVirtualCd,
+            PERSISTENT_VIRTUAL_DISK_REGION => SpaRangeType::PersistentVirtualDisk,
+            PERSISTENT_VIRTUAL_CD_REGION => SpaRangeType::PersistentVirtualCd,
+            v => SpaRangeType::Unknown(v),
+        }
+    }
+}
+
+impl From<SpaRangeType> for Guid {
+    fn from(val: SpaRangeType) -> Self {
+        match val {
+            SpaRangeType::VolatileMemory => VOLATILE_MEMORY_REGION,
+            SpaRangeType::PersistentMemory => PERSISTENT_MEMORY_REGION,
+            SpaRangeType::ControlRegion => CONTROL_REGION,
+            SpaRangeType::BlockDataWindow => BLOCK_DATA_WINDOW_REGION,
+            SpaRangeType::RamDiskVirtualDisk => RAM_DISK_VIRTUAL_DISK_REGION,
+            SpaRangeType::RamDiskVirtualCd => RAM_DISK_VIRTUAL_CD_REGION,
+            SpaRangeType::PersistentVirtualDisk => PERSISTENT_VIRTUAL_DISK_REGION,
+            SpaRangeType::PersistentVirtualCd => PERSISTENT_VIRTUAL_CD_REGION,
+            SpaRangeType::Unknown(v) => v,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpaRange {
+    pub spa_range_index: u16,
+    pub flags: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 4],
+    pub proximity_domain: u32,
+    pub address_range_type: SpaRangeType,
+    pub base_address: u64,
+    pub length: u64,
+    pub memory_mapping_attribute: u64,
+    pub location_cookie: Option<u64>,
+}
+
+impl SpaRange {
+    pub fn management_only(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
+
+    pub fn proximity_domain(&self) -> Option<u32> {
+        (self.flags & 0x02 != 0).then_some(self.proximity_domain)
+    }
+
+    pub fn location_cookie(&self) -> Option<u64> {
+        if self.flags & 0x04 == 0 {
+            return None;
+        }
+
+        self.location_cookie
+    }
+}
+
+impl From<Bytes> for SpaRange {
+    fn from(mut buf: Bytes) -> Self {
+        let spa_range_index = buf.get_u16_le();
+        let flags = buf.get_u16_le();
+        let reserved = buf.split_to(4)[..].try_into().unwrap();
+        let proximity_domain = buf.get_u32_le();
+        let address_range_type = Guid(buf.split_to(16)[..].try_into().unwrap());
+        let base_address = buf.get_u64_le();
+        let length = buf.get_u64_le();
+        let memory_mapping_attribute = buf.get_u64_le();
+        let location_cookie = if buf.len() >= 8 {
+            Some(buf.get_u64_le())
+        } else {
+            None
+        };
+
+        SpaRange {
+            spa_range_index,
+            flags,
+            reserved,
+            proximity_domain,
+            address_range_type: SpaRangeType::from(address_range_type),
+            base_address,
+            length,
+            memory_mapping_attribute,
+            location_cookie,
+        }
+    }
+}
+
+impl From<SpaRange> for Bytes {
+    fn from(val: SpaRange) -> Self {
+        let mut b = BytesMut::with_capacity(60);
+        b.put_u16_le(val.spa_range_index);
+        b.put_u16_le(val.flags);
+        b.put_slice(&val.reserved);
+        b.put_u32_le(val.proximity_domain);
+        b.put_slice(&Guid::from(val.address_range_type).0);
+        b.put_u64_le(val.base_address);
+        b.put_u64_le(val.length);
+        b.put_u64_le(val.memory_mapping_attribute);
+        if let Some(v) = val.location_cookie {
+            b.put_u64_le(v);
+        }
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NfitDeviceHandle(pub u32);
+
+impl NfitDeviceHandle {
+    pub fn dimm_number(&self) -> u8 {
+        (self.0 & 0x0f) as u8
+    }
+
+    pub fn memory_channel(&self) -> u8 {
+        ((self.0 >> 4) & 0x0f) as u8
+    }
+
+    pub fn memory_controller(&self) -> u8 {
+        ((self.0 >> 8) & 0x0f) as u8
+    }
+
+    pub fn socket(&self) -> u8 {
+        ((self.0 >> 12) & 0x0f) as u8
+    }
+
+    pub fn node_controller(&self) -> u16 {
+        ((self.0 >> 16) & 0x0fff) as u16
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegionMapping {
+    pub device_handle: NfitDeviceHandle,
+    pub physical_id: u16,
+    pub region_id: u16,
+    pub spa_range_index: u16,
+    pub control_region_index: u16,
+    pub region_size: u64,
+    pub region_offset: u64,
+    pub physical_address_region_base: u64,
+    pub interleave_index: u16,
+    pub interleave_ways: u16,
+    pub state_flags: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 2],
+}
+
+impl RegionMapping {
+    pub fn save_failed(&self) -> bool {
+        self.state_flags & 0x01 != 0
+    }
+
+    pub fn restore_failed(&self) -> bool {
+        self.state_flags & 0x02 != 0
+    }
+
+    pub fn flush_failed(&self) -> bool {
+        self.state_flags & 0x04 != 0
+    }
+
+    pub fn not_armed(&self) -> bool {
+        self.state_flags & 0x08 != 0
+    }
+
+    pub fn health_events_observed(&self) -> bool {
+        self.state_flags & 0x10 != 0
+    }
+
+    pub fn health_notifications_enabled(&self) -> bool {
+        self.state_flags & 0x20 != 0
+    }
+
+    pub fn map_failed(&self) -> bool {
+        self.state_flags & 0x40 != 0
+    }
+}
+
+impl From<Bytes> for RegionMapping {
+    fn from(mut buf: Bytes) -> Self {
+        let device_handle = NfitDeviceHandle(buf.get_u32_le());
+        let physical_id = buf.get_u16_le();
+        let region_id = buf.get_u16_le();
+        let spa_range_index = buf.get_u16_le();
+        let control_region_index = buf.get_u16_le();
+        let region_size = buf.get_u64_le();
+        let region_offset = buf.get_u64_le();
+        let physical_address_region_base = buf.get_u64_le();
+        let interleave_index = buf.get_u16_le();
+        let interleave_ways = buf.get_u16_le();
+        let state_flags = buf.get_u16_le();
+        let reserved = buf.split_to(2)[..].try_into().unwrap();
+
+        RegionMapping {
+            device_handle,
+            physical_id,
+            region_id,
+            spa_range_index,
+            control_region_index,
+            region_size,
+            region_offset,
+            physical_address_region_base,
+            interleave_index,
+            interleave_ways,
+            state_flags,
+            reserved,
+        }
+    }
+}
+
+impl From<RegionMapping> for Bytes {
+    fn from(val: RegionMapping) -> Self {
+        let mut b = BytesMut::with_capacity(44);
+        b.put_u32_le(val.device_handle.0);
+        b.put_u16_le(val.physical_id);
+        b.put_u16_le(val.region_id);
+        b.put_u16_le(val.spa_range_index);
+        b.put_u16_le(val.control_region_index);
+        b.put_u64_le(val.region_size);
+        b.put_u64_le(val.region_offset);
+        b.put_u64_le(val.physical_address_region_base);
+        b.put_u16_le(val.interleave_index);
+        b.put_u16_le(val.interleave_ways);
+        b.put_u16_le(val.state_flags);
+        b.put_slice(&val.reserved);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interleave {
+    pub interleave_index: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 2],
+    pub number_of_lines: u32,
+    pub line_size: u32,
+    pub line_offsets: Vec<u32>,
+}
+
+impl From<Bytes> for Interleave {
+    fn from(mut buf: Bytes) -> Self {
+        let interleave_index = buf.get_u16_le();
+        let reserved = buf.split_to(2)[..].try_into().unwrap();
+        let number_of_lines = buf.get_u32_le();
+        let line_size = buf.get_u32_le();
+        let line_offsets = buf
+            .chunks_exact(4)
+            .take(number_of_lines as usize)
+            .map(|d| u32::from_le_bytes(d.try_into().unwrap()))
+            .collect();
+
+        Interleave {
+            interleave_index,
+            reserved,
+            number_of_lines,
+            line_size,
+            line_offsets,
+        }
+    }
+}
+
+impl From<Interleave> for Bytes {
+    fn from(val: Interleave) -> Self {
+        let mut b = BytesMut::with_capacity(12 + 4 * val.line_offsets.len());
+        b.put_u16_le(val.interleave_index);
+        b.put_slice(&val.reserved);
+        b.put_u32_le(val.number_of_lines);
+        b.put_u32_le(val.line_size);
+        for offset in val.line_offsets {
+            b.put_u32_le(offset);
+        }
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmbiosManagementInformation {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 4],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub data: Bytes,
+}
+
+impl From<Bytes> for SmbiosManagementInformation {
+    fn from(mut buf: Bytes) -> Self {
+        let reserved = buf.split_to(4)[..].try_into().unwrap();
+
+        SmbiosManagementInformation {
+            reserved,
+            data: buf,
+        }
+    }
+}
+
+impl From<SmbiosManagementInformation> for Bytes {
+    fn from(val: SmbiosManagementInformation) -> Self {
+        let mut b = BytesMut::with_capacity(4 + val.data.len());
+        b.put_slice(&val.reserved);
+        b.put(val.data);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ControlRegion {
+    pub control_region_index: u16,
+    pub vendor_id: u16,
+    pub device_id: u16,
+    pub revision_id: u16,
+    pub subsystem_vendor_id: u16,
+    pub subsystem_device_id: u16,
+    pub subsystem_revision_id: u16,
+    pub valid_fields: u8,
+    pub manufacturing_location: u8,
+    pub manufacturing_date: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved1: [u8; 2],
+    pub serial_number: u32,
+    pub region_format_interface_code: u16,
+    pub number_of_block_control_windows: u16,
+    pub block_control_window: Option<BlockControlWindow>,
+}
+
+impl ControlRegion {
+    pub fn manufacturing_location(&self) -> Option<u8> {
+        (self.valid_fields & 0x01 != 0).then_some(self.manufacturing_location)
+    }
+
+    pub fn manufacturing_date(&self) -> Option<u16> {
+        (self.valid_fields & 0x01 != 0).then_some(self.manufacturing_date)
+    }
+}
+
+impl From<Bytes> for ControlRegion {
+    fn from(mut buf: Bytes) -> Self {
+        let control_region_index = buf.get_u16_le();
+        let vendor_id = buf.get_u16_le();
+        let device_id = buf.get_u16_le();
+        let revision_id = buf.get_u16_le();
+        let subsystem_vendor_id = buf.get_u16_le();
+        let subsystem_device_id = buf.get_u16_le();
+        let subsystem_revision_id = buf.get_u16_le();
+        let valid_fields = buf.get_u8();
+        let manufacturing_location = buf.get_u8();
+        let manufacturing_date = buf.get_u16_le();
+        let reserved1 = buf.split_to(2)[..].try_into().unwrap();
+        let serial_number = buf.get_u32_le();
+        let region_format_interface_code = buf.get_u16_le();
+        let number_of_block_control_windows = buf.get_u16_le();
+        let block_control_window = if buf.len() >= 48 {
+            Some(BlockControlWindow::from(buf))
+        } else {
+            None
+        };
+
+        ControlRegion {
+            control_region_index,
+            vendor_id,
+            device_id,
+            revision_id,
+            subsystem_vendor_id,
+            subsystem_device_id,
+            subsystem_revision_id,
+            valid_fields,
+            manufacturing_location,
+            manufacturing_date,
+            reserved1,
+            serial_number,
+            region_format_interface_code,
+            number_of_block_control_windows,
+            block_control_window,
+        }
+    }
+}
+
+impl From<ControlRegion> for Bytes {
+    fn from(val: ControlRegion) -> Self {
+        let mut b = BytesMut::with_capacity(76);
+        b.put_u16_le(val.control_region_index);
+        b.put_u16_le(val.vendor_id);
+        b.put_u16_le(val.device_id);
+        b.put_u16_le(val.revision_id);
+        b.put_u16_le(val.subsystem_vendor_id);
+        b.put_u16_le(val.subsystem_device_id);
+        b.put_u16_le(val.subsystem_revision_id);
+        b.put_u8(val.valid_fields);
+        b.put_u8(val.manufacturing_location);
+        b.put_u16_le(val.manufacturing_date);
+        b.put_slice(&val.reserved1);
+        b.put_u32_le(val.serial_number);
+        b.put_u16_le(val.region_format_interface_code);
+        b.put_u16_le(val.number_of_block_control_windows);
+        if let Some(v) = val.block_control_window {
+            b.put(Bytes::from(v));
+        }
+        b.freeze()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockControlWindow {
+    pub window_size: u64,
+    pub command_register_offset: u64,
+    pub command_register_size: u64,
+    pub status_register_offset: u64,
+    pub status_register_size: u64,
+    pub flags: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 6],
+}
+
+impl From<Bytes> for BlockControlWindow {
+    fn from(mut buf: Bytes) -> Self {
+        let window_size = buf.get_u64_le();
+        let command_register_offset = buf.get_u64_le();
+        let command_register_size = buf.get_u64_le();
+        let status_register_offset = buf.get_u64_le();
+        let status_register_size = buf.get_u64_le();
+        let flags = buf.get_u16_le();
+        let reserved = buf.split_to(6)[..].try_into().unwrap();
+
+        BlockControlWindow {
+            window_size,
+            command_register_offset,
+            command_register_size,
+            status_register_offset,
+            status_register_size,
+            flags,
+            reserved,
+        }
+    }
+}
+
+impl From<BlockControlWindow> for Bytes {
+    fn from(val: BlockControlWindow) -> Self {
+        let mut b = BytesMut::with_capacity(48);
+        b.put_u64_le(val.window_size);
+        b.put_u64_le(val.command_register_offset);
+        b.put_u64_le(val.command_register_size);
+        b.put_u64_le(val.status_register_offset);
+        b.put_u64_le(val.status_register_size);
+        b.put_u16_le(val.flags);
+        b.put_slice(&val.reserved);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockDataWindow {
+    pub control_region_index: u16,
+    pub number_of_block_data_windows: u16,
+    pub window_start_offset: u64,
+    pub window_size: u64,
+    pub accessible_memory_capacity: u64,
+    pub first_block_address: u64,
+}
+
+impl From<Bytes> for BlockDataWindow {
+    fn from(mut buf: Bytes) -> Self {
+        let control_region_index = buf.get_u16_le();
+        let number_of_block_data_windows = buf.get_u16_le();
+        let window_start_offset = buf.get_u64_le();
+        let window_size = buf.get_u64_le();
+        let accessible_memory_capacity = buf.get_u64_le();
+        let first_block_address = buf.get_u64_le();
+
+        BlockDataWindow {
+            control_region_index,
+            number_of_block_data_windows,
+            window_start_offset,
+            window_size,
+            accessible_memory_capacity,
+            first_block_address,
+        }
+    }
+}
+
+impl From<BlockDataWindow> for Bytes {
+    fn from(val: BlockDataWindow) -> Self {
+        let mut b = BytesMut::with_capacity(36);
+        b.put_u16_le(val.control_region_index);
+        b.put_u16_le(val.number_of_block_data_windows);
+        b.put_u64_le(val.window_start_offset);
+        b.put_u64_le(val.window_size);
+        b.put_u64_le(val.accessible_memory_capacity);
+        b.put_u64_le(val.first_block_address);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlushHintAddress {
+    pub device_handle: NfitDeviceHandle,
+    pub number_of_addresses: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 6],
+    pub addresses: Vec<u64>,
+}
+
+impl From<Bytes> for FlushHintAddress {
+    fn from(mut buf: Bytes) -> Self {
+        let device_handle = NfitDeviceHandle(buf.get_u32_le());
+        let number_of_addresses = buf.get_u16_le();
+        let reserved = buf.split_to(6)[..].try_into().unwrap();
+        let addresses = buf
+            .chunks_exact(8)
+            .take(number_of_addresses as usize)
+            .map(|d| u64::from_le_bytes(d.try_into().unwrap()))
+            .collect();
+
+        FlushHintAddress {
+            device_handle,
+            number_of_addresses,
+            reserved,
+            addresses,
+        }
+    }
+}
+
+impl From<FlushHintAddress> for Bytes {
+    fn from(val: FlushHintAddress) -> Self {
+        let mut b = BytesMut::with_capacity(12 + 8 * val.addresses.len());
+        b.put_u32_le(val.device_handle.0);
+        b.put_u16_le(val.number_of_addresses);
+        b.put_slice(&val.reserved);
+        for address in val.addresses {
+            b.put_u64_le(address);
+        }
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlatformCapabilities {
+    pub highest_valid_capability: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved1: [u8; 3],
+    pub capabilities: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved2: [u8; 4],
+}
+
+impl PlatformCapabilities {
+    fn capability(&self, bit: u8) -> bool {
+        bit <= self.highest_valid_capability && self.capabilities & (1 << bit) != 0
+    }
+
+    pub fn cpu_cache_flush_on_power_loss(&self) -> bool {
+        self.capability(0)
+    }
+
+    pub fn memory_controller_flush_on_power_loss(&self) -> bool {
+        self.capability(1)
+    }
+
+    pub fn hardware_mirroring(&self) -> bool {
+        self.capability(2)
+    }
+}
+
+impl From<Bytes> for PlatformCapabilities {
+    fn from(mut buf: Bytes) -> Self {
+        let highest_valid_capability = buf.get_u8();
+        let reserved1 = buf.split_to(3)[..].try_into().unwrap();
+        let capabilities = buf.get_u32_le();
+        let reserved2 = buf.split_to(4)[..].try_into().unwrap();
+
+        PlatformCapabilities {
+            highest_valid_capability,
+            reserved1,
+            capabilities,
+            reserved2,
+        }
+    }
+}
+
+impl From<PlatformCapabilities> for Bytes {
+    fn from(val: PlatformCapabilities) -> Self {
+        let mut b = BytesMut::with_capacity(12);
+        b.put_u8(val.highest_valid_capability);
+        b.put_slice(&val.reserved1);
+        b.put_u32_le(val.capabilities);
+        b.put_slice(&val.reserved2);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nvdimm_firmware_interface() {
+        let data = NvdimmFirmwareInterface {
+            signature: "NFIT".to_string(),
+            length: 40 + 64 + 48 + 24 + 80 + 40 + 24 + 16 + 12,
+            revision: 1,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            reserved: [0; 4],
+            structures: vec![
+                NfitStructure::SpaRange(SpaRange {
+                    spa_range_index: 1,
+                    flags: 0x06,
+                    reserved: [0; 4],
+                    proximity_domain: 1,
+                    address_range_type: SpaRangeType::PersistentMemory,
+                    base_address: 0x1_0000_0000,
+                    length: 0x4000_0000,
+                    memory_mapping_attribute: 0x8008,
+                    location_cookie: Some(0x1234),
+                }),
+                NfitStructure::RegionMapping(RegionMapping {
+                    device_handle: NfitDeviceHandle(0x1011),
+                    physical_id: 1,
+                    region_id: 0,
+                    spa_range_index: 1,
+                    control_region_index: 2,
+                    region_size: 0x2000_0000,
+                    region_offset: 0,
+                    physical_address_region_base: 0,
+                    interleave_index: 3,
+                    interleave_ways: 2,
+                    state_flags: 0x08,
+                    reserved: [0; 2],
+                }),
+                NfitStructure::Interleave(Interleave {
+                    interleave_index: 3,
+                    reserved: [0; 2],
+                    number_of_lines: 2,
+                    line_size: 256,
+                    line_offsets: vec![0, 1],
+                }),
+                NfitStructure::ControlRegion(ControlRegion {
+                    control_region_index: 2,
+                    vendor_id: 0x8086,
+                    device_id: 0x0979,
+                    revision_id: 1,
+                    subsystem_vendor_id: 0x8089,
+                    subsystem_device_id: 0x097a,
+                    subsystem_revision_id: 2,
+                    valid_fields: 1,
+                    manufacturing_location: 0x0a,
+                    manufacturing_date: 0x1819,
+                    reserved1: [0; 2],
+                    serial_number: 0xdeadbeef,
+                    region_format_interface_code: 0x0301,
+                    number_of_block_control_windows: 1,
+                    block_control_window: Some(BlockControlWindow {
+                        window_size: 0x1000,
+                        command_register_offset: 0x10,
+                        command_register_size: 8,
+                        status_register_offset: 0x20,
+                        status_register_size: 4,
+                        flags: 1,
+                        reserved: [0; 6],
+                    }),
+                }),
+                NfitStructure::BlockDataWindow(BlockDataWindow {
+                    control_region_index: 2,
+                    number_of_block_data_windows: 1,
+                    window_start_offset: 0x4000,
+                    window_size: 0x2000,
+                    accessible_memory_capacity: 0x1000_0000,
+                    first_block_address: 0,
+                }),
+                NfitStructure::FlushHintAddress(FlushHintAddress {
+                    device_handle: NfitDeviceHandle(0x1011),
+                    number_of_addresses: 1,
+                    reserved: [0; 6],
+                    addresses: vec![0xc000_0000],
+                }),
+                NfitStructure::PlatformCapabilities(PlatformCapabilities {
+                    highest_valid_capability: 1,
+                    reserved1: [0; 3],
+                    capabilities: 0x07,
+                    reserved2: [0; 4],
+                }),
+                NfitStructure::SmbiosManagementInformation(SmbiosManagementInformation {
+                    reserved: [0; 4],
+                    data: Bytes::from_static(&[0x11, 0x22, 0x33, 0x44]),
+                }),
+            ],
+        };
+        let b = Bytes::from(data.clone());
+        assert_eq!(b.len(), data.length as usize);
+        let raw = RawAcpiData::from(b);
+        let ret = NvdimmFirmwareInterface::from(raw);
+        assert_eq!(data, ret);
+
+        let spa = ret.spa_range(1).unwrap();
+        assert_eq!(spa.address_range_type.name(), "Persistent Memory");
+        assert_eq!(spa.location_cookie(), Some(0x1234));
+
+        let regions = ret.regions(1);
+        assert_eq!(regions.len(), 1);
+        assert!(regions[0].mapping.not_armed());
+        assert_eq!(regions[0].mapping.device_handle.socket(), 1);
+        assert_eq!(regions[0].control_region.unwrap().serial_number, 0xdeadbeef);
+        assert_eq!(regions[0].interleave.unwrap().line_size, 256);
+        assert_eq!(
+            regions[0].flush_hint_address.unwrap().addresses,
+            vec![0xc000_0000]
+        );
+
+        let caps = ret.platform_capabilities().unwrap();
+        assert!(caps.cpu_cache_flush_on_power_loss());
+        assert!(!caps.hardware_mirroring());
+    }
+}
@@ -0,0 +1,840 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::gas::GenericAddress;
+use super::{RawAcpiData, string_to_array};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://uefi.org/specs/ACPI/6.5/14_Platform_Communications_Channel.html
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlatformCommunicationsChannel {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub flags: u32,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 8],
+    pub subspaces: Vec<PcctSubspace>,
+}
+
+impl PlatformCommunicationsChannel {
+    // Doorbell completion is signalled through the platform interrupt instead of polling.
+    pub fn platform_interrupt(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
+
+    pub fn subspace(&self, id: usize) -> Option<&PcctSubspace> {
+        self.subspaces.get(id)
+    }
+}
+
+impl From<RawAcpiData> for PlatformCommunicationsChannel {
+    fn from(data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+
+        let mut body = data.acpi_table_data;
+        let flags = body.get_u32_le();
+        let reserved = body.split_to(8)[..].try_into().unwrap();
+        let mut subspaces = vec![];
+        while body.len() >= 2 {
+            let len = body[1] as usize;
+            if len < 2 || body.len() < len {
+                break;
+            }
+            subspaces.push(PcctSubspace::from(body.split_to(len)));
+        }
+
+        PlatformCommunicationsChannel {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            flags,
+            reserved,
+            subspaces,
+        }
+    }
+}
+
+impl From<PlatformCommunicationsChannel> for Bytes {
+    fn from(val: PlatformCommunicationsChannel) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        b.put_u32_le(val.flags);
+        b.put_slice(&val.reserved);
+        for subspace in val.subspaces {
+            b.put(Bytes::from(subspace));
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for PlatformCommunicationsChannel {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Platform Communications Channel Table");
+        w.u32("Flags (decoded below)", self.flags);
+        w.flag("Platform", self.platform_interrupt() as u8);
+        w.bytes("Reserved", &self.reserved);
+        for subspace in &self.subspaces {
+            w.blank();
+            subspace.disassemble(w);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PcctSubspace {
+    Generic(GenericSubspace),
+    HwReducedType1(HwReducedSubspace),
+    HwReducedType2(HwReducedSubspace),
+    ExtendedMaster(ExtendedSubspace),
+    ExtendedSlave(ExtendedSubspace),
+    HwRegisters(HwRegistersSubspace),
+    Unknown(
+        u8,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Bytes,
+    ),
+}
+
+impl PcctSubspace {
+    pub fn subspace_type(&self) -> u8 {
+        match self {
+            PcctSubspace::Generic(_) => 0,
+            PcctSubspace::HwReducedType1(_) => 1,
+            PcctSubspace::HwReducedType2(_) => 2,
+            PcctSubspace::ExtendedMaster(_) => 3,
+            PcctSubspace::ExtendedSlave(_) => 4,
+            PcctSubspace::HwRegisters(_) => 5,
+            PcctSubspace::Unknown(t, _) => *t,
+        }
+    }
+
+    // Base address and length of the shared memory region (the register block for type 5).
+    pub fn shared_memory(&self) -> Option<(u64, u64)> {
+        match self {
+            PcctSubspace::Generic(v) => Some((v.base_address, v.memory_length)),
+            PcctSubspace::HwReducedType1(v) | PcctSubspace::HwReducedType2(v) => {
+                Some((v.base_address, v.memory_length))
+            }
+            PcctSubspace::ExtendedMaster(v) | PcctSubspace::ExtendedSlave(v) => {
+                Some((v.base_address, v.memory_length as u64))
+            }
+            PcctSubspace::HwRegisters(v) => Some((v.base_address, v.memory_length)),
+            PcctSubspace::Unknown(..) => None,
+        }
+    }
+
+    pub fn doorbell_register(&self) -> Option<&GenericAddress> {
+        match self {
+            PcctSubspace::Generic(v) => Some(&v.doorbell_register),
+            PcctSubspace::HwReducedType1(v) | PcctSubspace::HwReducedType2(v) => {
+                Some(&v.doorbell_register)
+            }
+            PcctSubspace::ExtendedMaster(v) | PcctSubspace::ExtendedSlave(v) => {
+                Some(&v.doorbell_register)
+            }
+            PcctSubspace::HwRegisters(v) => Some(&v.doorbell_register),
+            PcctSubspace::Unknown(..) => None,
+        }
+    }
+
+    pub fn nominal_latency(&self) -> Option<u32> {
+        match self {
+            PcctSubspace::Generic(v) => Some(v.nominal_latency),
+            PcctSubspace::HwReducedType1(v) | PcctSubspace::HwReducedType2(v) => {
+                Some(v.nominal_latency)
+            }
+            PcctSubspace::ExtendedMaster(v) | PcctSubspace::ExtendedSlave(v) => {
+                Some(v.nominal_latency)
+            }
+            PcctSubspace::HwRegisters(v) => Some(v.nominal_latency),
+            PcctSubspace::Unknown(..) => None,
+        }
+    }
+
+    pub fn maximum_periodic_access_rate(&self) -> Option<u32> {
+        match self {
+            PcctSubspace::Generic(v) => Some(v.maximum_periodic_access_rate),
+            PcctSubspace::HwReducedType1(v) | PcctSubspace::HwReducedType2(v) => {
+                Some(v.maximum_periodic_access_rate)
+            }
+            PcctSubspace::ExtendedMaster(v) | PcctSubspace::ExtendedSlave(v) => {
+                Some(v.maximum_periodic_access_rate)
+            }
+            PcctSubspace::HwRegisters(_) | PcctSubspace::Unknown(..) => None,
+        }
+    }
+}
+
+impl From<Bytes> for PcctSubspace {
+    fn from(mut buf: Bytes) -> Self {
+        let subspace_type = buf.get_u8();
+        let _length = buf.get_u8();
+        match subspace_type {
+            0 if buf.len() >= 60 => PcctSubspace::Generic(GenericSubspace::from(buf)),
+            1 if buf.len() >= 60 => PcctSubspace::HwReducedType1(HwReducedSubspace::from(buf)),
+            2 if buf.len() >= 88 => PcctSubspace::HwReducedType2(HwReducedSubspace::from(buf)),
+            3 if buf.len() >= 162 => PcctSubspace::ExtendedMaster(ExtendedSubspace::from(buf)),
+            4 if buf.len() >= 162 => PcctSubspace::ExtendedSlave(ExtendedSubspace::from(buf)),
+            5 if buf.len() >= 94 => PcctSubspace::HwRegisters(HwRegistersSubspace::from(buf)),
+            t => PcctSubspace::Unknown(t, buf),
+        }
+    }
+}
+
+impl From<PcctSubspace> for Bytes {
+    fn from(val: PcctSubspace) -> Self {
+        let subspace_type = val.subspace_type();
+        let data = match val {
+            PcctSubspace::Generic(v) => Bytes::from(v),
+            PcctSubspace::HwReducedType1(v) | PcctSubspace::HwReducedType2(v) => Bytes::from(v),
+            PcctSubspace::ExtendedMaster(v) | PcctSubspace::ExtendedSlave(v) => Bytes::from(v),
+            PcctSubspace::HwRegisters(v) => Bytes::from(v),
+            PcctSubspace::Unknown(_, v) => v,
+        };
+
+        let mut b = BytesMut::with_capacity(2 + data.len());
+        b.put_u8(subspace_type);
+        b.put_u8(2 + data.len() as u8);
+        b.put(data);
+        b.freeze()
+    }
+}
+
+impl Disassemble for PcctSubspace {
+    fn disassemble(&self, w: &mut Disassembler) {
+        let name = match self {
+            PcctSubspace::Generic(_) => "Generic Communications Subspace",
+            PcctSubspace::HwReducedType1(_) => "HW-Reduced Comm Subspace",
+            PcctSubspace::HwReducedType2(_) => "HW-Reduced Comm Subspace Type2",
+            PcctSubspace::ExtendedMaster(_) => "Extended PCC Master Subspace",
+            PcctSubspace::ExtendedSlave(_) => "Extended PCC Slave Subspace",
+            PcctSubspace::HwRegisters(_) => "HW Registers based Comm Subspace",
+            PcctSubspace::Unknown(_, _) => "Unknown",
+        };
+        let length = Bytes::from(self.clone()).len();
+        w.annotated("Subtable Type", 1, self.subspace_type() as u64, name);
+        w.u8("Length", length as u8);
+        w.blank();
+
+        match self {
+            PcctSubspace::Generic(v) => {
+                w.bytes("Reserved", &v.reserved);
+                w.u64("Base Address", v.base_address);
+                w.u64("Address Length", v.memory_length);
+                w.blank();
+                w.gas("Doorbell Register", &v.doorbell_register);
+                w.blank();
+                w.u64("Preserve Mask", v.doorbell_preserve);
+                w.u64("Write Mask", v.doorbell_write);
+                w.u32("Command Latency", v.nominal_latency);
+                w.u32("Maximum Access Rate", v.maximum_periodic_access_rate);
+                w.u16("Minimum Turnaround Time", v.minimum_request_turnaround_time);
+            }
+            PcctSubspace::HwReducedType1(v) | PcctSubspace::HwReducedType2(v) => {
+                w.u32("Platform Interrupt", v.platform_interrupt);
+                platform_interrupt_flags(w, v.platform_interrupt_flags);
+                w.u8("Reserved", v.reserved);
+                w.u64("Base Address", v.base_address);
+                w.u64("Address Length", v.memory_length);
+                w.blank();
+                w.gas("Doorbell Register", &v.doorbell_register);
+                w.blank();
+                w.u64("Preserve Mask", v.doorbell_preserve);
+                w.u64("Write Mask", v.doorbell_write);
+                w.u32("Command Latency", v.nominal_latency);
+                w.u32("Maximum Access Rate", v.maximum_periodic_access_rate);
+                w.u16("Minimum Turnaround Time", v.minimum_request_turnaround_time);
+                if let Some(ack) = &v.platform_interrupt_ack {
+                    w.blank();
+                    w.gas("PlatformAckRegister", &ack.register);
+                    w.blank();
+                    w.u64("Ack Preserve Mask", ack.preserve);
+                    w.u64("Ack Write Mask", ack.write);
+                }
+            }
+            PcctSubspace::ExtendedMaster(v) | PcctSubspace::ExtendedSlave(v) => {
+                w.u32("Platform Interrupt", v.platform_interrupt);
+                platform_interrupt_flags(w, v.platform_interrupt_flags);
+                w.u8("Reserved", v.reserved1);
+                w.u64("Base Address", v.base_address);
+                w.u32("Address Length", v.memory_length);
+                w.blank();
+                w.gas("Doorbell Register", &v.doorbell_register);
+                w.blank();
+                w.u64("Preserve Mask", v.doorbell_preserve);
+                w.u64("Write Mask", v.doorbell_write);
+                w.u32("Command Latency", v.nominal_latency);
+                w.u32("Maximum Access Rate", v.maximum_periodic_access_rate);
+                w.u32("Minimum Turnaround Time", v.minimum_request_turnaround_time);
+                w.blank();
+                w.gas("PlatformAckRegister", &v.platform_interrupt_ack.register);
+                w.blank();
+                w.u64("Ack Preserve Mask", v.platform_interrupt_ack.preserve);
+                w.u64("Ack Set Mask", v.platform_interrupt_ack.write);
+                w.bytes("Reserved", &v.reserved2);
+                w.blank();
+                w.gas(
+                    "Command Complete Register",
+                    &v.command_complete_check_register,
+                );
+                w.blank();
+                w.u64("Command Complete Check Mask", v.command_complete_check_mask);
+                w.blank();
+                w.gas(
+                    "Command Update Register",
+                    &v.command_complete_update_register,
+                );
+                w.blank();
+                w.u64(
+                    "Command Update Preserve Mask",
+                    v.command_complete_update_preserve,
+                );
+                w.u64("Command Update Set Mask", v.command_complete_update_set);
+                w.blank();
+                w.gas("Error Status Register", &v.error_status_register);
+                w.blank();
+                w.u64("Error Status Mask", v.error_status_mask);
+            }
+            PcctSubspace::HwRegisters(v) => {
+                w.u16("Version", v.version);
+                w.u64("Base Address", v.base_address);
+                w.u64("Address Length", v.memory_length);
+                w.blank();
+                w.gas("Doorbell Register", &v.doorbell_register);
+                w.blank();
+                w.u64("Doorbell Preserve Mask", v.doorbell_preserve);
+                w.u64("Doorbell Write Mask", v.doorbell_write);
+                w.blank();
+                w.gas(
+                    "CmdComplete Check Register",
+                    &v.command_complete_check_register,
+                );
+                w.blank();
+                w.u64("CmdComplete Check Mask", v.command_complete_check_mask);
+                w.blank();
+                w.gas("Error Status Register", &v.error_status_register);
+                w.blank();
+                w.u64("Error Status Mask", v.error_status_mask);
+                w.u32("Nominal Latency", v.nominal_latency);
+                w.u32("Min Turnaround Time", v.minimum_request_turnaround_time);
+            }
+            PcctSubspace::Unknown(_, v) => w.bytes("Data", v),
+        }
+    }
+}
+
+fn platform_interrupt_flags(w: &mut Disassembler, flags: u8) {
+    w.u8("Flags (Decoded Below)", flags);
+    w.flag("Polarity", flags & 0x01);
+    w.flag("Mode", (flags >> 1) & 0x01);
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenericSubspace {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 6],
+    pub base_address: u64,
+    pub memory_length: u64,
+    pub doorbell_register: GenericAddress,
+    pub doorbell_preserve: u64,
+    pub doorbell_write: u64,
+    pub nominal_latency: u32,
+    pub maximum_periodic_access_rate: u32,
+    pub minimum_request_turnaround_time: u16,
+}
+
+impl From<Bytes> for GenericSubspace {
+    fn from(mut buf: Bytes) -> Self {
+        let reserved = buf.split_to(6)[..].try_into().unwrap();
+        let base_address = buf.get_u64_le();
+        let memory_length = buf.get_u64_le();
+        let doorbell_register = GenericAddress::from(buf.split_to(12));
+        let doorbell_preserve = buf.get_u64_le();
+        let doorbell_write = buf.get_u64_le();
+        let nominal_latency = buf.get_u32_le();
+        let maximum_periodic_access_rate = buf.get_u32_le();
+        let minimum_request_turnaround_time = buf.get_u16_le();
+
+        GenericSubspace {
+            reserved,
+            base_address,
+            memory_length,
+            doorbell_register,
+            doorbell_preserve,
+            doorbell_write,
+            nominal_latency,
+            maximum_periodic_access_rate,
+            minimum_request_turnaround_time,
+        }
+    }
+}
+
+impl From<GenericSubspace> for Bytes {
+    fn from(val: GenericSubspace) -> Self {
+        let mut b = BytesMut::with_capacity(60);
+        b.put_slice(&val.reserved);
+        b.put_u64_le(val.base_address);
+        b.put_u64_le(val.memory_length);
+        b.put(Bytes::from(val.doorbell_register));
+        b.put_u64_le(val.doorbell_preserve);
+        b.put_u64_le(val.doorbell_write);
+        b.put_u32_le(val.nominal_latency);
+        b.put_u32_le(val.maximum_periodic_access_rate);
+        b.put_u16_le(val.minimum_request_turnaround_time);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlatformInterruptAck {
+    pub register: GenericAddress,
+    pub preserve: u64,
+    pub write: u64,
+}
+
+impl From<Bytes> for PlatformInterruptAck {
+    fn from(mut buf: Bytes) -> Self {
+        let register = GenericAddress::from(buf.split_to(12));
+        let preserve = buf.get_u64_le();
+        let write = buf.get_u64_le();
+
+        PlatformInterruptAck {
+            register,
+            preserve,
+            write,
+        }
+    }
+}
+
+impl From<PlatformInterruptAck> for Bytes {
+    fn from(val: PlatformInterruptAck) -> Self {
+        let mut b = BytesMut::with_capacity(28);
+        b.put(Bytes::from(val.register));
+        b.put_u64_le(val.preserve);
+        b.put_u64_le(val.write);
+        b.freeze()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HwReducedSubspace {
+    pub platform_interrupt: u32,
+    pub platform_interrupt_flags: u8,
+    pub reserved: u8,
+    pub base_address: u64,
+    pub memory_length: u64,
+    pub doorbell_register: GenericAddress,
+    pub doorbell_preserve: u64,
+    pub doorbell_write: u64,
+    pub nominal_latency: u32,
+    pub maximum_periodic_access_rate: u32,
+    pub minimum_request_turnaround_time: u16,
+    // Only present in type 2 subspaces.
+    pub platform_interrupt_ack: Option<PlatformInterruptAck>,
+}
+
+impl HwReducedSubspace {
+    pub fn interrupt_active_low(&self) -> bool {
+        self.platform_interrupt_flags & 0x01 != 0
+    }
+
+    pub fn interrupt_edge_triggered(&self) -> bool {
+        self.platform_interrupt_flags & 0x02 != 0
+    }
+}
+
+impl From<Bytes> for HwReducedSubspace {
+    fn from(mut buf: Bytes) -> Self {
+        let platform_interrupt = buf.get_u32_le();
+        let platform_interrupt_flags = buf.get_u8();
+        let reserved = buf.get_u8();
+        let base_address = buf.get_u64_le();
+        let memory_length = buf.get_u64_le();
+        let doorbell_register = GenericAddress::from(buf.split_to(12));
+        let doorbell_preserve = buf.get_u64_le();
+        let doorbell_write = buf.get_u64_le();
+        let nominal_latency = buf.get_u32_le();
+        let maximum_periodic_access_rate = buf.get_u32_le();
+        let minimum_request_turnaround_time = buf.get_u16_le();
+        let platform_interrupt_ack = if buf.len() >= 28 {
+            Some(PlatformInterruptAck::from(buf.split_to(28)))
+        } else {
+            None
+        };
+
+        HwReducedSubspace {
+            platform_interrupt,
+            platform_interrupt_flags,
+            reserved,
+            base_address,
+            memory_length,
+            doorbell_register,
+            doorbell_preserve,
+            doorbell_write,
+            nominal_latency,
+            maximum_periodic_access_rate,
+            minimum_request_turnaround_time,
+            platform_interrupt_ack,
+        }
+    }
+}
+
+impl From<HwReducedSubspace> for Bytes {
+    fn from(val: HwReducedSubspace) -> Self {
+        let mut b = BytesMut::with_capacity(88);
+        b.put_u32_le(val.platform_interrupt);
+        b.put_u8(val.platform_interrupt_flags);
+        b.put_u8(val.reserved);
+        b.put_u64_le(val.base_address);
+        b.put_u64_le(val.memory_length);
+        b.put(Bytes::from(val.doorbell_register));
+        b.put_u64_le(val.doorbell_preserve);
+        b.put_u64_le(val.doorbell_write);
+        b.put_u32_le(val.nominal_latency);
+        b.put_u32_le(val.maximum_periodic_access_rate);
+        b.put_u16_le(val.minimum_request_turnaround_time);
+        if let Some(v) = val.platform_interrupt_ack {
+            b.put(Bytes::from(v));
+        }
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtendedSubspace {
+    pub platform_interrupt: u32,
+    pub platform_interrupt_flags: u8,
+    pub reserved1: u8,
+    pub base_address: u64,
+    pub memory_length: u32,
+    pub doorbell_register: GenericAddress,
+    pub doorbell_preserve: u64,
+    pub doorbell_write: u64,
+    pub nominal_latency: u32,
+    pub maximum_periodic_access_rate: u32,
+    pub minimum_request_turnaround_time: u32,
+    pub platform_interrupt_ack: PlatformInterruptAck,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved2: [u8; 8],
+    pub command_complete_check_register: GenericAddress,
+    pub command_complete_check_mask: u64,
+    pub command_complete_update_register: GenericAddress,
+    pub command_complete_update_preserve: u64,
+    pub command_complete_update_set: u64,
+    pub error_status_register: GenericAddress,
+    pub error_status_mask: u64,
+}
+
+impl ExtendedSubspace {
+    pub fn interrupt_active_low(&self) -> bool {
+        self.platform_interrupt_flags & 0x01 != 0
+    }
+
+    pub fn interrupt_edge_triggered(&self) -> bool {
+        self.platform_interrupt_flags & 0x02 != 0
+    }
+}
+
+impl From<Bytes> for ExtendedSubspace {
+    fn from(mut buf: Bytes) -> Self {
+        let platform_interrupt = buf.get_u32_le();
+        let platform_interrupt_flags = buf.get_u8();
+        let reserved1 = buf.get_u8();
+        let base_address = buf.get_u64_le();
+        let memory_length = buf.get_u32_le();
+        let doorbell_register = GenericAddress::from(buf.split_to(12));
+        let doorbell_preserve = buf.get_u64_le();
+        let doorbell_write = buf.get_u64_le();
+        let nominal_latency = buf.get_u32_le();
+        let maximum_periodic_access_rate = buf.get_u32_le();
+        let minimum_request_turnaround_time = buf.get_u32_le();
+        let platform_interrupt_ack = PlatformInterruptAck::from(buf.split_to(28));
+        let reserved2 = buf.split_to(8)[..].try_into().unwrap();
+        let command_complete_check_register = GenericAddress::from(buf.split_to(12));
+        let command_complete_check_mask = buf.get_u64_le();
+        let command_complete_update_register = GenericAddress::from(buf.split_to(12));
+        let command_complete_update_preserve = buf.get_u64_le();
+        let command_complete_update_set = buf.get_u64_le();
+        let error_status_register = GenericAddress::from(buf.split_to(12));
+        let error_status_mask = buf.get_u64_le();
+
+        ExtendedSubspace {
+            platform_interrupt,
+            platform_interrupt_flags,
+            reserved1,
+            base_address,
+            memory_length,
+            doorbell_register,
+            doorbell_preserve,
+            doorbell_write,
+            nominal_latency,
+            maximum_periodic_access_rate,
+            minimum_request_turnaround_time,
+            platform_interrupt_ack,
+            reserved2,
+            command_complete_check_register,
+            command_complete_check_mask,
+            command_complete_update_register,
+            command_complete_update_preserve,
+            command_complete_update_set,
+            error_status_register,
+            error_status_mask,
+        }
+    }
+}
+
+impl From<ExtendedSubspace> for Bytes {
+    fn from(val: ExtendedSubspace) -> Self {
+        let mut b = BytesMut::with_capacity(162);
+        b.put_u32_le(val.platform_interrupt);
+        b.put_u8(val.platform_interrupt_flags);
+        b.put_u8(val.reserved1);
+        b.put_u64_le(val.base_address);
+        b.put_u32_le(val.memory_length);
+        b.put(Bytes::from(val.doorbell_register));
+        b.put_u64_le(val.doorbell_preserve);
+        b.put_u64_le(val.doorbell_write);
+        b.put_u32_le(val.nominal_latency);
+        b.put_u32_le(val.maximum_periodic_access_rate);
+        b.put_u32_le(val.minimum_request_turnaround_time);
+        b.put(Bytes::from(val.platform_interrupt_ack));
+        b.put_slice(&val.reserved2);
+        b.put(Bytes::from(val.command_complete_check_register));
+        b.put_u64_le(val.command_complete_check_mask);
+        b.put(Bytes::from(val.command_complete_update_register));
+        b.put_u64_le(val.command_complete_update_preserve);
+        b.put_u64_le(val.command_complete_update_set);
+        b.put(Bytes::from(val.error_status_register));
+        b.put_u64_le(val.error_status_mask);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HwRegistersSubspace {
+    pub version: u16,
+    pub base_address: u64,
+    pub memory_length: u64,
+    pub doorbell_register: GenericAddress,
+    pub doorbell_preserve: u64,
+    pub doorbell_write: u64,
+    pub command_complete_check_register: GenericAddress,
+    pub command_complete_check_mask: u64,
+    pub error_status_register: GenericAddress,
+    pub error_status_mask: u64,
+    pub nominal_latency: u32,
+    pub minimum_request_turnaround_time: u32,
+}
+
+impl From<Bytes> for HwRegistersSubspace {
+    fn from(mut buf: Bytes) -> Self {
+        let version = buf.get_u16_le();
+        let base_address = buf.get_u64_le();
+        let memory_length = buf.get_u64_le();
+        let doorbell_register = GenericAddress::from(buf.split_to(12));
+        let doorbell_preserve = buf.get_u64_le();
+        let doorbell_write = buf.get_u64_le();
+        let command_complete_check_register = GenericAddress::from(buf.split_to(12));
+        let command_complete_check_mask = buf.get_u64_le();
+        let error_status_register = GenericAddress::from(buf.split_to(12));
+        let error_status_mask = buf.get_u64_le();
+        let nominal_latency = buf.get_u32_le();
+        let minimum_request_turnaround_time = buf.get_u32_le();
+
+        HwRegistersSubspace {
+            version,
+            base_address,
+            memory_length,
+            doorbell_register,
+            doorbell_preserve,
+            doorbell_write,
+            command_complete_check_register,
+            command_complete_check_mask,
+            error_status_register,
+            error_status_mask,
+            nominal_latency,
+            minimum_request_turnaround_time,
+        }
+    }
+}
+
+impl From<HwRegistersSubspace> for Bytes {
+    fn from(val: HwRegistersSubspace) -> Self {
+        let mut b = BytesMut::with_capacity(94);
+        b.put_u16_le(val.version);
+        b.put_u64_le(val.base_address);
+        b.put_u64_le(val.memory_length);
+        b.put(Bytes::from(val.doorbell_register));
+        b.put_u64_le(val.doorbell_preserve);
+        b.put_u64_le(val.doorbell_write);
+        b.put(Bytes::from(val.command_complete_check_register));
+        b.put_u64_le(val.command_complete_check_mask);
+        b.put(Bytes::from(val.error_status_register));
+        b.put_u64_le(val.error_status_mask);
+        b.put_u32_le(val.nominal_latency);
+        b.put_u32_le(val.minimum_request_turnaround_time);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AddressSpace;
+
+    #[test]
+    fn platform_communications_channel() {
+        let doorbell = GenericAddress {
+            address_space_id: AddressSpace::SystemMemory,
+            register_bit_width: 32,
+            register_bit_offset: 0,
+            access_size: 3,
+            address: 0x1000_0000,
+        };
+        let ack = PlatformInterruptAck {
+            register: GenericAddress {
+                address: 0x1000_0010,
+                ..doorbell
+            },
+            preserve: 0xffff_0000,
+            write: 1,
+        };
+        let hw_reduced = HwReducedSubspace {
+            platform_interrupt: 48,
+            platform_interrupt_flags: 0x02,
+            reserved: 0,
+            base_address: 0x8000_0000,
+            memory_length: 0x1000,
+            doorbell_register: doorbell,
+            doorbell_preserve: 0,
+            doorbell_write: 1,
+            nominal_latency: 100,
+            maximum_periodic_access_rate: 0,
+            minimum_request_turnaround_time: 10,
+            platform_interrupt_ack: None,
+        };
+
+        let data = PlatformCommunicationsChannel {
+            signature: "PCCT".to_string(),
+            length: 48 + 62 + 62 + 90 + 164 + 164 + 96,
+            revision: 2,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            flags: 1,
+            reserved: [0; 8],
+            subspaces: vec![
+                PcctSubspace::Generic(GenericSubspace {
+                    reserved: [0; 6],
+                    base_address: 0x7000_0000,
+                    memory_length: 0x100,
+                    doorbell_register: doorbell,
+                    doorbell_preserve: 0,
+                    doorbell_write: 1,
+                    nominal_latency: 50,
+                    maximum_periodic_access_rate: 1000,
+                    minimum_request_turnaround_time: 5,
+                }),
+                PcctSubspace::HwReducedType1(hw_reduced.clone()),
+                PcctSubspace::HwReducedType2(HwReducedSubspace {
+                    platform_interrupt_ack: Some(ack.clone()),
+                    ..hw_reduced
+                }),
+                PcctSubspace::ExtendedMaster(ExtendedSubspace {
+                    platform_interrupt: 49,
+                    base_address: 0x9000_0000,
+                    memory_length: 0x2000,
+                    doorbell_register: doorbell,
+                    nominal_latency: 200,
+                    platform_interrupt_ack: ack.clone(),
+                    command_complete_check_mask: 1,
+                    error_status_mask: 4,
+                    ..Default::default()
+                }),
+                PcctSubspace::ExtendedSlave(ExtendedSubspace {
+                    platform_interrupt_ack: ack,
+                    ..Default::default()
+                }),
+                PcctSubspace::HwRegisters(HwRegistersSubspace {
+                    version: 1,
+                    base_address: 0xa000_0000,
+                    memory_length: 0x40,
+                    doorbell_register: doorbell,
+                    nominal_latency: 20,
+                    ..Default::default()
+                }),
+            ],
+        };
+        let b = Bytes::from(data.clone());
+        assert_eq!(b.len(), data.length as usize);
+        let raw = RawAcpiData::from(b);
+        let ret = PlatformCommunicationsChannel::from(raw);
+        assert_eq!(data, ret);
+
+        assert!(ret.platform_interrupt());
+        let subspace = ret.subspace(3).unwrap();
+        assert_eq!(subspace.shared_memory(), Some((0x9000_0000, 0x2000)));
+        assert_eq!(subspace.nominal_latency(), Some(200));
+        assert_eq!(subspace.doorbell_register().unwrap().address, 0x1000_0000);
+        assert_eq!(
+            ret.subspace(5).unwrap().maximum_periodic_access_rate(),
+            None
+        );
+    }
+}
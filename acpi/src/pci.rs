@@ -0,0 +1,105 @@
+use super::MemoryMappedConfiguration;
+use super::error::Error;
+use super::memory::PhysicalMemoryReader;
+
+pub trait PciConfigReader {
+    fn read_config(
+        &mut self,
+        segment: u16,
+        bus: u8,
+        device: u8,
+        function: u8,
+        offset: u16,
+        buf: &mut [u8],
+    ) -> Result<(), Error>;
+
+    // Read-only sources such as an ECAM window opened through `PhysicalMemoryReader` keep this.
+    fn write_config(
+        &mut self,
+        segment: u16,
+        bus: u8,
+        device: u8,
+        function: u8,
+        offset: u16,
+        buf: &[u8],
+    ) -> Result<(), Error> {
+        let _ = (segment, bus, device, function, offset, buf);
+        Err(Error::ReadOnly)
+    }
+}
+
+// Reads configuration space through the ECAM windows described by an MCFG.
+pub struct EcamReader<'a, M> {
+    mcfg: &'a MemoryMappedConfiguration,
+    memory: M,
+}
+
+impl<'a, M> EcamReader<'a, M>
+where
+    M: PhysicalMemoryReader,
+{
+    pub fn new(mcfg: &'a MemoryMappedConfiguration, memory: M) -> Self {
+        EcamReader { mcfg, memory }
+    }
+}
+
+impl<M> PciConfigReader for EcamReader<'_, M>
+where
+    M: PhysicalMemoryReader,
+{
+    fn read_config(
+        &mut self,
+        segment: u16,
+        bus: u8,
+        device: u8,
+        function: u8,
+        offset: u16,
+        buf: &mut [u8],
+    ) -> Result<(), Error> {
+        let last = offset as usize + buf.len();
+        if last > 4096 {
+            return Err(Error::OutOfRange);
+        }
+
+        let address = self
+            .mcfg
+            .ecam_address(segment, bus, device, function, offset)
+            .ok_or(Error::OutOfRange)?;
+        self.memory.read_physical(address, buf)
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::MemoryMappedConfigurationSpace;
+    use std::io::Cursor;
+
+    #[test]
+    fn ecam_reader() {
+        let mcfg = MemoryMappedConfiguration {
+            spaces: vec![MemoryMappedConfigurationSpace {
+                base_address: 0,
+                segment_number: 0,
+                bus_number_start: 0,
+                bus_number_end: 0,
+                reserved: [0; 4],
+            }],
+            ..Default::default()
+        };
+
+        // Bus 0, device 1, function 0 lives at 0x8000.
+        let mut memory = vec![0xffu8; 0x10000];
+        memory[0x8000..0x8004].copy_from_slice(&[0x86, 0x80, 0x34, 0x12]);
+        let mut reader = EcamReader::new(&mcfg, Cursor::new(memory));
+
+        let mut id = [0u8; 4];
+        reader.read_config(0, 0, 1, 0, 0, &mut id).unwrap();
+        assert_eq!(u32::from_le_bytes(id), 0x1234_8086);
+
+        assert!(reader.read_config(0, 1, 0, 0, 0, &mut id).is_err());
+        assert!(reader.read_config(0, 0, 1, 0, 4094, &mut id).is_err());
+    }
+}
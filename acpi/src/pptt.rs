@@ -0,0 +1,854 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::{RawAcpiData, put_at, string_to_array};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://uefi.org/specs/ACPI/6.5/05_ACPI_Software_Programming_Model.html#processor-properties-topology-table-pptt
+
+const MAX_TOPOLOGY_DEPTH: usize = 16;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessorPropertiesTopology {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub structures: Vec<PpttStructure>,
+}
+
+impl ProcessorPropertiesTopology {
+    pub fn structure_at(&self, offset: u32) -> Option<&PpttStructure> {
+        self.structures.iter().find(|s| s.offset == offset)
+    }
+
+    pub fn processor_at(&self, offset: u32) -> Option<&ProcessorHierarchyNode> {
+        match &self.structure_at(offset)?.data {
+            PpttStructureData::ProcessorHierarchy(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn cache_at(&self, offset: u32) -> Option<&CacheProperties> {
+        match &self.structure_at(offset)?.data {
+            PpttStructureData::Cache(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn processors(&self) -> impl Iterator<Item = (u32, &ProcessorHierarchyNode)> {
+        self.structures.iter().filter_map(|s| match &s.data {
+            PpttStructureData::ProcessorHierarchy(v) => Some((s.offset, v)),
+            _ => None,
+        })
+    }
+
+    // Leaves without the "ACPI Processor ID valid" flag carry no usable ID and are skipped.
+    pub fn leaf_processor_ids(&self) -> Vec<u32> {
+        let mut ids = self
+            .processors()
+            .filter(|(o, p)| p.acpi_processor_id_valid() && self.is_leaf(*o, p))
+            .map(|(_, p)| p.acpi_processor_id)
+            .collect::<Vec<u32>>();
+        ids.sort();
+        ids
+    }
+
+    pub fn leaf(&self, acpi_processor_id: u32) -> Option<(u32, &ProcessorHierarchyNode)> {
+        self.processors().find(|(o, p)| {
+            p.acpi_processor_id_valid()
+                && p.acpi_processor_id == acpi_processor_id
+                && self.is_leaf(*o, p)
+        })
+    }
+
+    // Returns the leaf node followed by its parents up to the root (e.g. thread, core, cluster, package).
+    pub fn ancestors(&self, acpi_processor_id: u32) -> Vec<(u32, &ProcessorHierarchyNode)> {
+        let mut nodes = vec![];
+
+        let mut current = self.leaf(acpi_processor_id);
+        while let Some((offset, node)) = current {
+            if nodes.len() >= MAX_TOPOLOGY_DEPTH {
+                break;
+            }
+
+            nodes.push((offset, node));
+            current = if node.parent != 0 {
+                self.processor_at(node.parent).map(|p| (node.parent, p))
+            } else {
+                None
+            };
+        }
+
+        nodes
+    }
+
+    pub fn caches(&self, acpi_processor_id: u32) -> Vec<CacheLevel> {
+        let mut caches: Vec<CacheLevel> = vec![];
+
+        let mut base = 0;
+        for (_, node) in self.ancestors(acpi_processor_id) {
+            let mut depth = 0;
+            for resource in &node.private_resources {
+                let mut level = 0;
+                let mut offset = *resource;
+                while let Some(cache) = self.cache_at(offset) {
+                    if level as usize >= MAX_TOPOLOGY_DEPTH {
+                        break;
+                    }
+
+                    level += 1;
+                    if !caches.iter().any(|c| c.offset == offset) {
+                        caches.push(CacheLevel {
+                            level: base + level,
+                            offset,
+                            cache: cache.clone(),
+                        });
+                    }
+
+                    if cache.next_level_of_cache == 0 {
+                        break;
+                    }
+                    offset = cache.next_level_of_cache;
+                }
+                depth = depth.max(level);
+            }
+            base += depth;
+        }
+
+        caches.sort_by_key(|c| (c.level, c.offset));
+        caches
+    }
+
+    pub fn cache(
+        &self,
+        acpi_processor_id: u32,
+        level: u8,
+        unified_or_data: bool,
+    ) -> Option<CacheLevel> {
+        self.caches(acpi_processor_id).into_iter().find(|c| {
+            c.level == level
+                && (!unified_or_data || c.cache.cache_type() != Some(CacheType::Instruction))
+        })
+    }
+
+    // Leaf ACPI processor IDs that share the cache at `level` with `acpi_processor_id`.
+    pub fn sharing_processors(&self, acpi_processor_id: u32, level: u8) -> Vec<u32> {
+        let Some(target) = self.cache(acpi_processor_id, level, true) else {
+            return vec![];
+        };
+
+        self.leaf_processor_ids()
+            .into_iter()
+            .filter(|id| {
+                self.caches(*id)
+                    .iter()
+                    .any(|c| c.level == level && c.offset == target.offset)
+            })
+            .collect()
+    }
+
+    pub fn topology(&self) -> Vec<TopologyNode> {
+        self.children(0, 0)
+    }
+
+    fn children(&self, parent: u32, depth: usize) -> Vec<TopologyNode> {
+        if depth >= MAX_TOPOLOGY_DEPTH {
+            return vec![];
+        }
+
+        self.processors()
+            .filter(|(o, p)| p.parent == parent && *o != parent)
+            .map(|(offset, node)| TopologyNode {
+                offset,
+                node: node.clone(),
+                caches: node
+                    .private_resources
+                    .iter()
+                    .filter_map(|r| self.cache_at(*r).map(|c| (*r, c.clone())))
+                    .collect(),
+                children: self.children(offset, depth + 1),
+            })
+            .collect()
+    }
+
+    fn is_leaf(&self, offset: u32, node: &ProcessorHierarchyNode) -> bool {
+        if node.leaf() {
+            return true;
+        }
+
+        !self.processors().any(|(_, p)| p.parent == offset)
+    }
+}
+
+impl From<RawAcpiData> for ProcessorPropertiesTopology {
+    fn from(data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+
+        let mut body = data.acpi_table_data;
+        let mut structures = vec![];
+        let mut offset = 36;
+        while body.len() >= PpttStructure::HEADER_LENGTH {
+            let len = body[1] as usize;
+            if len < PpttStructure::HEADER_LENGTH || body.len() < len {
+                break;
+            }
+
+            let mut structure = PpttStructure::from(body.split_to(len));
+            structure.offset = offset;
+            structures.push(structure);
+            offset += len as u32;
+        }
+
+        ProcessorPropertiesTopology {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            structures,
+        }
+    }
+}
+
+impl From<ProcessorPropertiesTopology> for Bytes {
+    fn from(val: ProcessorPropertiesTopology) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        for structure in val.structures {
+            let offset = (structure.offset as usize).max(b.len());
+            put_at(&mut b, offset, &Bytes::from(structure));
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for ProcessorPropertiesTopology {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Processor Properties Topology Table");
+        for structure in &self.structures {
+            w.seek(structure.offset as usize);
+            structure.disassemble(w);
+            w.blank();
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TopologyNode {
+    pub offset: u32,
+    pub node: ProcessorHierarchyNode,
+    pub caches: Vec<(u32, CacheProperties)>,
+    pub children: Vec<TopologyNode>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CacheLevel {
+    pub level: u8,
+    pub offset: u32,
+    pub cache: CacheProperties,
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PpttStructure {
+    // Offset of this structure from the start of the PPTT, used by parent and cache references.
+    pub offset: u32,
+    pub length: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 2],
+    pub data: PpttStructureData,
+}
+
+impl PpttStructure {
+    const HEADER_LENGTH: usize = 4;
+}
+
+impl From<Bytes> for PpttStructure {
+    fn from(mut buf: Bytes) -> Self {
+        let structure_type = buf.get_u8();
+        let length = buf.get_u8();
+        let reserved = buf.split_to(2)[..].try_into().unwrap();
+        let data = match structure_type {
+            0 if buf.len() >= 16 => {
+                PpttStructureData::ProcessorHierarchy(ProcessorHierarchyNode::from(buf))
+            }
+            1 if buf.len() >= 20 => PpttStructureData::Cache(CacheProperties::from(buf)),
+            2 if buf.len() >= 26 => PpttStructureData::Id(IdStructure::from(buf)),
+            t => PpttStructureData::Unknown(t, buf),
+        };
+
+        PpttStructure {
+            offset: 0,
+            length,
+            reserved,
+            data,
+        }
+    }
+}
+
+impl From<PpttStructure> for Bytes {
+    fn from(val: PpttStructure) -> Self {
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_u8(val.data.structure_type());
+        b.put_u8(val.length);
+        b.put_slice(&val.reserved);
+        b.put(Bytes::from(val.data));
+        b.freeze()
+    }
+}
+
+impl Disassemble for PpttStructure {
+    fn disassemble(&self, w: &mut Disassembler) {
+        let name = match self.data {
+            PpttStructureData::ProcessorHierarchy(_) => "Processor Hierarchy Node",
+            PpttStructureData::Cache(_) => "Cache Type",
+            PpttStructureData::Id(_) => "ID",
+            PpttStructureData::Unknown(_, _) => "Unknown",
+        };
+        w.annotated("Subtable Type", 1, self.data.structure_type() as u64, name);
+        w.u8("Length", self.length);
+        w.bytes("Reserved", &self.reserved);
+
+        match &self.data {
+            PpttStructureData::ProcessorHierarchy(v) => {
+                w.u32("Flags (decoded below)", v.flags);
+                w.flag("Physical package", v.physical_package() as u8);
+                w.flag("ACPI Processor ID valid", v.acpi_processor_id_valid() as u8);
+                w.flag("Processor is a thread", v.thread() as u8);
+                w.flag("Node is a leaf", v.leaf() as u8);
+                w.flag(
+                    "Identical Implementation",
+                    v.identical_implementation() as u8,
+                );
+                w.u32("Parent", v.parent);
+                w.u32("ACPI Processor ID", v.acpi_processor_id);
+                w.u32("Private Resource Number", v.private_resource_count);
+                for resource in &v.private_resources {
+                    w.u32("Private Resource", *resource);
+                }
+            }
+            PpttStructureData::Cache(v) => {
+                w.u32("Flags (decoded below)", v.flags);
+                w.flag("Size valid", v.flags & 0x01);
+                w.flag("Number of Sets valid", (v.flags >> 1) & 0x01);
+                w.flag("Associativity valid", (v.flags >> 2) & 0x01);
+                w.flag("Allocation Type valid", (v.flags >> 3) & 0x01);
+                w.flag("Cache Type valid", (v.flags >> 4) & 0x01);
+                w.flag("Write Policy valid", (v.flags >> 5) & 0x01);
+                w.flag("Line Size valid", (v.flags >> 6) & 0x01);
+                w.flag("Cache ID valid", (v.flags >> 7) & 0x01);
+                w.u32("Next Level of Cache", v.next_level_of_cache);
+                w.u32("Size", v.size);
+                w.u32("Number of Sets", v.number_of_sets);
+                w.u8("Associativity", v.associativity);
+                w.u8("Attributes", v.attributes);
+                w.flag("Allocation Type", v.attributes & 0x03);
+                w.flag("Cache Type", (v.attributes >> 2) & 0x03);
+                w.flag("Write Policy", (v.attributes >> 4) & 0x01);
+                w.u16("Line Size", v.line_size);
+                if let Some(id) = v.cache_id {
+                    w.u32("Cache ID", id);
+                }
+            }
+            PpttStructureData::Id(v) => {
+                w.string("VENDOR_ID", 4, &String::from_utf8_lossy(&v.vendor_id));
+                w.u64("LEVEL_1_ID", v.level1_id);
+                w.u64("LEVEL_2_ID", v.level2_id);
+                w.u16("MAJOR_REV", v.major_revision);
+                w.u16("MINOR_REV", v.minor_revision);
+                w.u16("SPIN_REV", v.spin_revision);
+            }
+            PpttStructureData::Unknown(_, v) => w.bytes("Data", v),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PpttStructureData {
+    ProcessorHierarchy(ProcessorHierarchyNode),
+    Cache(CacheProperties),
+    Id(IdStructure),
+    Unknown(
+        u8,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Bytes,
+    ),
+}
+
+impl Default for PpttStructureData {
+    fn default() -> Self {
+        PpttStructureData::Unknown(0xff, Bytes::new())
+    }
+}
+
+impl PpttStructureData {
+    pub fn structure_type(&self) -> u8 {
+        match self {
+            PpttStructureData::ProcessorHierarchy(_) => 0,
+            PpttStructureData::Cache(_) => 1,
+            PpttStructureData::Id(_) => 2,
+            PpttStructureData::Unknown(t, _) => *t,
+        }
+    }
+}
+
+impl From<PpttStructureData> for Bytes {
+    fn from(val: PpttStructureData) -> Self {
+        match val {
+            PpttStructureData::ProcessorHierarchy(v) => Bytes::from(v),
+            PpttStructureData::Cache(v) => Bytes::from(v),
+            PpttStructureData::Id(v) => Bytes::from(v),
+            PpttStructureData::Unknown(_, v) => v,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessorHierarchyNode {
+    pub flags: u32,
+    pub parent: u32,
+    pub acpi_processor_id: u32,
+    pub private_resource_count: u32,
+    pub private_resources: Vec<u32>,
+}
+
+impl ProcessorHierarchyNode {
+    pub fn physical_package(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
+
+    pub fn acpi_processor_id_valid(&self) -> bool {
+        self.flags & 0x02 != 0
+    }
+
+    pub fn thread(&self) -> bool {
+        self.flags & 0x04 != 0
+    }
+
+    pub fn leaf(&self) -> bool {
+        self.flags & 0x08 != 0
+    }
+
+    pub fn identical_implementation(&self) -> bool {
+        self.flags & 0x10 != 0
+    }
+}
+
+impl From<Bytes> for ProcessorHierarchyNode {
+    fn from(mut buf: Bytes) -> Self {
+        let flags = buf.get_u32_le();
+        let parent = buf.get_u32_le();
+        let acpi_processor_id = buf.get_u32_le();
+        let private_resource_count = buf.get_u32_le();
+        let private_resources = buf
+            .chunks_exact(4)
+            .take(private_resource_count as usize)
+            .map(|d| u32::from_le_bytes(d.try_into().unwrap()))
+            .collect();
+
+        ProcessorHierarchyNode {
+            flags,
+            parent,
+            acpi_processor_id,
+            private_resource_count,
+            private_resources,
+        }
+    }
+}
+
+impl From<ProcessorHierarchyNode> for Bytes {
+    fn from(val: ProcessorHierarchyNode) -> Self {
+        let mut b = BytesMut::with_capacity(16 + 4 * val.private_resources.len());
+        b.put_u32_le(val.flags);
+        b.put_u32_le(val.parent);
+        b.put_u32_le(val.acpi_processor_id);
+        b.put_u32_le(val.private_resource_count);
+        for resource in val.private_resources {
+            b.put_u32_le(resource);
+        }
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CacheType {
+    Data,
+    Instruction,
+    Unified,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AllocationType {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CacheProperties {
+    pub flags: u32,
+    pub next_level_of_cache: u32,
+    pub size: u32,
+    pub number_of_sets: u32,
+    pub associativity: u8,
+    pub attributes: u8,
+    pub line_size: u16,
+    pub cache_id: Option<u32>,
+}
+
+impl CacheProperties {
+    pub fn size(&self) -> Option<u32> {
+        (self.flags & 0x01 != 0).then_some(self.size)
+    }
+
+    pub fn number_of_sets(&self) -> Option<u32> {
+        (self.flags & 0x02 != 0).then_some(self.number_of_sets)
+    }
+
+    pub fn associativity(&self) -> Option<u8> {
+        (self.flags & 0x04 != 0).then_some(self.associativity)
+    }
+
+    pub fn allocation_type(&self) -> Option<AllocationType> {
+        if self.flags & 0x08 == 0 {
+            return None;
+        }
+
+        match self.attributes & 0x03 {
+            0 => Some(AllocationType::Read),
+            1 => Some(AllocationType::Write),
+            _ => Some(AllocationType::ReadWrite),
+        }
+    }
+
+    pub fn cache_type(&self) -> Option<CacheType> {
+        if self.flags & 0x10 == 0 {
+            return None;
+        }
+
+        match (self.attributes >> 2) & 0x03 {
+            0 => Some(CacheType::Data),
+            1 => Some(CacheType::Instruction),
+            _ => Some(CacheType::Unified),
+        }
+    }
+
+    pub fn write_through(&self) -> Option<bool> {
+        (self.flags & 0x20 != 0).then_some(self.attributes & 0x10 != 0)
+    }
+
+    pub fn line_size(&self) -> Option<u16> {
+        (self.flags & 0x40 != 0).then_some(self.line_size)
+    }
+
+    pub fn id(&self) -> Option<u32> {
+        if self.flags & 0x80 == 0 {
+            return None;
+        }
+
+        self.cache_id
+    }
+}
+
+impl From<Bytes> for CacheProperties {
+    fn from(mut buf: Bytes) -> Self {
+        let flags = buf.get_u32_le();
+        let next_level_of_cache = buf.get_u32_le();
+        let size = buf.get_u32_le();
+        let number_of_sets = buf.get_u32_le();
+        let associativity = buf.get_u8();
+        let attributes = buf.get_u8();
+        let line_size = buf.get_u16_le();
+        let cache_id = if buf.len() >= 4 {
+            Some(buf.get_u32_le())
+        } else {
+            None
+        };
+
+        CacheProperties {
+            flags,
+            next_level_of_cache,
+            size,
+            number_of_sets,
+            associativity,
+            attributes,
+            line_size,
+            cache_id,
+        }
+    }
+}
+
+impl From<CacheProperties> for Bytes {
+    fn from(val: CacheProperties) -> Self {
+        let mut b = BytesMut::with_capacity(24);
+        b.put_u32_le(val.flags);
+        b.put_u32_le(val.next_level_of_cache);
+        b.put_u32_le(val.size);
+        b.put_u32_le(val.number_of_sets);
+        b.put_u8(val.associativity);
+        b.put_u8(val.attributes);
+        b.put_u16_le(val.line_size);
+        if let Some(v) = val.cache_id {
+            b.put_u32_le(v);
+        }
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdStructure {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub vendor_id: [u8; 4],
+    pub level1_id: u64,
+    pub level2_id: u64,
+    pub major_revision: u16,
+    pub minor_revision: u16,
+    pub spin_revision: u16,
+}
+
+impl From<Bytes> for IdStructure {
+    fn from(mut buf: Bytes) -> Self {
+        let vendor_id = buf.split_to(4)[..].try_into().unwrap();
+        let level1_id = buf.get_u64_le();
+        let level2_id = buf.get_u64_le();
+        let major_revision = buf.get_u16_le();
+        let minor_revision = buf.get_u16_le();
+        let spin_revision = buf.get_u16_le();
+
+        IdStructure {
+            vendor_id,
+            level1_id,
+            level2_id,
+            major_revision,
+            minor_revision,
+            spin_revision,
+        }
+    }
+}
+
+impl From<IdStructure> for Bytes {
+    fn from(val: IdStructure) -> Self {
+        let mut b = BytesMut::with_capacity(26);
+        b.put_slice(&val.vendor_id);
+        b.put_u64_le(val.level1_id);
+        b.put_u64_le(val.level2_id);
+        b.put_u16_le(val.major_revision);
+        b.put_u16_le(val.minor_revision);
+        b.put_u16_le(val.spin_revision);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn processor_properties_topology() {
+        // package(L3) -> 2 cores(L1D -> L2) each
+        let data = ProcessorPropertiesTopology {
+            signature: "PPTT".to_string(),
+            length: 248,
+            revision: 3,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            structures: vec![
+                PpttStructure {
+                    offset: 36,
+                    length: 28,
+                    reserved: [0; 2],
+                    data: PpttStructureData::Cache(CacheProperties {
+                        flags: 0xff,
+                        next_level_of_cache: 0,
+                        size: 32 << 20,
+                        number_of_sets: 32768,
+                        associativity: 16,
+                        attributes: 0x0a,
+                        line_size: 64,
+                        cache_id: Some(36),
+                    }),
+                },
+                PpttStructure {
+                    offset: 64,
+                    length: 24,
+                    reserved: [0; 2],
+                    data: PpttStructureData::ProcessorHierarchy(ProcessorHierarchyNode {
+                        flags: 0x01,
+                        parent: 0,
+                        acpi_processor_id: 0,
+                        private_resource_count: 1,
+                        private_resources: vec![36],
+                    }),
+                },
+                PpttStructure {
+                    offset: 88,
+                    length: 28,
+                    reserved: [0; 2],
+                    data: PpttStructureData::Cache(CacheProperties {
+                        flags: 0xff,
+                        next_level_of_cache: 0,
+                        size: 1 << 20,
+                        number_of_sets: 1024,
+                        associativity: 16,
+                        attributes: 0x0a,
+                        line_size: 64,
+                        cache_id: Some(88),
+                    }),
+                },
+                PpttStructure {
+                    offset: 116,
+                    length: 28,
+                    reserved: [0; 2],
+                    data: PpttStructureData::Cache(CacheProperties {
+                        flags: 0xff,
+                        next_level_of_cache: 88,
+                        size: 64 << 10,
+                        number_of_sets: 64,
+                        associativity: 16,
+                        attributes: 0x02,
+                        line_size: 64,
+                        cache_id: Some(116),
+                    }),
+                },
+                PpttStructure {
+                    offset: 144,
+                    length: 24,
+                    reserved: [0; 2],
+                    data: PpttStructureData::ProcessorHierarchy(ProcessorHierarchyNode {
+                        flags: 0x0a,
+                        parent: 64,
+                        acpi_processor_id: 0,
+                        private_resource_count: 1,
+                        private_resources: vec![116],
+                    }),
+                },
+                PpttStructure {
+                    offset: 168,
+                    length: 28,
+                    reserved: [0; 2],
+                    data: PpttStructureData::Cache(CacheProperties {
+                        flags: 0xff,
+                        next_level_of_cache: 0,
+                        size: 1 << 20,
+                        number_of_sets: 1024,
+                        associativity: 16,
+                        attributes: 0x0a,
+                        line_size: 64,
+                        cache_id: Some(168),
+                    }),
+                },
+                PpttStructure {
+                    offset: 196,
+                    length: 28,
+                    reserved: [0; 2],
+                    data: PpttStructureData::Cache(CacheProperties {
+                        flags: 0xff,
+                        next_level_of_cache: 168,
+                        size: 64 << 10,
+                        number_of_sets: 64,
+                        associativity: 16,
+                        attributes: 0x02,
+                        line_size: 64,
+                        cache_id: Some(196),
+                    }),
+                },
+                PpttStructure {
+                    offset: 224,
+                    length: 24,
+                    reserved: [0; 2],
+                    data: PpttStructureData::ProcessorHierarchy(ProcessorHierarchyNode {
+                        flags: 0x0a,
+                        parent: 64,
+                        acpi_processor_id: 1,
+                        private_resource_count: 1,
+                        private_resources: vec![196],
+                    }),
+                },
+            ],
+        };
+        let b = Bytes::from(data.clone());
+        let raw = RawAcpiData::from(b);
+        let ret = ProcessorPropertiesTopology::from(raw);
+        assert_eq!(data, ret);
+        assert_eq!(ret.leaf_processor_ids(), vec![0, 1]);
+
+        let caches = ret.caches(1);
+        let levels = caches
+            .iter()
+            .map(|c| (c.level, c.offset))
+            .collect::<Vec<(u8, u32)>>();
+        assert_eq!(levels, vec![(1, 196), (2, 168), (3, 36)]);
+        assert_eq!(caches[2].cache.size(), Some(32 << 20));
+        assert_eq!(caches[2].cache.cache_type(), Some(CacheType::Unified));
+
+        assert_eq!(ret.sharing_processors(0, 3), vec![0, 1]);
+        assert_eq!(ret.sharing_processors(0, 2), vec![0]);
+
+        let topology = ret.topology();
+        assert_eq!(topology.len(), 1);
+        assert_eq!(topology[0].children.len(), 2);
+        assert_eq!(topology[0].caches[0].0, 36);
+
+        // A leaf without a valid processor ID is left out.
+        let mut invalid = ret.clone();
+        if let PpttStructureData::ProcessorHierarchy(p) = &mut invalid.structures[7].data {
+            p.flags = 0x08;
+        }
+        assert_eq!(invalid.leaf_processor_ids(), vec![0]);
+    }
+}
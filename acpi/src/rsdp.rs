@@ -0,0 +1,440 @@
+use super::RawAcpiData;
+use super::error::Error;
+use super::memory::PhysicalMemoryReader;
+use super::{extract_string, string_to_array};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://uefi.org/specs/ACPI/6.5/05_ACPI_Software_Programming_Model.html#root-system-description-pointer-rsdp-structure
+
+const SIGNATURE: &str = "RSD PTR ";
+const HEADER_LENGTH: usize = 36;
+// Larger tables are taken to be garbage rather than read.
+const MAX_TABLE_LENGTH: u32 = 16 << 20;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RootSystemDescriptionPointer {
+    pub signature: String,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub revision: u8,
+    pub rsdt_address: u32,
+    // The remaining fields exist from revision 2 and are zero before it.
+    pub length: u32,
+    pub xsdt_address: u64,
+    pub extended_checksum: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 3],
+}
+
+impl RootSystemDescriptionPointer {
+    const LENGTH_V1: usize = 20;
+    const LENGTH: usize = 36;
+
+    pub fn read_from<M>(memory: &mut M, address: u64) -> Result<Self, Error>
+    where
+        M: PhysicalMemoryReader,
+    {
+        let mut data = vec![0u8; RootSystemDescriptionPointer::LENGTH_V1];
+        memory.read_physical(address, &mut data)?;
+        if &data[..8] != SIGNATURE.as_bytes() {
+            return Err(Error::InvalidRsdp);
+        }
+
+        if data[15] >= 2 {
+            data.resize(RootSystemDescriptionPointer::LENGTH, 0);
+            memory.read_physical(address, &mut data)?;
+        }
+
+        let rsdp = RootSystemDescriptionPointer::from(Bytes::from(data));
+        if !rsdp.is_valid() {
+            return Err(Error::InvalidRsdp);
+        }
+
+        Ok(rsdp)
+    }
+
+    // Legacy BIOS systems put the structure on a 16 byte boundary in the first KiB of the EBDA
+    // or in the read-only BIOS area. UEFI systems pass its address in the configuration table.
+    pub fn search<M>(memory: &mut M) -> Result<(u64, Self), Error>
+    where
+        M: PhysicalMemoryReader,
+    {
+        let mut segment = [0u8; 2];
+        memory.read_physical(0x40e, &mut segment)?;
+        let ebda = (u16::from_le_bytes(segment) as u64) << 4;
+
+        let mut areas = vec![(0xe0000, 0x20000)];
+        if ebda != 0 {
+            areas.insert(0, (ebda, 0x400));
+        }
+
+        for (start, length) in areas {
+            let mut area = vec![0u8; length];
+            memory.read_physical(start, &mut area)?;
+            for offset in (0..length).step_by(16) {
+                if !area[offset..].starts_with(SIGNATURE.as_bytes()) {
+                    continue;
+                }
+
+                let address = start + offset as u64;
+                if let Ok(rsdp) = RootSystemDescriptionPointer::read_from(memory, address) {
+                    return Ok((address, rsdp));
+                }
+            }
+        }
+
+        Err(Error::InvalidRsdp)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        let data = Bytes::from(self.clone());
+        let sum = |v: &[u8]| v.iter().fold(0u8, |sum, b| sum.wrapping_add(*b));
+        if self.signature != SIGNATURE || sum(&data[..RootSystemDescriptionPointer::LENGTH_V1]) != 0
+        {
+            return false;
+        }
+
+        self.revision < 2 || sum(&data) == 0
+    }
+
+    // The XSDT is preferred; the RSDT is only used before ACPI 2.0 or when there is no XSDT.
+    pub fn root_table_address(&self) -> u64 {
+        if self.revision >= 2 && self.xsdt_address != 0 {
+            self.xsdt_address
+        } else {
+            self.rsdt_address as u64
+        }
+    }
+
+    // Physical addresses of the tables the root table lists, in order.
+    pub fn table_addresses<M>(&self, memory: &mut M) -> Result<Vec<u64>, Error>
+    where
+        M: PhysicalMemoryReader,
+    {
+        let root = read_table(memory, self.root_table_address())?;
+        let entries = match root.signature.as_str() {
+            "XSDT" => root
+                .acpi_table_data
+                .chunks_exact(8)
+                .map(|e| u64::from_le_bytes(e.try_into().unwrap()))
+                .collect(),
+            "RSDT" => root
+                .acpi_table_data
+                .chunks_exact(4)
+                .map(|e| u32::from_le_bytes(e.try_into().unwrap()) as u64)
+                .collect(),
+            _ => return Err(Error::InvalidTable),
+        };
+
+        Ok(entries)
+    }
+}
+
+impl From<Bytes> for RootSystemDescriptionPointer {
+    fn from(mut buf: Bytes) -> Self {
+        let signature = extract_string::<8>(&mut buf);
+        let checksum = buf.get_u8();
+        let oem_id = extract_string::<6>(&mut buf);
+        let revision = buf.get_u8();
+        let rsdt_address = buf.get_u32_le();
+
+        let mut rsdp = RootSystemDescriptionPointer {
+            signature,
+            checksum,
+            oem_id,
+            revision,
+            rsdt_address,
+            ..Default::default()
+        };
+
+        if buf.len()
+            >= RootSystemDescriptionPointer::LENGTH - RootSystemDescriptionPointer::LENGTH_V1
+        {
+            rsdp.length = buf.get_u32_le();
+            rsdp.xsdt_address = buf.get_u64_le();
+            rsdp.extended_checksum = buf.get_u8();
+            rsdp.reserved = buf.split_to(3)[..].try_into().unwrap();
+        }
+
+        rsdp
+    }
+}
+
+impl From<RootSystemDescriptionPointer> for Bytes {
+    fn from(val: RootSystemDescriptionPointer) -> Self {
+        let signature = string_to_array::<8>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+
+        let mut b = BytesMut::with_capacity(RootSystemDescriptionPointer::LENGTH);
+        b.put_slice(&signature);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_u8(val.revision);
+        b.put_u32_le(val.rsdt_address);
+        if val.revision >= 2 {
+            b.put_u32_le(val.length);
+            b.put_u64_le(val.xsdt_address);
+            b.put_u8(val.extended_checksum);
+            b.put_slice(&val.reserved);
+        }
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+// Reads a whole table given the physical address of its header.
+pub fn read_table<M>(memory: &mut M, address: u64) -> Result<RawAcpiData, Error>
+where
+    M: PhysicalMemoryReader,
+{
+    let mut header = [0u8; HEADER_LENGTH];
+    memory.read_physical(address, &mut header)?;
+    let length = u32::from_le_bytes(header[4..8].try_into().unwrap());
+    let signature = header[..4]
+        .iter()
+        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || *c == b'_');
+    if !signature || (length as usize) < HEADER_LENGTH || length > MAX_TABLE_LENGTH {
+        return Err(Error::InvalidTable);
+    }
+
+    let mut data = vec![0u8; length as usize];
+    memory.read_physical(address, &mut data)?;
+    Ok(RawAcpiData::from(Bytes::from(data)))
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LintRule, MappedMemory, PhysicalMemoryMapper, TableSnapshot, XsdtRule};
+    use alloc::string::ToString;
+
+    struct VecMapper(Vec<u8>);
+
+    impl PhysicalMemoryMapper for VecMapper {
+        fn map_physical(&mut self, address: u64, length: usize) -> Result<&[u8], Error> {
+            let start = address as usize;
+            self.0.get(start..start + length).ok_or(Error::OutOfRange)
+        }
+    }
+
+    #[test]
+    fn root_system_description_pointer() {
+        let data = RootSystemDescriptionPointer {
+            signature: SIGNATURE.to_string(),
+            checksum: 0x61,
+            oem_id: "OEMID".to_string(),
+            revision: 2,
+            rsdt_address: 0x1000,
+            length: 36,
+            xsdt_address: 0x2000,
+            extended_checksum: 0xbc,
+            reserved: [0; 3],
+        };
+        let ret = RootSystemDescriptionPointer::from(Bytes::from(data.clone()));
+        assert_eq!(data, ret);
+        assert!(ret.is_valid());
+        assert_eq!(ret.root_table_address(), 0x2000);
+
+        let data = RootSystemDescriptionPointer {
+            signature: SIGNATURE.to_string(),
+            checksum: 0x63,
+            oem_id: "OEMID".to_string(),
+            revision: 0,
+            rsdt_address: 0x1000,
+            ..Default::default()
+        };
+        let ret = RootSystemDescriptionPointer::from(Bytes::from(data.clone()));
+        assert_eq!(data, ret);
+        assert!(ret.is_valid());
+        assert_eq!(ret.root_table_address(), 0x1000);
+        assert_eq!(Bytes::from(ret).len(), 20);
+
+        // The first 20 bytes still sum to zero, the extended checksum does not.
+        let data = RootSystemDescriptionPointer {
+            signature: SIGNATURE.to_string(),
+            checksum: 0x61,
+            oem_id: "OEMID".to_string(),
+            revision: 2,
+            rsdt_address: 0x1000,
+            length: 37,
+            xsdt_address: 0x2000,
+            extended_checksum: 0xbc,
+            reserved: [0; 3],
+        };
+        assert!(!data.is_valid());
+    }
+
+    #[test]
+    fn search_and_read_tables() {
+        let mut memory = vec![0u8; 0x100000];
+        let xsdt = Bytes::from(RawAcpiData {
+            signature: "XSDT".to_string(),
+            length: 44,
+            acpi_table_data: Bytes::from_static(&[0x00, 0x30, 0, 0, 0, 0, 0, 0]),
+            ..Default::default()
+        });
+        let mcfg = Bytes::from(RawAcpiData {
+            signature: "MCFG".to_string(),
+            length: 44,
+            acpi_table_data: Bytes::from_static(&[0; 8]),
+            ..Default::default()
+        });
+        memory[0x2000..0x2000 + xsdt.len()].copy_from_slice(&xsdt);
+        memory[0x3000..0x3000 + mcfg.len()].copy_from_slice(&mcfg);
+        // A plausible length behind a signature that is not a name.
+        memory[0x5000..0x5008].copy_from_slice(&[0xff, 0xff, 0xff, 0xff, 36, 0, 0, 0]);
+
+        let pointer = Bytes::from(RootSystemDescriptionPointer {
+            signature: SIGNATURE.to_string(),
+            checksum: 0x71,
+            oem_id: "OEMID".to_string(),
+            revision: 2,
+            rsdt_address: 0,
+            length: 36,
+            xsdt_address: 0x2000,
+            extended_checksum: 0xbc,
+            reserved: [0; 3],
+        });
+        memory[0xe1230..0xe1230 + 36].copy_from_slice(&pointer);
+        // Decoy without a valid checksum, ahead of the real one.
+        memory[0xe0100..0xe0108].copy_from_slice(SIGNATURE.as_bytes());
+
+        let mut memory = MappedMemory::new(VecMapper(memory));
+        let (address, rsdp) = RootSystemDescriptionPointer::search(&mut memory).unwrap();
+        assert_eq!(address, 0xe1230);
+        assert_eq!(rsdp.oem_id, "OEMID");
+
+        let addresses = rsdp.table_addresses(&mut memory).unwrap();
+        assert_eq!(addresses, vec![0x3000]);
+        let ret = read_table(&mut memory, addresses[0]).unwrap();
+        assert_eq!(ret.signature, "MCFG");
+        assert_eq!(ret.length, 44);
+
+        assert!(matches!(
+            read_table(&mut memory, 0x4000),
+            Err(Error::InvalidTable)
+        ));
+        assert!(matches!(
+            read_table(&mut memory, 0x5000),
+            Err(Error::InvalidTable)
+        ));
+    }
+
+    #[test]
+    fn table_snapshot_from_memory() {
+        let mut memory = vec![0u8; 0x10000];
+        let mut put = |address: usize, data: &[u8]| {
+            memory[address..address + data.len()].copy_from_slice(data);
+        };
+        let mut fadt = BytesMut::zeroed(80);
+        fadt[4..8].copy_from_slice(&0x5000u32.to_le_bytes());
+        put(
+            0x100,
+            &Bytes::from(RootSystemDescriptionPointer {
+                signature: SIGNATURE.to_string(),
+                checksum: 0x63,
+                oem_id: "OEMID".to_string(),
+                revision: 0,
+                rsdt_address: 0x1000,
+                ..Default::default()
+            }),
+        );
+        put(
+            0x1000,
+            &Bytes::from(RawAcpiData {
+                signature: "RSDT".to_string(),
+                length: 56,
+                acpi_table_data: Bytes::from_static(&[
+                    0x00, 0x20, 0, 0, 0, 0x30, 0, 0, 0, 0x40, 0, 0, 0, 0x60, 0, 0, 0, 0, 0x10, 0,
+                ]),
+                ..Default::default()
+            }),
+        );
+        put(
+            0x2000,
+            &Bytes::from(RawAcpiData {
+                signature: "FACP".to_string(),
+                length: 116,
+                acpi_table_data: fadt.freeze(),
+                ..Default::default()
+            }),
+        );
+        put(
+            0x3000,
+            &Bytes::from(RawAcpiData {
+                signature: "SSDT".to_string(),
+                length: 37,
+                acpi_table_data: Bytes::from_static(&[1]),
+                ..Default::default()
+            }),
+        );
+        put(
+            0x4000,
+            &Bytes::from(RawAcpiData {
+                signature: "SSDT".to_string(),
+                length: 37,
+                acpi_table_data: Bytes::from_static(&[2]),
+                ..Default::default()
+            }),
+        );
+        put(
+            0x5000,
+            &Bytes::from(RawAcpiData {
+                signature: "DSDT".to_string(),
+                length: 37,
+                acpi_table_data: Bytes::from_static(&[3]),
+                ..Default::default()
+            }),
+        );
+
+        let mut memory = MappedMemory::new(VecMapper(memory));
+        let ret = TableSnapshot::read_from(&mut memory, 0x100).unwrap();
+        let tables = ret
+            .tables
+            .iter()
+            .map(|t| (t.signature.as_str(), t.instance, t.address))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tables,
+            vec![
+                ("RSDT", 1, Some(0x1000)),
+                ("FACP", 1, Some(0x2000)),
+                ("SSDT", 1, Some(0x3000)),
+                ("SSDT", 2, Some(0x4000)),
+                ("DSDT", 1, Some(0x5000)),
+            ]
+        );
+        assert_eq!(ret.table("SSDT", 2).unwrap().raw().acpi_table_data[..], [2]);
+
+        // Entries to zeroed memory and past the end are left for the linter to report.
+        let findings = XsdtRule.check(&ret.tables);
+        let messages = findings
+            .iter()
+            .map(|f| f.message.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "entry 3 points to 0x6000, no table there",
+                "entry 4 points to 0x100000, no table there",
+            ]
+        );
+
+        let mut buf = [0u8; 4];
+        assert!(matches!(
+            memory.read_physical(0xfffe, &mut buf),
+            Err(Error::OutOfRange)
+        ));
+        assert!(matches!(
+            TableSnapshot::read_from(&mut memory, 0x200),
+            Err(Error::InvalidRsdp)
+        ));
+    }
+}
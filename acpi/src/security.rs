@@ -0,0 +1,871 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::{RawAcpiData, extract_cstring, put_at, string_to_array};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://learn.microsoft.com/en-us/windows-hardware/drivers/bringup/acpi-system-description-tables#windows-smm-security-mitigation-table-wsmt
+// https://uefi.org/specs/ACPI/6.5/05_ACPI_Software_Programming_Model.html#secure-devices-sdev-acpi-table
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowsSmmSecurityMitigations {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub protection_flags: u32,
+}
+
+impl WindowsSmmSecurityMitigations {
+    pub fn fixed_comm_buffers(&self) -> bool {
+        self.protection_flags & 0x01 != 0
+    }
+
+    pub fn comm_buffer_nested_pointer_protection(&self) -> bool {
+        self.protection_flags & 0x02 != 0
+    }
+
+    pub fn system_resource_protection(&self) -> bool {
+        self.protection_flags & 0x04 != 0
+    }
+}
+
+impl From<RawAcpiData> for WindowsSmmSecurityMitigations {
+    fn from(data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+
+        let mut body = data.acpi_table_data;
+        let protection_flags = body.get_u32_le();
+
+        WindowsSmmSecurityMitigations {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            protection_flags,
+        }
+    }
+}
+
+impl From<WindowsSmmSecurityMitigations> for Bytes {
+    fn from(val: WindowsSmmSecurityMitigations) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        b.put_u32_le(val.protection_flags);
+        b.freeze()
+    }
+}
+
+impl Disassemble for WindowsSmmSecurityMitigations {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Windows SMM Security Mitigations Table");
+        w.u32("Protection Flags", self.protection_flags);
+        w.flag("FIXED_COMM_BUFFERS", self.fixed_comm_buffers() as u8);
+        w.flag(
+            "COMM_BUFFER_NESTED_PTR_PROTECTION",
+            self.comm_buffer_nested_pointer_protection() as u8,
+        );
+        w.flag(
+            "SYSTEM_RESOURCE_PROTECTION",
+            self.system_resource_protection() as u8,
+        );
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SecureDevices {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub entries: Vec<SecureDeviceEntry>,
+}
+
+impl From<RawAcpiData> for SecureDevices {
+    fn from(data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+
+        let mut body = data.acpi_table_data;
+        let mut entries = vec![];
+        while body.len() >= 4 {
+            let len = u16::from_le_bytes([body[2], body[3]]) as usize;
+            if len < 4 || body.len() < len {
+                break;
+            }
+            entries.push(SecureDeviceEntry::from(body.split_to(len)));
+        }
+
+        SecureDevices {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            entries,
+        }
+    }
+}
+
+impl From<SecureDevices> for Bytes {
+    fn from(val: SecureDevices) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        for entry in val.entries {
+            b.put(Bytes::from(entry));
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for SecureDevices {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Secure Devices Table");
+        for entry in &self.entries {
+            entry.disassemble(w);
+            w.blank();
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SecureDeviceEntry {
+    AcpiNamespaceDevice(NamespaceDevice),
+    PcieEndpoint(PcieEndpoint),
+    Unknown(
+        u8,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Bytes,
+    ),
+}
+
+impl SecureDeviceEntry {
+    pub fn flags(&self) -> u8 {
+        match self {
+            SecureDeviceEntry::AcpiNamespaceDevice(v) => v.flags,
+            SecureDeviceEntry::PcieEndpoint(v) => v.flags,
+            SecureDeviceEntry::Unknown(_, v) => v.get(1).copied().unwrap_or_default(),
+        }
+    }
+
+    // The OS may hand the device over to the non-secure OS once it no longer needs it.
+    pub fn allow_handoff(&self) -> bool {
+        self.flags() & 0x01 != 0
+    }
+}
+
+impl From<Bytes> for SecureDeviceEntry {
+    fn from(buf: Bytes) -> Self {
+        match buf[0] {
+            0 if buf.len() >= NamespaceDevice::HEADER_LENGTH => {
+                SecureDeviceEntry::AcpiNamespaceDevice(NamespaceDevice::from(buf))
+            }
+            1 if buf.len() >= PcieEndpoint::HEADER_LENGTH => {
+                SecureDeviceEntry::PcieEndpoint(PcieEndpoint::from(buf))
+            }
+            t => SecureDeviceEntry::Unknown(t, buf),
+        }
+    }
+}
+
+impl From<SecureDeviceEntry> for Bytes {
+    fn from(val: SecureDeviceEntry) -> Self {
+        match val {
+            SecureDeviceEntry::AcpiNamespaceDevice(v) => Bytes::from(v),
+            SecureDeviceEntry::PcieEndpoint(v) => Bytes::from(v),
+            SecureDeviceEntry::Unknown(_, v) => v,
+        }
+    }
+}
+
+impl Disassemble for SecureDeviceEntry {
+    fn disassemble(&self, w: &mut Disassembler) {
+        let start = w.offset();
+        match self {
+            SecureDeviceEntry::AcpiNamespaceDevice(v) => {
+                w.annotated("Subtable Type", 1, 0, "Namespace Device");
+                entry_flags(w, v.flags);
+                w.u16("Length", v.length);
+                w.u16("Device ID Offset", v.device_identifier_offset);
+                w.u16("Device ID Length", v.device_identifier_length);
+                w.u16("Vendor Data Offset", v.vendor_data_offset);
+                w.u16("Vendor Data Length", v.vendor_data_length);
+                if let (Some(offset), Some(len)) = (
+                    v.secure_access_components_offset,
+                    v.secure_access_components_length,
+                ) {
+                    w.u16("Secure Access Components Offset", offset);
+                    w.u16("Secure Access Components Length", len);
+                }
+
+                w.seek(start + v.device_identifier_offset as usize);
+                w.string(
+                    "Namepath",
+                    v.device_identifier_length as usize,
+                    &v.device_identifier,
+                );
+                if v.vendor_data_offset != 0 {
+                    w.seek(start + v.vendor_data_offset as usize);
+                    w.bytes("Vendor Data", &v.vendor_data);
+                }
+                if let Some(offset) = v.secure_access_components_offset {
+                    w.seek(start + offset as usize);
+                }
+                for component in &v.secure_access_components {
+                    w.blank();
+                    component.disassemble(w);
+                }
+                w.seek(start + v.length as usize);
+            }
+            SecureDeviceEntry::PcieEndpoint(v) => {
+                w.annotated("Subtable Type", 1, 1, "PCIe Endpoint Device");
+                entry_flags(w, v.flags);
+                w.u16("Length", v.length);
+                w.u16("PCI Segment Number", v.segment);
+                w.u16("Start Bus", v.start_bus);
+                w.u16("Path Offset", v.path_offset);
+                w.u16("Path Length", v.path_length);
+                w.u16("Vendor Data Offset", v.vendor_data_offset);
+                w.u16("Vendor Data Length", v.vendor_data_length);
+
+                w.seek(start + v.path_offset as usize);
+                for (device, function) in &v.path {
+                    w.u8("Device", *device);
+                    w.u8("Function", *function);
+                }
+                if v.vendor_data_offset != 0 {
+                    w.seek(start + v.vendor_data_offset as usize);
+                    w.bytes("Vendor Data", &v.vendor_data);
+                }
+                w.seek(start + v.length as usize);
+            }
+            SecureDeviceEntry::Unknown(t, v) => {
+                w.annotated("Subtable Type", 1, *t as u64, "Unknown");
+                w.bytes("Data", v.get(1..).unwrap_or_default());
+            }
+        }
+    }
+}
+
+fn entry_flags(w: &mut Disassembler, flags: u8) {
+    w.u8("Flags (decoded below)", flags);
+    w.flag("Allow handoff to unsecure OS", flags & 0x01);
+    w.flag("Secure access components present", (flags >> 1) & 0x01);
+}
+
+fn field(entry: &Bytes, offset: u16, len: u16) -> Bytes {
+    let start = (offset as usize).min(entry.len());
+    let end = (start + len as usize).min(entry.len());
+    entry.slice(start..end)
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NamespaceDevice {
+    pub flags: u8,
+    pub length: u16,
+    pub device_identifier_offset: u16,
+    pub device_identifier_length: u16,
+    pub vendor_data_offset: u16,
+    pub vendor_data_length: u16,
+    // Present when flags bit 1 is set.
+    pub secure_access_components_offset: Option<u16>,
+    pub secure_access_components_length: Option<u16>,
+    pub device_identifier: String,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub vendor_data: Bytes,
+    pub secure_access_components: Vec<SecureAccessComponent>,
+}
+
+impl NamespaceDevice {
+    const HEADER_LENGTH: usize = 12;
+
+    pub fn secure_access_components_present(&self) -> bool {
+        self.flags & 0x02 != 0
+    }
+}
+
+impl From<Bytes> for NamespaceDevice {
+    fn from(mut buf: Bytes) -> Self {
+        let entry = buf.clone();
+        let _entry_type = buf.get_u8();
+        let flags = buf.get_u8();
+        let length = buf.get_u16_le();
+        let device_identifier_offset = buf.get_u16_le();
+        let device_identifier_length = buf.get_u16_le();
+        let vendor_data_offset = buf.get_u16_le();
+        let vendor_data_length = buf.get_u16_le();
+        let (secure_access_components_offset, secure_access_components_length) =
+            if flags & 0x02 != 0 && buf.len() >= 4 {
+                (Some(buf.get_u16_le()), Some(buf.get_u16_le()))
+            } else {
+                (None, None)
+            };
+
+        let device_identifier = extract_cstring(&field(
+            &entry,
+            device_identifier_offset,
+            device_identifier_length,
+        ));
+        let vendor_data = field(&entry, vendor_data_offset, vendor_data_length);
+
+        let mut secure_access_components = vec![];
+        if let (Some(offset), Some(len)) = (
+            secure_access_components_offset,
+            secure_access_components_length,
+        ) {
+            let mut components = field(&entry, offset, len);
+            while components.len() >= 4 {
+                let len = u16::from_le_bytes([components[2], components[3]]) as usize;
+                if len < 4 || components.len() < len {
+                    break;
+                }
+                secure_access_components
+                    .push(SecureAccessComponent::from(components.split_to(len)));
+            }
+        }
+
+        NamespaceDevice {
+            flags,
+            length,
+            device_identifier_offset,
+            device_identifier_length,
+            vendor_data_offset,
+            vendor_data_length,
+            secure_access_components_offset,
+            secure_access_components_length,
+            device_identifier,
+            vendor_data,
+            secure_access_components,
+        }
+    }
+}
+
+impl From<NamespaceDevice> for Bytes {
+    fn from(val: NamespaceDevice) -> Self {
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_u8(0);
+        b.put_u8(val.flags);
+        b.put_u16_le(val.length);
+        b.put_u16_le(val.device_identifier_offset);
+        b.put_u16_le(val.device_identifier_length);
+        b.put_u16_le(val.vendor_data_offset);
+        b.put_u16_le(val.vendor_data_length);
+        if let (Some(offset), Some(len)) = (
+            val.secure_access_components_offset,
+            val.secure_access_components_length,
+        ) {
+            b.put_u16_le(offset);
+            b.put_u16_le(len);
+        }
+        b.resize(val.length as usize, 0);
+
+        put_at(
+            &mut b,
+            val.device_identifier_offset as usize,
+            val.device_identifier.as_bytes(),
+        );
+        if val.vendor_data_offset != 0 {
+            put_at(&mut b, val.vendor_data_offset as usize, &val.vendor_data);
+        }
+        if let Some(offset) = val.secure_access_components_offset {
+            let mut components = BytesMut::new();
+            for component in val.secure_access_components {
+                components.put(Bytes::from(component));
+            }
+            put_at(&mut b, offset as usize, &components);
+        }
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SecureAccessComponent {
+    Identification(IdentificationComponent),
+    Memory(MemoryComponent),
+    Unknown(
+        u8,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Bytes,
+    ),
+}
+
+impl From<Bytes> for SecureAccessComponent {
+    fn from(buf: Bytes) -> Self {
+        match buf[0] {
+            0 if buf.len() >= IdentificationComponent::HEADER_LENGTH => {
+                SecureAccessComponent::Identification(IdentificationComponent::from(buf))
+            }
+            1 if buf.len() >= MemoryComponent::LENGTH => {
+                SecureAccessComponent::Memory(MemoryComponent::from(buf))
+            }
+            t => SecureAccessComponent::Unknown(t, buf),
+        }
+    }
+}
+
+impl From<SecureAccessComponent> for Bytes {
+    fn from(val: SecureAccessComponent) -> Self {
+        match val {
+            SecureAccessComponent::Identification(v) => Bytes::from(v),
+            SecureAccessComponent::Memory(v) => Bytes::from(v),
+            SecureAccessComponent::Unknown(_, v) => v,
+        }
+    }
+}
+
+impl Disassemble for SecureAccessComponent {
+    fn disassemble(&self, w: &mut Disassembler) {
+        let start = w.offset();
+        match self {
+            SecureAccessComponent::Identification(v) => {
+                w.annotated("Subtable Type", 1, 0, "Identification Based Secure Access");
+                w.u8("Flags", v.flags);
+                w.u16("Length", v.length);
+                w.u16("Hardware ID Offset", v.hardware_id_offset);
+                w.u16("Hardware ID Length", v.hardware_id_length);
+                w.u16("Subsystem ID Offset", v.subsystem_id_offset);
+                w.u16("Subsystem ID Length", v.subsystem_id_length);
+                w.u16("Hardware Revision", v.hardware_revision);
+                w.u8("Hardware Rev Present", v.hardware_revision_present);
+                w.u8("Class Code Present", v.class_code_present);
+                w.u8("PCI Base Class", v.pci_base_class);
+                w.u8("PCI SubClass", v.pci_sub_class);
+                w.u8("PCI Programming Xface", v.pci_programming_interface);
+
+                w.seek(start + v.hardware_id_offset as usize);
+                w.string("Hardware ID", v.hardware_id_length as usize, &v.hardware_id);
+                if v.subsystem_id_offset != 0 {
+                    w.seek(start + v.subsystem_id_offset as usize);
+                    w.string(
+                        "Subsystem ID",
+                        v.subsystem_id_length as usize,
+                        &v.subsystem_id,
+                    );
+                }
+                w.seek(start + v.length as usize);
+            }
+            SecureAccessComponent::Memory(v) => {
+                w.annotated("Subtable Type", 1, 1, "Memory Based Secure Access");
+                w.u8("Flags", v.flags);
+                w.u16("Length", v.length);
+                w.bytes("Reserved", &v.reserved);
+                w.u64("Memory Base Address", v.base_address);
+                w.u64("Memory Length", v.memory_length);
+            }
+            SecureAccessComponent::Unknown(t, v) => {
+                w.annotated("Subtable Type", 1, *t as u64, "Unknown");
+                w.bytes("Data", v.get(1..).unwrap_or_default());
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdentificationComponent {
+    pub flags: u8,
+    pub length: u16,
+    pub hardware_id_offset: u16,
+    pub hardware_id_length: u16,
+    pub subsystem_id_offset: u16,
+    pub subsystem_id_length: u16,
+    pub hardware_revision: u16,
+    pub hardware_revision_present: u8,
+    pub class_code_present: u8,
+    pub pci_base_class: u8,
+    pub pci_sub_class: u8,
+    pub pci_programming_interface: u8,
+    pub hardware_id: String,
+    pub subsystem_id: String,
+}
+
+impl IdentificationComponent {
+    const HEADER_LENGTH: usize = 19;
+
+    pub fn hardware_revision(&self) -> Option<u16> {
+        (self.hardware_revision_present != 0).then_some(self.hardware_revision)
+    }
+
+    pub fn class_code(&self) -> Option<(u8, u8, u8)> {
+        (self.class_code_present != 0).then_some((
+            self.pci_base_class,
+            self.pci_sub_class,
+            self.pci_programming_interface,
+        ))
+    }
+}
+
+impl From<Bytes> for IdentificationComponent {
+    fn from(mut buf: Bytes) -> Self {
+        let component = buf.clone();
+        let _component_type = buf.get_u8();
+        let flags = buf.get_u8();
+        let length = buf.get_u16_le();
+        let hardware_id_offset = buf.get_u16_le();
+        let hardware_id_length = buf.get_u16_le();
+        let subsystem_id_offset = buf.get_u16_le();
+        let subsystem_id_length = buf.get_u16_le();
+        let hardware_revision = buf.get_u16_le();
+        let hardware_revision_present = buf.get_u8();
+        let class_code_present = buf.get_u8();
+        let pci_base_class = buf.get_u8();
+        let pci_sub_class = buf.get_u8();
+        let pci_programming_interface = buf.get_u8();
+
+        let hardware_id =
+            extract_cstring(&field(&component, hardware_id_offset, hardware_id_length));
+        let subsystem_id = if subsystem_id_offset != 0 {
+            extract_cstring(&field(&component, subsystem_id_offset, subsystem_id_length))
+        } else {
+            String::new()
+        };
+
+        IdentificationComponent {
+            flags,
+            length,
+            hardware_id_offset,
+            hardware_id_length,
+            subsystem_id_offset,
+            subsystem_id_length,
+            hardware_revision,
+            hardware_revision_present,
+            class_code_present,
+            pci_base_class,
+            pci_sub_class,
+            pci_programming_interface,
+            hardware_id,
+            subsystem_id,
+        }
+    }
+}
+
+impl From<IdentificationComponent> for Bytes {
+    fn from(val: IdentificationComponent) -> Self {
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_u8(0);
+        b.put_u8(val.flags);
+        b.put_u16_le(val.length);
+        b.put_u16_le(val.hardware_id_offset);
+        b.put_u16_le(val.hardware_id_length);
+        b.put_u16_le(val.subsystem_id_offset);
+        b.put_u16_le(val.subsystem_id_length);
+        b.put_u16_le(val.hardware_revision);
+        b.put_u8(val.hardware_revision_present);
+        b.put_u8(val.class_code_present);
+        b.put_u8(val.pci_base_class);
+        b.put_u8(val.pci_sub_class);
+        b.put_u8(val.pci_programming_interface);
+        b.resize(val.length as usize, 0);
+
+        put_at(
+            &mut b,
+            val.hardware_id_offset as usize,
+            val.hardware_id.as_bytes(),
+        );
+        if val.subsystem_id_offset != 0 {
+            put_at(
+                &mut b,
+                val.subsystem_id_offset as usize,
+                val.subsystem_id.as_bytes(),
+            );
+        }
+        b.freeze()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryComponent {
+    pub flags: u8,
+    pub length: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 4],
+    pub base_address: u64,
+    pub memory_length: u64,
+}
+
+impl MemoryComponent {
+    const LENGTH: usize = 24;
+}
+
+impl From<Bytes> for MemoryComponent {
+    fn from(mut buf: Bytes) -> Self {
+        let _component_type = buf.get_u8();
+        let flags = buf.get_u8();
+        let length = buf.get_u16_le();
+        let reserved = buf.split_to(4)[..].try_into().unwrap();
+        let base_address = buf.get_u64_le();
+        let memory_length = buf.get_u64_le();
+
+        MemoryComponent {
+            flags,
+            length,
+            reserved,
+            base_address,
+            memory_length,
+        }
+    }
+}
+
+impl From<MemoryComponent> for Bytes {
+    fn from(val: MemoryComponent) -> Self {
+        let mut b = BytesMut::with_capacity(MemoryComponent::LENGTH);
+        b.put_u8(1);
+        b.put_u8(val.flags);
+        b.put_u16_le(val.length);
+        b.put_slice(&val.reserved);
+        b.put_u64_le(val.base_address);
+        b.put_u64_le(val.memory_length);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PcieEndpoint {
+    pub flags: u8,
+    pub length: u16,
+    pub segment: u16,
+    pub start_bus: u16,
+    pub path_offset: u16,
+    pub path_length: u16,
+    pub vendor_data_offset: u16,
+    pub vendor_data_length: u16,
+    // (device, function) pairs walked from the start bus.
+    pub path: Vec<(u8, u8)>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub vendor_data: Bytes,
+}
+
+impl PcieEndpoint {
+    const HEADER_LENGTH: usize = 16;
+}
+
+impl From<Bytes> for PcieEndpoint {
+    fn from(mut buf: Bytes) -> Self {
+        let entry = buf.clone();
+        let _entry_type = buf.get_u8();
+        let flags = buf.get_u8();
+        let length = buf.get_u16_le();
+        let segment = buf.get_u16_le();
+        let start_bus = buf.get_u16_le();
+        let path_offset = buf.get_u16_le();
+        let path_length = buf.get_u16_le();
+        let vendor_data_offset = buf.get_u16_le();
+        let vendor_data_length = buf.get_u16_le();
+
+        let path = field(&entry, path_offset, path_length)
+            .chunks_exact(2)
+            .map(|d| (d[0], d[1]))
+            .collect();
+        let vendor_data = field(&entry, vendor_data_offset, vendor_data_length);
+
+        PcieEndpoint {
+            flags,
+            length,
+            segment,
+            start_bus,
+            path_offset,
+            path_length,
+            vendor_data_offset,
+            vendor_data_length,
+            path,
+            vendor_data,
+        }
+    }
+}
+
+impl From<PcieEndpoint> for Bytes {
+    fn from(val: PcieEndpoint) -> Self {
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_u8(1);
+        b.put_u8(val.flags);
+        b.put_u16_le(val.length);
+        b.put_u16_le(val.segment);
+        b.put_u16_le(val.start_bus);
+        b.put_u16_le(val.path_offset);
+        b.put_u16_le(val.path_length);
+        b.put_u16_le(val.vendor_data_offset);
+        b.put_u16_le(val.vendor_data_length);
+        b.resize(val.length as usize, 0);
+
+        let path = val
+            .path
+            .iter()
+            .flat_map(|(device, function)| [*device, *function])
+            .collect::<Vec<u8>>();
+        put_at(&mut b, val.path_offset as usize, &path);
+        if val.vendor_data_offset != 0 {
+            put_at(&mut b, val.vendor_data_offset as usize, &val.vendor_data);
+        }
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_smm_security_mitigations() {
+        let data = WindowsSmmSecurityMitigations {
+            signature: "WSMT".to_string(),
+            length: 40,
+            revision: 1,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            protection_flags: 0x05,
+        };
+        let b = Bytes::from(data.clone());
+        let raw = RawAcpiData::from(b);
+        let ret = WindowsSmmSecurityMitigations::from(raw);
+        assert_eq!(data, ret);
+        assert!(ret.fixed_comm_buffers());
+        assert!(!ret.comm_buffer_nested_pointer_protection());
+        assert!(ret.system_resource_protection());
+    }
+
+    #[test]
+    fn secure_devices() {
+        let data = SecureDevices {
+            signature: "SDEV".to_string(),
+            length: 36 + 72 + 24,
+            revision: 1,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            entries: vec![
+                SecureDeviceEntry::AcpiNamespaceDevice(NamespaceDevice {
+                    flags: 0x03,
+                    length: 72,
+                    device_identifier_offset: 16,
+                    device_identifier_length: 8,
+                    vendor_data_offset: 0,
+                    vendor_data_length: 0,
+                    secure_access_components_offset: Some(24),
+                    secure_access_components_length: Some(48),
+                    device_identifier: "\\_SB.FPC".to_string(),
+                    vendor_data: Bytes::new(),
+                    secure_access_components: vec![
+                        SecureAccessComponent::Identification(IdentificationComponent {
+                            flags: 0,
+                            length: 24,
+                            hardware_id_offset: 19,
+                            hardware_id_length: 5,
+                            hardware_revision: 2,
+                            hardware_revision_present: 1,
+                            hardware_id: "FPC01".to_string(),
+                            ..Default::default()
+                        }),
+                        SecureAccessComponent::Memory(MemoryComponent {
+                            flags: 0,
+                            length: 24,
+                            reserved: [0; 4],
+                            base_address: 0xfe00_0000,
+                            memory_length: 0x1000,
+                        }),
+                    ],
+                }),
+                SecureDeviceEntry::PcieEndpoint(PcieEndpoint {
+                    flags: 0x01,
+                    length: 24,
+                    segment: 0,
+                    start_bus: 0,
+                    path_offset: 16,
+                    path_length: 4,
+                    vendor_data_offset: 20,
+                    vendor_data_length: 4,
+                    path: vec![(0x1c, 0), (0, 0)],
+                    vendor_data: Bytes::from_static(&[1, 2, 3, 4]),
+                }),
+            ],
+        };
+        let b = Bytes::from(data.clone());
+        assert_eq!(b.len(), data.length as usize);
+        let raw = RawAcpiData::from(b);
+        let ret = SecureDevices::from(raw);
+        assert_eq!(data, ret);
+        assert!(ret.entries[1].allow_handoff());
+    }
+}
@@ -0,0 +1,66 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use bytes::Bytes;
+use core::fmt::Write;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serializer};
+
+// Byte buffers and arrays are written as lowercase hex strings, e.g. "0a1b2c".
+
+pub trait HexBytes: Sized {
+    fn hex_bytes(&self) -> &[u8];
+
+    fn from_hex_bytes(value: Vec<u8>) -> Option<Self>;
+}
+
+impl HexBytes for Bytes {
+    fn hex_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn from_hex_bytes(value: Vec<u8>) -> Option<Self> {
+        Some(Bytes::from(value))
+    }
+}
+
+impl<const N: usize> HexBytes for [u8; N] {
+    fn hex_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn from_hex_bytes(value: Vec<u8>) -> Option<Self> {
+        value.try_into().ok()
+    }
+}
+
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: HexBytes,
+    S: Serializer,
+{
+    let bytes = value.hex_bytes();
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        write!(s, "{:02x}", b).unwrap();
+    }
+    serializer.serialize_str(&s)
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: HexBytes,
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    if s.len() % 2 != 0 || !s.is_ascii() {
+        return Err(D::Error::custom("invalid hex string"));
+    }
+
+    let bytes = (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(D::Error::custom)?;
+    let len = bytes.len();
+    T::from_hex_bytes(bytes).ok_or_else(|| D::Error::invalid_length(len, &"a fixed size array"))
+}
@@ -0,0 +1,877 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::gas::{AddressSpace, GenericAddress};
+use super::{RawAcpiData, extract_cstring, put_at, string_to_array};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://learn.microsoft.com/en-us/windows-hardware/drivers/serports/serial-port-console-redirection-table
+// https://learn.microsoft.com/en-us/windows-hardware/drivers/bringup/acpi-debug-port-table
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SerialPortConsoleRedirection {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub interface_type: SerialInterface,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved1: [u8; 3],
+    pub base_address: GenericAddress,
+    pub interrupt_type: u8,
+    pub irq: u8,
+    pub global_system_interrupt: u32,
+    pub configured_baud_rate: u8,
+    pub parity: u8,
+    pub stop_bits: u8,
+    pub flow_control: u8,
+    pub terminal_type: u8,
+    pub language: u8,
+    pub pci_device_id: u16,
+    pub pci_vendor_id: u16,
+    pub pci_bus_number: u8,
+    pub pci_device_number: u8,
+    pub pci_function_number: u8,
+    pub pci_flags: u32,
+    pub pci_segment: u8,
+    pub uart_clock_frequency: u32,
+    pub precise_baud_rate: Option<u32>,
+    pub namespace_string_length: Option<u16>,
+    pub namespace_string_offset: Option<u16>,
+    pub namespace_string: Option<String>,
+}
+
+impl SerialPortConsoleRedirection {
+    const NAMESPACE_STRING_OFFSET: usize = 88;
+
+    pub fn pc_at_interrupt(&self) -> bool {
+        self.interrupt_type & 0x01 != 0
+    }
+
+    pub fn io_apic_interrupt(&self) -> bool {
+        self.interrupt_type & 0x02 != 0
+    }
+
+    pub fn io_sapic_interrupt(&self) -> bool {
+        self.interrupt_type & 0x04 != 0
+    }
+
+    pub fn gic_interrupt(&self) -> bool {
+        self.interrupt_type & 0x08 != 0
+    }
+
+    pub fn plic_interrupt(&self) -> bool {
+        self.interrupt_type & 0x10 != 0
+    }
+
+    pub fn baud_rate(&self) -> Option<u32> {
+        if let Some(v) = self.precise_baud_rate.filter(|v| *v != 0) {
+            return Some(v);
+        }
+
+        match self.configured_baud_rate {
+            3 => Some(9600),
+            4 => Some(19200),
+            6 => Some(57600),
+            7 => Some(115200),
+            _ => None,
+        }
+    }
+
+    pub fn dcd_flow_control(&self) -> bool {
+        self.flow_control & 0x01 != 0
+    }
+
+    pub fn rts_cts_flow_control(&self) -> bool {
+        self.flow_control & 0x02 != 0
+    }
+
+    pub fn xon_xoff_flow_control(&self) -> bool {
+        self.flow_control & 0x04 != 0
+    }
+
+    pub fn is_pci_device(&self) -> bool {
+        self.pci_device_id != 0xffff && self.pci_vendor_id != 0xffff
+    }
+
+    // Linux `earlycon=` compatible options, e.g. `uart,mmio32,0xfe215040,115200`.
+    pub fn console_options(&self) -> Option<String> {
+        if self.base_address.address == 0 {
+            return None;
+        }
+
+        let iotype = match (
+            self.base_address.address_space_id,
+            self.base_address.access_size,
+        ) {
+            (AddressSpace::SystemIo, _) => "io",
+            (AddressSpace::SystemMemory, 2) => "mmio16",
+            (AddressSpace::SystemMemory, 3) => "mmio32",
+            (AddressSpace::SystemMemory, _) => "mmio",
+            _ => return None,
+        };
+
+        let (uart, iotype) = match self.interface_type {
+            SerialInterface::Full16550
+            | SerialInterface::Subset16550
+            | SerialInterface::Nvidia16550
+            | SerialInterface::Generic16550
+            | SerialInterface::IntelLpss => ("uart", iotype),
+            SerialInterface::ArmSbsa32Bit => ("pl011", "mmio32"),
+            SerialInterface::ArmPl011
+            | SerialInterface::ArmSbsaGeneric
+            | SerialInterface::Bcm2835 => ("pl011", iotype),
+            _ => return None,
+        };
+
+        let mut options = format!("{},{},0x{:x}", uart, iotype, self.base_address.address);
+        if let Some(baud) = self.baud_rate() {
+            options.push_str(&format!(",{}", baud));
+        }
+        Some(options)
+    }
+}
+
+impl From<RawAcpiData> for SerialPortConsoleRedirection {
+    fn from(mut data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+        let interface_type = SerialInterface::from(data.acpi_table_data.get_u8() as u16);
+        let reserved1 = data.acpi_table_data.split_to(3)[..].try_into().unwrap();
+        let base_address = GenericAddress::from(data.acpi_table_data.split_to(12));
+        let interrupt_type = data.acpi_table_data.get_u8();
+        let irq = data.acpi_table_data.get_u8();
+        let global_system_interrupt = data.acpi_table_data.get_u32_le();
+        let configured_baud_rate = data.acpi_table_data.get_u8();
+        let parity = data.acpi_table_data.get_u8();
+        let stop_bits = data.acpi_table_data.get_u8();
+        let flow_control = data.acpi_table_data.get_u8();
+        let terminal_type = data.acpi_table_data.get_u8();
+        let language = data.acpi_table_data.get_u8();
+        let pci_device_id = data.acpi_table_data.get_u16_le();
+        let pci_vendor_id = data.acpi_table_data.get_u16_le();
+        let pci_bus_number = data.acpi_table_data.get_u8();
+        let pci_device_number = data.acpi_table_data.get_u8();
+        let pci_function_number = data.acpi_table_data.get_u8();
+        let pci_flags = data.acpi_table_data.get_u32_le();
+        let pci_segment = data.acpi_table_data.get_u8();
+        let uart_clock_frequency = data.acpi_table_data.get_u32_le();
+
+        let (precise_baud_rate, namespace_string_length, namespace_string_offset) =
+            if data.acpi_table_data.len() >= 8 {
+                let precise_baud_rate = data.acpi_table_data.get_u32_le();
+                let namespace_string_length = data.acpi_table_data.get_u16_le();
+                let namespace_string_offset = data.acpi_table_data.get_u16_le();
+                (
+                    Some(precise_baud_rate),
+                    Some(namespace_string_length),
+                    Some(namespace_string_offset),
+                )
+            } else {
+                (None, None, None)
+            };
+
+        let namespace_string = match (namespace_string_length, namespace_string_offset) {
+            (Some(len), Some(offset)) => {
+                let skip = (offset as usize).saturating_sub(Self::NAMESPACE_STRING_OFFSET);
+                data.acpi_table_data
+                    .advance(skip.min(data.acpi_table_data.len()));
+                let len = (len as usize).min(data.acpi_table_data.len());
+                Some(extract_cstring(&data.acpi_table_data.split_to(len)))
+            }
+            _ => None,
+        };
+
+        SerialPortConsoleRedirection {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            interface_type,
+            reserved1,
+            base_address,
+            interrupt_type,
+            irq,
+            global_system_interrupt,
+            configured_baud_rate,
+            parity,
+            stop_bits,
+            flow_control,
+            terminal_type,
+            language,
+            pci_device_id,
+            pci_vendor_id,
+            pci_bus_number,
+            pci_device_number,
+            pci_function_number,
+            pci_flags,
+            pci_segment,
+            uart_clock_frequency,
+            precise_baud_rate,
+            namespace_string_length,
+            namespace_string_offset,
+            namespace_string,
+        }
+    }
+}
+
+impl From<SerialPortConsoleRedirection> for Bytes {
+    fn from(val: SerialPortConsoleRedirection) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        b.put_u8(u16::from(val.interface_type) as u8);
+        b.put_slice(&val.reserved1);
+        b.put(Bytes::from(val.base_address));
+        b.put_u8(val.interrupt_type);
+        b.put_u8(val.irq);
+        b.put_u32_le(val.global_system_interrupt);
+        b.put_u8(val.configured_baud_rate);
+        b.put_u8(val.parity);
+        b.put_u8(val.stop_bits);
+        b.put_u8(val.flow_control);
+        b.put_u8(val.terminal_type);
+        b.put_u8(val.language);
+        b.put_u16_le(val.pci_device_id);
+        b.put_u16_le(val.pci_vendor_id);
+        b.put_u8(val.pci_bus_number);
+        b.put_u8(val.pci_device_number);
+        b.put_u8(val.pci_function_number);
+        b.put_u32_le(val.pci_flags);
+        b.put_u8(val.pci_segment);
+        b.put_u32_le(val.uart_clock_frequency);
+        if let Some(v) = val.precise_baud_rate {
+            b.put_u32_le(v);
+        }
+        if let Some(v) = val.namespace_string_length {
+            b.put_u16_le(v);
+        }
+        if let Some(v) = val.namespace_string_offset {
+            b.put_u16_le(v);
+            if b.len() < v as usize {
+                b.resize(v as usize, 0);
+            }
+        }
+        if let Some(v) = val.namespace_string {
+            b.put_slice(v.as_bytes());
+            b.put_u8(0);
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for SerialPortConsoleRedirection {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Serial Port Console Redirection Table");
+        w.annotated(
+            "Interface Type",
+            1,
+            u16::from(self.interface_type) as u64,
+            &format!("{:?}", self.interface_type),
+        );
+        w.bytes("Reserved", &self.reserved1);
+        w.blank();
+        w.gas("Serial Port Register", &self.base_address);
+        w.blank();
+        w.u8("Interrupt Type", self.interrupt_type);
+        w.flag("PC-AT", self.pc_at_interrupt() as u8);
+        w.flag("I/O APIC", self.io_apic_interrupt() as u8);
+        w.flag("I/O SAPIC", self.io_sapic_interrupt() as u8);
+        w.flag("GIC", self.gic_interrupt() as u8);
+        w.flag("PLIC", self.plic_interrupt() as u8);
+        w.u8("PCAT-compatible IRQ", self.irq);
+        w.u32("Interrupt", self.global_system_interrupt);
+        w.u8("Baud Rate", self.configured_baud_rate);
+        w.u8("Parity", self.parity);
+        w.u8("Stop Bits", self.stop_bits);
+        w.u8("Flow Control", self.flow_control);
+        w.flag("DCD", self.dcd_flow_control() as u8);
+        w.flag("RTS/CTS", self.rts_cts_flow_control() as u8);
+        w.flag("XON/XOFF", self.xon_xoff_flow_control() as u8);
+        w.u8("Terminal Type", self.terminal_type);
+        w.u8("Language", self.language);
+        w.u16("PCI Device ID", self.pci_device_id);
+        w.u16("PCI Vendor ID", self.pci_vendor_id);
+        w.u8("PCI Bus", self.pci_bus_number);
+        w.u8("PCI Device", self.pci_device_number);
+        w.u8("PCI Function", self.pci_function_number);
+        w.u32("PCI Flags", self.pci_flags);
+        w.u8("PCI Segment", self.pci_segment);
+        w.u32("Uart Clock Freq", self.uart_clock_frequency);
+        if let Some(v) = self.precise_baud_rate {
+            w.u32("Precise Baud rate", v);
+        }
+        if let Some(v) = self.namespace_string_length {
+            w.u16("NameSpaceStringLength", v);
+        }
+        if let Some(v) = self.namespace_string_offset {
+            w.u16("NameSpaceStringOffset", v);
+            w.seek(w.offset().max(v as usize));
+        }
+        if let Some(v) = &self.namespace_string {
+            w.string("NamespaceString", v.len() + 1, v);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SerialInterface {
+    #[default]
+    Full16550,
+    Subset16550,
+    Max311xE,
+    ArmPl011,
+    Msm8x60,
+    Nvidia16550,
+    TiOmap,
+    Apm88xxxx,
+    Msm8974,
+    Sam5250,
+    IntelUsif,
+    Imx6,
+    ArmSbsa32Bit,
+    ArmSbsaGeneric,
+    ArmDcc,
+    Bcm2835,
+    Sdm845_18432,
+    Generic16550,
+    Sdm845_7372,
+    IntelLpss,
+    RiscVSbi,
+    Reserved(u16),
+}
+
+impl From<u16> for SerialInterface {
+    fn from(value: u16) -> Self {
+        match value {
+            0x0000 => SerialInterface::Full16550,
+            0x0001 => SerialInterface::Subset16550,
+            0x0002 => SerialInterface::Max311xE,
+            0x0003 => SerialInterface::ArmPl011,
+            0x0004 => SerialInterface::Msm8x60,
+            0x0005 => SerialInterface::Nvidia16550,
+            0x0006 => SerialInterface::TiOmap,
+            0x0008 => SerialInterface::Apm88xxxx,
+            0x0009 => SerialInterface::Msm8974,
+            0x000a => SerialInterface::Sam5250,
+            0x000b => SerialInterface::IntelUsif,
+            0x000c => SerialInterface::Imx6,
+            0x000d => SerialInterface::ArmSbsa32Bit,
+            0x000e => SerialInterface::ArmSbsaGeneric,
+            0x000f => SerialInterface::ArmDcc,
+            0x0010 => SerialInterface::Bcm2835,
+            0x0011 => SerialInterface::Sdm845_18432,
+            0x0012 => SerialInterface::Generic16550,
+            0x0013 => SerialInterface::Sdm845_7372,
+            0x0014 => SerialInterface::IntelLpss,
+            0x0015 => SerialInterface::RiscVSbi,
+            v => SerialInterface::Reserved(v),
+        }
+    }
+}
+
+impl From<SerialInterface> for u16 {
+    fn from(val: SerialInterface) -> Self {
+        match val {
+            SerialInterface::Full16550 => 0x0000,
+            SerialInterface::Subset16550 => 0x0001,
+            SerialInterface::Max311xE => 0x0002,
+            SerialInterface::ArmPl011 => 0x0003,
+            SerialInterface::Msm8x60 => 0x0004,
+            SerialInterface::Nvidia16550 => 0x0005,
+            SerialInterface::TiOmap => 0x0006,
+            SerialInterface::Apm88xxxx => 0x0008,
+            SerialInterface::Msm8974 => 0x0009,
+            SerialInterface::Sam5250 => 0x000a,
+            SerialInterface::IntelUsif => 0x000b,
+            SerialInterface::Imx6 => 0x000c,
+            SerialInterface::ArmSbsa32Bit => 0x000d,
+            SerialInterface::ArmSbsaGeneric => 0x000e,
+            SerialInterface::ArmDcc => 0x000f,
+            SerialInterface::Bcm2835 => 0x0010,
+            SerialInterface::Sdm845_18432 => 0x0011,
+            SerialInterface::Generic16550 => 0x0012,
+            SerialInterface::Sdm845_7372 => 0x0013,
+            SerialInterface::IntelLpss => 0x0014,
+            SerialInterface::RiscVSbi => 0x0015,
+            SerialInterface::Reserved(v) => v,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DebugPort2 {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub device_info_offset: u32,
+    pub device_info_count: u32,
+    pub devices: Vec<DebugDeviceInformation>,
+}
+
+impl From<RawAcpiData> for DebugPort2 {
+    fn from(mut data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+        let device_info_offset = data.acpi_table_data.get_u32_le();
+        let device_info_count = data.acpi_table_data.get_u32_le();
+
+        let skip = (device_info_offset as usize).saturating_sub(44);
+        data.acpi_table_data
+            .advance(skip.min(data.acpi_table_data.len()));
+
+        let mut devices = vec![];
+        for _ in 0..device_info_count {
+            if data.acpi_table_data.len() < 3 {
+                break;
+            }
+
+            let len = u16::from_le_bytes([data.acpi_table_data[1], data.acpi_table_data[2]]);
+            let len = (len as usize).min(data.acpi_table_data.len());
+            if len < DebugDeviceInformation::HEADER_LENGTH {
+                break;
+            }
+
+            let device = data.acpi_table_data.split_to(len);
+            devices.push(DebugDeviceInformation::from(device));
+        }
+
+        DebugPort2 {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            device_info_offset,
+            device_info_count,
+            devices,
+        }
+    }
+}
+
+impl From<DebugPort2> for Bytes {
+    fn from(val: DebugPort2) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        b.put_u32_le(val.device_info_offset);
+        b.put_u32_le(val.device_info_count);
+        if b.len() < val.device_info_offset as usize {
+            b.resize(val.device_info_offset as usize, 0);
+        }
+        for device in val.devices {
+            b.put(Bytes::from(device));
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for DebugPort2 {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Debug Port Table 2");
+        w.u32("Info Offset", self.device_info_offset);
+        w.u32("Info Count", self.device_info_count);
+        w.seek(w.offset().max(self.device_info_offset as usize));
+        for device in &self.devices {
+            w.blank();
+            device.disassemble(w);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DebugPortType {
+    #[default]
+    Serial,
+    Ieee1394,
+    Usb,
+    Net,
+    Reserved(u16),
+}
+
+impl From<u16> for DebugPortType {
+    fn from(value: u16) -> Self {
+        match value {
+            0x8000 => DebugPortType::Serial,
+            0x8001 => DebugPortType::Ieee1394,
+            0x8002 => DebugPortType::Usb,
+            0x8003 => DebugPortType::Net,
+            v => DebugPortType::Reserved(v),
+        }
+    }
+}
+
+impl From<DebugPortType> for u16 {
+    fn from(val: DebugPortType) -> Self {
+        match val {
+            DebugPortType::Serial => 0x8000,
+            DebugPortType::Ieee1394 => 0x8001,
+            DebugPortType::Usb => 0x8002,
+            DebugPortType::Net => 0x8003,
+            DebugPortType::Reserved(v) => v,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DebugDeviceInformation {
+    pub revision: u8,
+    pub length: u16,
+    pub generic_address_count: u8,
+    pub namespace_string_length: u16,
+    pub namespace_string_offset: u16,
+    pub oem_data_length: u16,
+    pub oem_data_offset: u16,
+    pub port_type: DebugPortType,
+    pub port_subtype: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 2],
+    pub base_address_register_offset: u16,
+    pub address_size_offset: u16,
+    pub base_address_registers: Vec<GenericAddress>,
+    pub address_sizes: Vec<u32>,
+    pub namespace_string: String,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub oem_data: Bytes,
+}
+
+impl DebugDeviceInformation {
+    const HEADER_LENGTH: usize = 22;
+
+    pub fn serial_interface(&self) -> Option<SerialInterface> {
+        match self.port_type {
+            DebugPortType::Serial => Some(SerialInterface::from(self.port_subtype)),
+            _ => None,
+        }
+    }
+}
+
+impl From<Bytes> for DebugDeviceInformation {
+    fn from(mut buf: Bytes) -> Self {
+        let device = buf.clone();
+        let revision = buf.get_u8();
+        let length = buf.get_u16_le();
+        let generic_address_count = buf.get_u8();
+        let namespace_string_length = buf.get_u16_le();
+        let namespace_string_offset = buf.get_u16_le();
+        let oem_data_length = buf.get_u16_le();
+        let oem_data_offset = buf.get_u16_le();
+        let port_type = DebugPortType::from(buf.get_u16_le());
+        let port_subtype = buf.get_u16_le();
+        let reserved = buf.split_to(2)[..].try_into().unwrap();
+        let base_address_register_offset = buf.get_u16_le();
+        let address_size_offset = buf.get_u16_le();
+
+        let field = |offset: u16, len: usize| {
+            let start = (offset as usize).min(device.len());
+            let end = (start + len).min(device.len());
+            device.slice(start..end)
+        };
+
+        let count = generic_address_count as usize;
+        let base_address_registers = field(base_address_register_offset, 12 * count)
+            .chunks_exact(12)
+            .map(|d| GenericAddress::from(Bytes::copy_from_slice(d)))
+            .collect();
+        let address_sizes = field(address_size_offset, 4 * count)
+            .chunks_exact(4)
+            .map(|d| u32::from_le_bytes(d.try_into().unwrap()))
+            .collect();
+        let namespace = field(namespace_string_offset, namespace_string_length as usize);
+        let namespace_string = extract_cstring(&namespace);
+        let oem_data = if oem_data_offset != 0 {
+            field(oem_data_offset, oem_data_length as usize)
+        } else {
+            Bytes::new()
+        };
+
+        DebugDeviceInformation {
+            revision,
+            length,
+            generic_address_count,
+            namespace_string_length,
+            namespace_string_offset,
+            oem_data_length,
+            oem_data_offset,
+            port_type,
+            port_subtype,
+            reserved,
+            base_address_register_offset,
+            address_size_offset,
+            base_address_registers,
+            address_sizes,
+            namespace_string,
+            oem_data,
+        }
+    }
+}
+
+impl From<DebugDeviceInformation> for Bytes {
+    fn from(val: DebugDeviceInformation) -> Self {
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_u8(val.revision);
+        b.put_u16_le(val.length);
+        b.put_u8(val.generic_address_count);
+        b.put_u16_le(val.namespace_string_length);
+        b.put_u16_le(val.namespace_string_offset);
+        b.put_u16_le(val.oem_data_length);
+        b.put_u16_le(val.oem_data_offset);
+        b.put_u16_le(val.port_type.into());
+        b.put_u16_le(val.port_subtype);
+        b.put_slice(&val.reserved);
+        b.put_u16_le(val.base_address_register_offset);
+        b.put_u16_le(val.address_size_offset);
+        b.resize(val.length as usize, 0);
+
+        let mut registers = BytesMut::new();
+        for register in val.base_address_registers {
+            registers.put(Bytes::from(register));
+        }
+        put_at(
+            &mut b,
+            val.base_address_register_offset as usize,
+            &registers,
+        );
+
+        let mut sizes = BytesMut::new();
+        for size in val.address_sizes {
+            sizes.put_u32_le(size);
+        }
+        put_at(&mut b, val.address_size_offset as usize, &sizes);
+
+        put_at(
+            &mut b,
+            val.namespace_string_offset as usize,
+            val.namespace_string.as_bytes(),
+        );
+
+        if val.oem_data_offset != 0 {
+            put_at(&mut b, val.oem_data_offset as usize, &val.oem_data);
+        }
+
+        b.freeze()
+    }
+}
+
+impl Disassemble for DebugDeviceInformation {
+    fn disassemble(&self, w: &mut Disassembler) {
+        let start = w.offset();
+        w.u8("Revision", self.revision);
+        w.u16("Length", self.length);
+        w.u8("Register Count", self.generic_address_count);
+        w.u16("Names Path Length", self.namespace_string_length);
+        w.u16("Names Path Offset", self.namespace_string_offset);
+        w.u16("OEM Data Length", self.oem_data_length);
+        w.u16("OEM Data Offset", self.oem_data_offset);
+        w.annotated(
+            "Port Type",
+            2,
+            u16::from(self.port_type) as u64,
+            &format!("{:?}", self.port_type),
+        );
+        w.u16("Port Subtype", self.port_subtype);
+        w.bytes("Reserved", &self.reserved);
+        w.u16("Base Address Offset", self.base_address_register_offset);
+        w.u16("Address Size Offset", self.address_size_offset);
+
+        w.seek(start + self.base_address_register_offset as usize);
+        for register in &self.base_address_registers {
+            w.gas("Base Address Register", register);
+        }
+        w.seek(start + self.address_size_offset as usize);
+        for size in &self.address_sizes {
+            w.u32("Address Size", *size);
+        }
+        w.seek(start + self.namespace_string_offset as usize);
+        w.string(
+            "Namepath",
+            self.namespace_string_length as usize,
+            &self.namespace_string,
+        );
+        if self.oem_data_offset != 0 {
+            w.seek(start + self.oem_data_offset as usize);
+            w.bytes("OEM Data", &self.oem_data);
+        }
+        w.seek(start + self.length as usize);
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serial_port_console_redirection() {
+        let data = SerialPortConsoleRedirection {
+            signature: "SPCR".to_string(),
+            length: 90,
+            revision: 4,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            interface_type: SerialInterface::ArmPl011,
+            reserved1: [0; 3],
+            base_address: GenericAddress {
+                address_space_id: AddressSpace::SystemMemory,
+                register_bit_width: 32,
+                register_bit_offset: 0,
+                access_size: 3,
+                address: 0x9000000,
+            },
+            interrupt_type: 0x08,
+            irq: 0,
+            global_system_interrupt: 33,
+            configured_baud_rate: 7,
+            parity: 0,
+            stop_bits: 1,
+            flow_control: 0,
+            terminal_type: 3,
+            language: 0,
+            pci_device_id: 0xffff,
+            pci_vendor_id: 0xffff,
+            pci_bus_number: 0,
+            pci_device_number: 0,
+            pci_function_number: 0,
+            pci_flags: 1,
+            pci_segment: 0,
+            uart_clock_frequency: 24000000,
+            precise_baud_rate: Some(0),
+            namespace_string_length: Some(2),
+            namespace_string_offset: Some(88),
+            namespace_string: Some(".".to_string()),
+        };
+        let b = Bytes::from(data.clone());
+        let raw = RawAcpiData::from(b);
+        let ret = SerialPortConsoleRedirection::from(raw);
+        assert_eq!(data, ret);
+        assert!(ret.gic_interrupt());
+        assert!(!ret.is_pci_device());
+        assert_eq!(
+            ret.console_options(),
+            Some("pl011,mmio32,0x9000000,115200".to_string())
+        );
+    }
+
+    #[test]
+    fn debug_port2() {
+        let data = DebugPort2 {
+            signature: "DBG2".to_string(),
+            length: 44 + 56,
+            revision: 0,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            device_info_offset: 44,
+            device_info_count: 1,
+            devices: vec![DebugDeviceInformation {
+                revision: 0,
+                length: 56,
+                generic_address_count: 1,
+                namespace_string_length: 18,
+                namespace_string_offset: 38,
+                oem_data_length: 0,
+                oem_data_offset: 0,
+                port_type: DebugPortType::Serial,
+                port_subtype: 0x000e,
+                reserved: [0; 2],
+                base_address_register_offset: 22,
+                address_size_offset: 34,
+                base_address_registers: vec![GenericAddress {
+                    address_space_id: AddressSpace::SystemMemory,
+                    register_bit_width: 32,
+                    register_bit_offset: 0,
+                    access_size: 3,
+                    address: 0x9000000,
+                }],
+                address_sizes: vec![0x1000],
+                namespace_string: "\\_SB.COM0".to_string(),
+                oem_data: Bytes::new(),
+            }],
+        };
+        let b = Bytes::from(data.clone());
+        let raw = RawAcpiData::from(b);
+        let ret = DebugPort2::from(raw);
+        assert_eq!(data, ret);
+        assert_eq!(
+            ret.devices[0].serial_interface(),
+            Some(SerialInterface::ArmSbsaGeneric)
+        );
+    }
+}
@@ -0,0 +1,754 @@
+use super::disasm::{DisassembledField, Disassembler};
+use super::error::Error;
+use super::memory::PhysicalMemoryReader;
+use super::rsdp::{RootSystemDescriptionPointer, read_table};
+use super::{AcpiTable, RawAcpiData};
+#[cfg(feature = "std")]
+use super::{get_raw_table, table_types};
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use core::fmt;
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::fs;
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Archive layout, all integers little endian:
+//   "ACPISNAP", u16 version, metadata, u32 table count, tables
+// Strings are stored as a u16 length followed by UTF-8 bytes.
+
+const MAGIC: &[u8; 8] = b"ACPISNAP";
+const VERSION: u16 = 1;
+const HEADER_LENGTH: usize = 36;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableSnapshot {
+    pub metadata: SnapshotMetadata,
+    pub tables: Vec<SnapshotTable>,
+}
+
+#[cfg(feature = "std")]
+impl TableSnapshot {
+    pub fn capture() -> Result<Self, Error> {
+        let mut tables: Vec<SnapshotTable> = vec![];
+        for name in table_types()? {
+            let raw = get_raw_table(&name)?;
+            let seen = tables
+                .iter()
+                .filter(|t| t.signature == raw.signature)
+                .count();
+            // The OS numbers tables that appear more than once, e.g. `SSDT2`.
+            let instance = name
+                .get(raw.signature.len()..)
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(seen as u32 + 1);
+            tables.push(SnapshotTable {
+                signature: raw.signature.clone(),
+                instance,
+                address: None,
+                data: Bytes::from(raw),
+            });
+        }
+
+        Ok(TableSnapshot {
+            metadata: SnapshotMetadata::capture(),
+            tables,
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let data = fs::read(path)?;
+        TableSnapshot::try_from(Bytes::from(data))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        fs::write(path, Bytes::from(self.clone()))?;
+        Ok(())
+    }
+}
+
+impl TableSnapshot {
+    pub fn table(&self, signature: &str, instance: u32) -> Option<&SnapshotTable> {
+        self.tables
+            .iter()
+            .find(|t| t.signature == signature && t.instance == instance)
+    }
+
+    // Walks the root table the RSDP points at, for environments without an OS to ask. The DSDT is
+    // only listed in the FADT, so it is followed from there; the FACS has no standard header.
+    // Entries that do not lead to a valid header are skipped so the linter can report them.
+    pub fn read_from<M>(memory: &mut M, rsdp_address: u64) -> Result<Self, Error>
+    where
+        M: PhysicalMemoryReader,
+    {
+        let rsdp = RootSystemDescriptionPointer::read_from(memory, rsdp_address)?;
+        let mut addresses = vec![rsdp.root_table_address()];
+        addresses.extend(rsdp.table_addresses(memory)?);
+
+        let mut snapshot = TableSnapshot::default();
+        let mut index = 0;
+        while index < addresses.len() {
+            let address = addresses[index];
+            index += 1;
+            if address == 0 {
+                continue;
+            }
+
+            let raw = match read_table(memory, address) {
+                Err(Error::InvalidTable | Error::OutOfRange) => continue,
+                ret => ret?,
+            };
+            if raw.signature == "FACP" {
+                if let Some(dsdt) = dsdt_address(&raw.acpi_table_data) {
+                    addresses.push(dsdt);
+                }
+            }
+
+            let instance = snapshot
+                .tables
+                .iter()
+                .filter(|t| t.signature == raw.signature)
+                .count() as u32
+                + 1;
+            snapshot.tables.push(SnapshotTable {
+                signature: raw.signature.clone(),
+                instance,
+                address: Some(address),
+                data: Bytes::from(raw),
+            });
+        }
+
+        Ok(snapshot)
+    }
+
+    // Lists what changed from `self` to `newer`; identical tables are left out.
+    pub fn diff(&self, newer: &TableSnapshot) -> Vec<TableDiff> {
+        let mut diffs = vec![];
+        for old in &self.tables {
+            match newer.table(&old.signature, old.instance) {
+                Some(new) if new.data != old.data => {
+                    diffs.push(TableDiff::Changed(old.compare(new)));
+                }
+                Some(_) => {}
+                None => diffs.push(TableDiff::Removed {
+                    signature: old.signature.clone(),
+                    instance: old.instance,
+                }),
+            }
+        }
+
+        for new in &newer.tables {
+            if self.table(&new.signature, new.instance).is_none() {
+                diffs.push(TableDiff::Added {
+                    signature: new.signature.clone(),
+                    instance: new.instance,
+                });
+            }
+        }
+
+        diffs
+    }
+}
+
+impl TryFrom<Bytes> for TableSnapshot {
+    type Error = Error;
+
+    fn try_from(mut buf: Bytes) -> Result<Self, Self::Error> {
+        if take(&mut buf, MAGIC.len())?[..] != MAGIC[..] {
+            return Err(Error::InvalidSnapshot);
+        }
+
+        if take(&mut buf, 2)?.get_u16_le() != VERSION {
+            return Err(Error::InvalidSnapshot);
+        }
+
+        let metadata = SnapshotMetadata {
+            hostname: take_string(&mut buf)?,
+            os: take_string(&mut buf)?,
+            arch: take_string(&mut buf)?,
+            captured_at: take(&mut buf, 8)?.get_u64_le(),
+            firmware_version: take_string(&mut buf)?,
+        };
+
+        let count = take(&mut buf, 4)?.get_u32_le();
+        let mut tables = vec![];
+        for _ in 0..count {
+            let signature = String::from_utf8_lossy(&take(&mut buf, 4)?).to_string();
+            let instance = take(&mut buf, 4)?.get_u32_le();
+            let mut address = take(&mut buf, 9)?;
+            let address = match address.get_u8() {
+                0 => None,
+                _ => Some(address.get_u64_le()),
+            };
+            let length = take(&mut buf, 4)?.get_u32_le();
+            let data = take(&mut buf, length as usize)?;
+            if data.len() < HEADER_LENGTH {
+                return Err(Error::InvalidSnapshot);
+            }
+
+            tables.push(SnapshotTable {
+                signature,
+                instance,
+                address,
+                data,
+            });
+        }
+
+        Ok(TableSnapshot { metadata, tables })
+    }
+}
+
+impl From<TableSnapshot> for Bytes {
+    fn from(val: TableSnapshot) -> Self {
+        let mut b = BytesMut::new();
+        b.put_slice(MAGIC);
+        b.put_u16_le(VERSION);
+        put_string(&mut b, &val.metadata.hostname);
+        put_string(&mut b, &val.metadata.os);
+        put_string(&mut b, &val.metadata.arch);
+        b.put_u64_le(val.metadata.captured_at);
+        put_string(&mut b, &val.metadata.firmware_version);
+        b.put_u32_le(val.tables.len() as u32);
+        for table in val.tables {
+            b.put_slice(&super::string_to_array::<4>(&table.signature));
+            b.put_u32_le(table.instance);
+            b.put_u8(table.address.is_some() as u8);
+            b.put_u64_le(table.address.unwrap_or_default());
+            b.put_u32_le(table.data.len() as u32);
+            b.put(table.data);
+        }
+        b.freeze()
+    }
+}
+
+fn take(buf: &mut Bytes, length: usize) -> Result<Bytes, Error> {
+    if buf.len() < length {
+        return Err(Error::InvalidSnapshot);
+    }
+
+    Ok(buf.split_to(length))
+}
+
+fn take_string(buf: &mut Bytes) -> Result<String, Error> {
+    let length = take(buf, 2)?.get_u16_le();
+    let value = take(buf, length as usize)?;
+    Ok(String::from_utf8_lossy(&value).to_string())
+}
+
+fn put_string(b: &mut BytesMut, value: &str) {
+    let value = &value.as_bytes()[..value.len().min(u16::MAX as usize)];
+    b.put_u16_le(value.len() as u16);
+    b.put_slice(value);
+}
+
+// X_DSDT wins over DSDT when both are set; offsets are past the table header.
+fn dsdt_address(fadt: &[u8]) -> Option<u64> {
+    let x_dsdt = fadt
+        .get(104..112)
+        .map(|v| u64::from_le_bytes(v.try_into().unwrap()))
+        .unwrap_or_default();
+    let dsdt = fadt
+        .get(4..8)
+        .map(|v| u32::from_le_bytes(v.try_into().unwrap()) as u64)
+        .unwrap_or_default();
+
+    [x_dsdt, dsdt].into_iter().find(|a| *a != 0)
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotMetadata {
+    pub hostname: String,
+    pub os: String,
+    pub arch: String,
+    // Seconds since the Unix epoch.
+    pub captured_at: u64,
+    pub firmware_version: String,
+}
+
+#[cfg(feature = "std")]
+impl SnapshotMetadata {
+    pub fn capture() -> Self {
+        let captured_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        SnapshotMetadata {
+            hostname: super::get_hostname(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            captured_at,
+            firmware_version: super::get_firmware_version(),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotTable {
+    pub signature: String,
+    // Starts at 1, counting tables with the same signature.
+    pub instance: u32,
+    pub address: Option<u64>,
+    // The whole table, header included.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub data: Bytes,
+}
+
+impl SnapshotTable {
+    pub fn raw(&self) -> RawAcpiData {
+        RawAcpiData::from(self.data.clone())
+    }
+
+    fn compare(&self, new: &SnapshotTable) -> TableChanges {
+        let old_table = AcpiTable::from(self.raw());
+        let new_table = AcpiTable::from(new.raw());
+        let old_fields = fields(&old_table);
+        let new_fields = fields(&new_table);
+
+        // The checksum changes along with any other byte, so it is not worth reporting.
+        let (old_header, old_body) = split_header(old_fields);
+        let (new_header, new_body) = split_header(new_fields);
+        let header = compare_fields(&old_header, &new_header);
+
+        let (fields, ranges) = if old_table.is_typed() && new_table.is_typed() {
+            (compare_fields(&old_body, &new_body), vec![])
+        } else {
+            (vec![], compare_bytes(&self.data, &new.data))
+        };
+
+        TableChanges {
+            signature: self.signature.clone(),
+            instance: self.instance,
+            header,
+            fields,
+            ranges,
+        }
+    }
+}
+
+fn fields(table: &AcpiTable) -> Vec<DisassembledField> {
+    let mut w = Disassembler::default();
+    super::Disassemble::disassemble(table, &mut w);
+    w.fields().to_vec()
+}
+
+fn split_header(
+    fields: Vec<DisassembledField>,
+) -> (Vec<DisassembledField>, Vec<DisassembledField>) {
+    let (header, body) = fields
+        .into_iter()
+        .partition::<Vec<DisassembledField>, _>(|f| f.offset < HEADER_LENGTH);
+    let header = header
+        .into_iter()
+        .filter(|f| f.name != "Checksum")
+        .collect();
+    (header, body)
+}
+
+// Fields are matched by name and by how many fields of that name came before, so a changed
+// count of subtables shows up as added or removed fields at the end.
+fn compare_fields(old: &[DisassembledField], new: &[DisassembledField]) -> Vec<FieldChange> {
+    let keyed = |fields: &[DisassembledField]| {
+        let mut seen = BTreeMap::new();
+        fields
+            .iter()
+            .map(|f| {
+                let n = seen.entry(f.name.clone()).or_insert(0usize);
+                *n += 1;
+                ((f.name.clone(), *n), f.clone())
+            })
+            .collect::<Vec<((String, usize), DisassembledField)>>()
+    };
+    let old = keyed(old);
+    let new = keyed(new);
+    let new_map = new.iter().cloned().collect::<BTreeMap<_, _>>();
+    let old_map = old.iter().cloned().collect::<BTreeMap<_, _>>();
+
+    let mut changes = vec![];
+    for (key, o) in &old {
+        match new_map.get(key) {
+            Some(n) if n.value == o.value => {}
+            Some(n) => changes.push(FieldChange {
+                offset: n.offset,
+                name: o.name.clone(),
+                old: Some(o.value.clone()),
+                new: Some(n.value.clone()),
+            }),
+            None => changes.push(FieldChange {
+                offset: o.offset,
+                name: o.name.clone(),
+                old: Some(o.value.clone()),
+                new: None,
+            }),
+        }
+    }
+
+    for (key, n) in &new {
+        if !old_map.contains_key(key) {
+            changes.push(FieldChange {
+                offset: n.offset,
+                name: n.name.clone(),
+                old: None,
+                new: Some(n.value.clone()),
+            });
+        }
+    }
+
+    changes
+}
+
+// Byte ranges after the header that differ; bytes past the end of the shorter table count.
+fn compare_bytes(old: &[u8], new: &[u8]) -> Vec<Range<usize>> {
+    let length = old.len().max(new.len());
+    let mut ranges: Vec<Range<usize>> = vec![];
+    for i in HEADER_LENGTH..length {
+        if old.get(i) == new.get(i) {
+            continue;
+        }
+
+        match ranges.last_mut() {
+            Some(r) if r.end == i => r.end = i + 1,
+            _ => ranges.push(i..i + 1),
+        }
+    }
+    ranges
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TableDiff {
+    Added { signature: String, instance: u32 },
+    Removed { signature: String, instance: u32 },
+    Changed(TableChanges),
+}
+
+impl fmt::Display for TableDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableDiff::Added {
+                signature,
+                instance,
+            } => writeln!(f, "+ {} {}", signature, instance),
+            TableDiff::Removed {
+                signature,
+                instance,
+            } => writeln!(f, "- {} {}", signature, instance),
+            TableDiff::Changed(v) => {
+                writeln!(f, "~ {} {}", v.signature, v.instance)?;
+                for change in v.header.iter().chain(&v.fields) {
+                    writeln!(f, "    {}", change)?;
+                }
+                for range in &v.ranges {
+                    writeln!(
+                        f,
+                        "    [{:03X}h-{:03X}h] bytes differ",
+                        range.start,
+                        range.end - 1
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableChanges {
+    pub signature: String,
+    pub instance: u32,
+    pub header: Vec<FieldChange>,
+    // Only for tables with a typed parser.
+    pub fields: Vec<FieldChange>,
+    // Only for opaque tables.
+    pub ranges: Vec<Range<usize>>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldChange {
+    pub offset: usize,
+    pub name: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let old = self.old.as_deref().unwrap_or("(none)");
+        let new = self.new.as_deref().unwrap_or("(none)");
+        write!(
+            f,
+            "[{:03X}h] {} : {} -> {}",
+            self.offset, self.name, old, new
+        )
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemoryMappedConfiguration, MemoryMappedConfigurationSpace};
+
+    #[test]
+    fn table_snapshot() {
+        let data = TableSnapshot {
+            metadata: SnapshotMetadata {
+                hostname: "host".to_string(),
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                captured_at: 1,
+                firmware_version: "1.0".to_string(),
+            },
+            tables: vec![
+                SnapshotTable {
+                    signature: "MCFG".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(MemoryMappedConfiguration {
+                        signature: "MCFG".to_string(),
+                        length: 60,
+                        revision: 1,
+                        oem_id: "OEM".to_string(),
+                        oem_table_id: "TABLE".to_string(),
+                        oem_revision: 1,
+                        spaces: vec![MemoryMappedConfigurationSpace {
+                            base_address: 0xe000_0000,
+                            segment_number: 0,
+                            bus_number_start: 0,
+                            bus_number_end: 0xff,
+                            reserved: [0; 4],
+                        }],
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "SSDT".to_string(),
+                    instance: 2,
+                    address: Some(0x7fff_0000),
+                    data: Bytes::from(RawAcpiData {
+                        signature: "SSDT".to_string(),
+                        length: 39,
+                        acpi_table_data: Bytes::from_static(&[1, 2, 3]),
+                        ..Default::default()
+                    }),
+                },
+            ],
+        };
+        let b = Bytes::from(data.clone());
+        let ret = TableSnapshot::try_from(b.clone()).unwrap();
+        assert_eq!(data, ret);
+
+        assert!(matches!(
+            TableSnapshot::try_from(b.slice(..b.len() - 1)),
+            Err(Error::InvalidSnapshot)
+        ));
+        assert!(matches!(
+            TableSnapshot::try_from(b.slice(1..)),
+            Err(Error::InvalidSnapshot)
+        ));
+    }
+
+    #[test]
+    fn table_snapshot_diff() {
+        let old = TableSnapshot {
+            tables: vec![
+                SnapshotTable {
+                    signature: "MCFG".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(MemoryMappedConfiguration {
+                        signature: "MCFG".to_string(),
+                        length: 60,
+                        revision: 1,
+                        oem_id: "OEM".to_string(),
+                        oem_table_id: "TABLE".to_string(),
+                        oem_revision: 1,
+                        spaces: vec![MemoryMappedConfigurationSpace {
+                            base_address: 0xe000_0000,
+                            segment_number: 0,
+                            bus_number_start: 0,
+                            bus_number_end: 0xff,
+                            reserved: [0; 4],
+                        }],
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "SSDT".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "SSDT".to_string(),
+                        length: 42,
+                        acpi_table_data: Bytes::from_static(&[0, 1, 2, 3, 4, 5]),
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "SSDT".to_string(),
+                    instance: 2,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "SSDT".to_string(),
+                        length: 36,
+                        acpi_table_data: Bytes::from_static(&[]),
+                        ..Default::default()
+                    }),
+                },
+                // Too short for the WSMT parser, so it is compared byte by byte.
+                SnapshotTable {
+                    signature: "WSMT".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "WSMT".to_string(),
+                        length: 36,
+                        acpi_table_data: Bytes::from_static(&[]),
+                        ..Default::default()
+                    }),
+                },
+            ],
+            ..Default::default()
+        };
+        let new = TableSnapshot {
+            tables: vec![
+                SnapshotTable {
+                    signature: "MCFG".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(MemoryMappedConfiguration {
+                        signature: "MCFG".to_string(),
+                        length: 76,
+                        revision: 1,
+                        oem_id: "OEM".to_string(),
+                        oem_table_id: "TABLE".to_string(),
+                        oem_revision: 2,
+                        spaces: vec![
+                            MemoryMappedConfigurationSpace {
+                                base_address: 0xf000_0000,
+                                segment_number: 0,
+                                bus_number_start: 0,
+                                bus_number_end: 0xff,
+                                reserved: [0; 4],
+                            },
+                            MemoryMappedConfigurationSpace {
+                                base_address: 0xc000_0000,
+                                segment_number: 1,
+                                bus_number_start: 0,
+                                bus_number_end: 0xff,
+                                reserved: [0; 4],
+                            },
+                        ],
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "SSDT".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "SSDT".to_string(),
+                        length: 43,
+                        acpi_table_data: Bytes::from_static(&[0, 9, 9, 3, 4, 5, 6]),
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "HPET".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "HPET".to_string(),
+                        length: 36,
+                        acpi_table_data: Bytes::from_static(&[]),
+                        ..Default::default()
+                    }),
+                },
+                SnapshotTable {
+                    signature: "WSMT".to_string(),
+                    instance: 1,
+                    address: None,
+                    data: Bytes::from(RawAcpiData {
+                        signature: "WSMT".to_string(),
+                        length: 40,
+                        acpi_table_data: Bytes::from_static(&[7, 0, 0, 0]),
+                        ..Default::default()
+                    }),
+                },
+            ],
+            ..Default::default()
+        };
+
+        let diffs = old.diff(&new);
+        assert_eq!(diffs.len(), 5);
+        let TableDiff::Changed(mcfg) = &diffs[0] else {
+            panic!("{:?}", diffs[0]);
+        };
+        assert_eq!(mcfg.header.len(), 2);
+        assert_eq!(mcfg.header[0].name, "Table Length");
+        assert_eq!(mcfg.header[1].name, "Oem Revision");
+        assert_eq!(mcfg.header[1].old.as_deref(), Some("00000001"));
+        assert_eq!(mcfg.header[1].new.as_deref(), Some("00000002"));
+        assert_eq!(
+            mcfg.fields[0],
+            FieldChange {
+                offset: 0x2c,
+                name: "Base Address".to_string(),
+                old: Some("00000000E0000000".to_string()),
+                new: Some("00000000F0000000".to_string()),
+            }
+        );
+        assert!(mcfg.fields[1..].iter().all(|f| f.old.is_none()));
+        assert!(mcfg.ranges.is_empty());
+
+        let TableDiff::Changed(ssdt) = &diffs[1] else {
+            panic!("{:?}", diffs[1]);
+        };
+        assert_eq!(ssdt.header[0].name, "Table Length");
+        assert!(ssdt.fields.is_empty());
+        assert_eq!(ssdt.ranges, vec![37..39, 42..43]);
+
+        assert_eq!(
+            diffs[2],
+            TableDiff::Removed {
+                signature: "SSDT".to_string(),
+                instance: 2
+            }
+        );
+
+        let TableDiff::Changed(wsmt) = &diffs[3] else {
+            panic!("{:?}", diffs[3]);
+        };
+        assert!(wsmt.fields.is_empty());
+        assert_eq!(wsmt.ranges, vec![36..40]);
+        assert_eq!(
+            diffs[4],
+            TableDiff::Added {
+                signature: "HPET".to_string(),
+                instance: 1
+            }
+        );
+        assert_eq!(
+            diffs[0].to_string().lines().nth(2),
+            Some("    [018h] Oem Revision : 00000001 -> 00000002")
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use super::disasm::{Disassemble, Disassembler};
+use super::{
+    BootErrorRecord, BootGraphicsResource, CxlEarlyDiscovery, DebugPort2, ErrorInjection,
+    ErrorRecordSerialization, FirmwarePerformanceData, GenericTimerDescription,
+    HighPrecisionEventTimer, IoRemapping, LowPowerIdle, MemoryMappedConfiguration,
+    NvdimmFirmwareInterface, PlatformCommunicationsChannel, ProcessorPropertiesTopology,
+    RawAcpiData, SecureDevices, SerialPortConsoleRedirection, SystemManagementModeCommunication,
+    TrustedComputingPlatformAlliance, TrustedPlatformModule2, WindowsSmmSecurityMitigations,
+};
+use bytes::Bytes;
+
+// Any table, parsed by the typed parser matching its signature.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AcpiTable {
+    Bert(BootErrorRecord),
+    Bgrt(BootGraphicsResource),
+    Cedt(CxlEarlyDiscovery),
+    Dbg2(DebugPort2),
+    Einj(ErrorInjection),
+    Erst(ErrorRecordSerialization),
+    Fpdt(FirmwarePerformanceData),
+    Gtdt(GenericTimerDescription),
+    Hpet(HighPrecisionEventTimer),
+    Iort(IoRemapping),
+    Lpit(LowPowerIdle),
+    Mcfg(MemoryMappedConfiguration),
+    Nfit(NvdimmFirmwareInterface),
+    Pcct(PlatformCommunicationsChannel),
+    Pptt(ProcessorPropertiesTopology),
+    Sdev(SecureDevices),
+    Spcr(SerialPortConsoleRedirection),
+    Tcpa(TrustedComputingPlatformAlliance),
+    Tpm2(TrustedPlatformModule2),
+    Uefi(SystemManagementModeCommunication),
+    Wsmt(WindowsSmmSecurityMitigations),
+    Unknown(RawAcpiData),
+}
+
+impl AcpiTable {
+    pub fn is_typed(&self) -> bool {
+        !matches!(self, AcpiTable::Unknown(_))
+    }
+}
+
+impl From<RawAcpiData> for AcpiTable {
+    fn from(data: RawAcpiData) -> Self {
+        // Typed parsers read their fixed fields unchecked, so bodies too short for them stay raw.
+        let length = data.acpi_table_data.len();
+        match data.signature.as_str() {
+            "BERT" if length >= 12 => AcpiTable::Bert(BootErrorRecord::from(data)),
+            "BGRT" if length >= 20 => AcpiTable::Bgrt(BootGraphicsResource::from(data)),
+            "CEDT" => AcpiTable::Cedt(CxlEarlyDiscovery::from(data)),
+            "DBG2" if length >= 8 => AcpiTable::Dbg2(DebugPort2::from(data)),
+            "EINJ" if length >= 12 => AcpiTable::Einj(ErrorInjection::from(data)),
+            "ERST" if length >= 12 => AcpiTable::Erst(ErrorRecordSerialization::from(data)),
+            "FPDT" => AcpiTable::Fpdt(FirmwarePerformanceData::from(data)),
+            "GTDT" if length >= 60 => AcpiTable::Gtdt(GenericTimerDescription::from(data)),
+            "HPET" if length >= 20 => AcpiTable::Hpet(HighPrecisionEventTimer::from(data)),
+            "IORT" if length >= 12 => AcpiTable::Iort(IoRemapping::from(data)),
+            "LPIT" => AcpiTable::Lpit(LowPowerIdle::from(data)),
+            "MCFG" if length >= 8 => AcpiTable::Mcfg(MemoryMappedConfiguration::from(data)),
+            "NFIT" if length >= 4 => AcpiTable::Nfit(NvdimmFirmwareInterface::from(data)),
+            "PCCT" if length >= 12 => AcpiTable::Pcct(PlatformCommunicationsChannel::from(data)),
+            "PPTT" => AcpiTable::Pptt(ProcessorPropertiesTopology::from(data)),
+            "SDEV" => AcpiTable::Sdev(SecureDevices::from(data)),
+            "SPCR" if length >= 44 => AcpiTable::Spcr(SerialPortConsoleRedirection::from(data)),
+            "TCPA" => AcpiTable::Tcpa(TrustedComputingPlatformAlliance::from(data)),
+            "TPM2" if length >= 16 => AcpiTable::Tpm2(TrustedPlatformModule2::from(data)),
+            "UEFI" if length >= 18 => {
+                AcpiTable::Uefi(SystemManagementModeCommunication::from(data))
+            }
+            "WSMT" if length >= 4 => AcpiTable::Wsmt(WindowsSmmSecurityMitigations::from(data)),
+            _ => AcpiTable::Unknown(data),
+        }
+    }
+}
+
+impl From<AcpiTable> for Bytes {
+    fn from(val: AcpiTable) -> Self {
+        match val {
+            AcpiTable::Bert(v) => Bytes::from(v),
+            AcpiTable::Bgrt(v) => Bytes::from(v),
+            AcpiTable::Cedt(v) => Bytes::from(v),
+            AcpiTable::Dbg2(v) => Bytes::from(v),
+            AcpiTable::Einj(v) => Bytes::from(v),
+            AcpiTable::Erst(v) => Bytes::from(v),
+            AcpiTable::Fpdt(v) => Bytes::from(v),
+            AcpiTable::Gtdt(v) => Bytes::from(v),
+            AcpiTable::Hpet(v) => Bytes::from(v),
+            AcpiTable::Iort(v) => Bytes::from(v),
+            AcpiTable::Lpit(v) => Bytes::from(v),
+            AcpiTable::Mcfg(v) => Bytes::from(v),
+            AcpiTable::Nfit(v) => Bytes::from(v),
+            AcpiTable::Pcct(v) => Bytes::from(v),
+            AcpiTable::Pptt(v) => Bytes::from(v),
+            AcpiTable::Sdev(v) => Bytes::from(v),
+            AcpiTable::Spcr(v) => Bytes::from(v),
+            AcpiTable::Tcpa(v) => Bytes::from(v),
+            AcpiTable::Tpm2(v) => Bytes::from(v),
+            AcpiTable::Uefi(v) => Bytes::from(v),
+            AcpiTable::Wsmt(v) => Bytes::from(v),
+            AcpiTable::Unknown(v) => Bytes::from(v),
+        }
+    }
+}
+
+impl Disassemble for AcpiTable {
+    fn disassemble(&self, w: &mut Disassembler) {
+        match self {
+            AcpiTable::Bert(v) => v.disassemble(w),
+            AcpiTable::Bgrt(v) => v.disassemble(w),
+            AcpiTable::Cedt(v) => v.disassemble(w),
+            AcpiTable::Dbg2(v) => v.disassemble(w),
+            AcpiTable::Einj(v) => v.disassemble(w),
+            AcpiTable::Erst(v) => v.disassemble(w),
+            AcpiTable::Fpdt(v) => v.disassemble(w),
+            AcpiTable::Gtdt(v) => v.disassemble(w),
+            AcpiTable::Hpet(v) => v.disassemble(w),
+            AcpiTable::Iort(v) => v.disassemble(w),
+            AcpiTable::Lpit(v) => v.disassemble(w),
+            AcpiTable::Mcfg(v) => v.disassemble(w),
+            AcpiTable::Nfit(v) => v.disassemble(w),
+            AcpiTable::Pcct(v) => v.disassemble(w),
+            AcpiTable::Pptt(v) => v.disassemble(w),
+            AcpiTable::Sdev(v) => v.disassemble(w),
+            AcpiTable::Spcr(v) => v.disassemble(w),
+            AcpiTable::Tcpa(v) => v.disassemble(w),
+            AcpiTable::Tpm2(v) => v.disassemble(w),
+            AcpiTable::Uefi(v) => v.disassemble(w),
+            AcpiTable::Wsmt(v) => v.disassemble(w),
+            AcpiTable::Unknown(v) => v.disassemble(w),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn acpi_table() {
+        let data = RawAcpiData {
+            signature: "WSMT".to_string(),
+            length: 40,
+            acpi_table_data: Bytes::from_static(&[7, 0, 0, 0]),
+            ..Default::default()
+        };
+        let ret = AcpiTable::from(data.clone());
+        assert!(matches!(&ret, AcpiTable::Wsmt(v) if v.protection_flags == 7));
+        assert_eq!(Bytes::from(ret), Bytes::from(data.clone()));
+
+        let data = RawAcpiData {
+            length: 36,
+            acpi_table_data: Bytes::new(),
+            ..data
+        };
+        let ret = AcpiTable::from(data.clone());
+        assert_eq!(ret, AcpiTable::Unknown(data));
+        assert!(!ret.is_typed());
+    }
+}
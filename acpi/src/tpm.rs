@@ -0,0 +1,757 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::gas::GenericAddress;
+use super::{RawAcpiData, string_to_array};
+use alloc::format;
+use alloc::string::String;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://trustedcomputinggroup.org/resource/tcg-acpi-specification/
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrustedPlatformModule2 {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub platform_class: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 2],
+    pub control_area_address: u64,
+    pub start_method: StartMethod,
+    pub start_method_parameters: StartMethodParameters,
+    pub log_area_minimum_length: Option<u32>,
+    pub log_area_start_address: Option<u64>,
+}
+
+impl TrustedPlatformModule2 {
+    pub fn event_log(&self) -> Option<(u64, u64)> {
+        let address = self.log_area_start_address?;
+        let length = self.log_area_minimum_length?;
+        if address == 0 || length == 0 {
+            return None;
+        }
+
+        Some((address, length as u64))
+    }
+}
+
+impl From<RawAcpiData> for TrustedPlatformModule2 {
+    fn from(mut data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+        let platform_class = data.acpi_table_data.get_u16_le();
+        let reserved = data.acpi_table_data.split_to(2)[..].try_into().unwrap();
+        let control_area_address = data.acpi_table_data.get_u64_le();
+        let start_method = StartMethod::from(data.acpi_table_data.get_u32_le());
+
+        let log_area_length = 12;
+        let parameters_length = if data.acpi_table_data.len() >= 12 + log_area_length {
+            data.acpi_table_data.len() - log_area_length
+        } else {
+            data.acpi_table_data.len()
+        };
+        let parameters = data.acpi_table_data.split_to(parameters_length);
+        let start_method_parameters = StartMethodParameters::new(start_method, parameters);
+
+        let (log_area_minimum_length, log_area_start_address) =
+            if data.acpi_table_data.len() >= log_area_length {
+                let laml = data.acpi_table_data.get_u32_le();
+                let lasa = data.acpi_table_data.get_u64_le();
+                (Some(laml), Some(lasa))
+            } else {
+                (None, None)
+            };
+
+        TrustedPlatformModule2 {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            platform_class,
+            reserved,
+            control_area_address,
+            start_method,
+            start_method_parameters,
+            log_area_minimum_length,
+            log_area_start_address,
+        }
+    }
+}
+
+impl From<TrustedPlatformModule2> for Bytes {
+    fn from(val: TrustedPlatformModule2) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        b.put_u16_le(val.platform_class);
+        b.put_slice(&val.reserved);
+        b.put_u64_le(val.control_area_address);
+        b.put_u32_le(val.start_method.into());
+        b.put(Bytes::from(val.start_method_parameters));
+        if let Some(v) = val.log_area_minimum_length {
+            b.put_u32_le(v);
+        }
+        if let Some(v) = val.log_area_start_address {
+            b.put_u64_le(v);
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for TrustedPlatformModule2 {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Trusted Platform Module hardware interface Table");
+        w.u16("Platform Class", self.platform_class);
+        w.bytes("Reserved", &self.reserved);
+        w.u64("Control Address", self.control_area_address);
+        w.annotated(
+            "Start Method",
+            4,
+            u32::from(self.start_method) as u64,
+            &format!("{:?}", self.start_method),
+        );
+        self.start_method_parameters.disassemble(w);
+        if let Some(v) = self.log_area_minimum_length {
+            w.u32("Minimum Log Length", v);
+        }
+        if let Some(v) = self.log_area_start_address {
+            w.u64("Log Address", v);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StartMethod {
+    #[default]
+    NotAllowed,
+    AcpiStartMethod,
+    MemoryMappedFifo,
+    CommandResponseBuffer,
+    CommandResponseBufferWithAcpiStartMethod,
+    CommandResponseBufferWithArmSmc,
+    CommandResponseBufferWithPluton,
+    CommandResponseBufferWithArmFfa,
+    Reserved(u32),
+}
+
+impl StartMethod {
+    pub fn uses_command_response_buffer(&self) -> bool {
+        matches!(
+            self,
+            StartMethod::CommandResponseBuffer
+                | StartMethod::CommandResponseBufferWithAcpiStartMethod
+                | StartMethod::CommandResponseBufferWithArmSmc
+                | StartMethod::CommandResponseBufferWithPluton
+                | StartMethod::CommandResponseBufferWithArmFfa
+        )
+    }
+}
+
+impl From<u32> for StartMethod {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => StartMethod::NotAllowed,
+            2 => StartMethod::AcpiStartMethod,
+            6 => StartMethod::MemoryMappedFifo,
+            7 => StartMethod::CommandResponseBuffer,
+            8 => StartMethod::CommandResponseBufferWithAcpiStartMethod,
+            11 => StartMethod::CommandResponseBufferWithArmSmc,
+            13 => StartMethod::CommandResponseBufferWithPluton,
+            15 => StartMethod::CommandResponseBufferWithArmFfa,
+            v => StartMethod::Reserved(v),
+        }
+    }
+}
+
+impl From<StartMethod> for u32 {
+    fn from(val: StartMethod) -> Self {
+        match val {
+            StartMethod::NotAllowed => 0,
+            StartMethod::AcpiStartMethod => 2,
+            StartMethod::MemoryMappedFifo => 6,
+            StartMethod::CommandResponseBuffer => 7,
+            StartMethod::CommandResponseBufferWithAcpiStartMethod => 8,
+            StartMethod::CommandResponseBufferWithArmSmc => 11,
+            StartMethod::CommandResponseBufferWithPluton => 13,
+            StartMethod::CommandResponseBufferWithArmFfa => 15,
+            StartMethod::Reserved(v) => v,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StartMethodParameters {
+    ArmSmc {
+        interrupt: u32,
+        interrupt_flags: u8,
+        operation_flags: u8,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        reserved: [u8; 2],
+        function_id: u32,
+    },
+    Pluton {
+        start_address: u64,
+        reply_address: u64,
+    },
+    ArmFfa {
+        flags: u8,
+        attributes: u8,
+        partition_id: u16,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+        reserved: [u8; 8],
+    },
+    Other(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Bytes),
+}
+
+impl Default for StartMethodParameters {
+    fn default() -> Self {
+        StartMethodParameters::Other(Bytes::new())
+    }
+}
+
+impl StartMethodParameters {
+    pub fn new(start_method: StartMethod, mut buf: Bytes) -> Self {
+        match start_method {
+            StartMethod::CommandResponseBufferWithArmSmc if buf.len() == 12 => {
+                let interrupt = buf.get_u32_le();
+                let interrupt_flags = buf.get_u8();
+                let operation_flags = buf.get_u8();
+                let reserved = buf.split_to(2)[..].try_into().unwrap();
+                let function_id = buf.get_u32_le();
+                StartMethodParameters::ArmSmc {
+                    interrupt,
+                    interrupt_flags,
+                    operation_flags,
+                    reserved,
+                    function_id,
+                }
+            }
+            StartMethod::CommandResponseBufferWithPluton if buf.len() == 16 => {
+                let start_address = buf.get_u64_le();
+                let reply_address = buf.get_u64_le();
+                StartMethodParameters::Pluton {
+                    start_address,
+                    reply_address,
+                }
+            }
+            StartMethod::CommandResponseBufferWithArmFfa if buf.len() == 12 => {
+                let flags = buf.get_u8();
+                let attributes = buf.get_u8();
+                let partition_id = buf.get_u16_le();
+                let reserved = buf.split_to(8)[..].try_into().unwrap();
+                StartMethodParameters::ArmFfa {
+                    flags,
+                    attributes,
+                    partition_id,
+                    reserved,
+                }
+            }
+            _ => StartMethodParameters::Other(buf),
+        }
+    }
+}
+
+impl From<StartMethodParameters> for Bytes {
+    fn from(val: StartMethodParameters) -> Self {
+        let mut b = BytesMut::with_capacity(16);
+        match val {
+            StartMethodParameters::ArmSmc {
+                interrupt,
+                interrupt_flags,
+                operation_flags,
+                reserved,
+                function_id,
+            } => {
+                b.put_u32_le(interrupt);
+                b.put_u8(interrupt_flags);
+                b.put_u8(operation_flags);
+                b.put_slice(&reserved);
+                b.put_u32_le(function_id);
+            }
+            StartMethodParameters::Pluton {
+                start_address,
+                reply_address,
+            } => {
+                b.put_u64_le(start_address);
+                b.put_u64_le(reply_address);
+            }
+            StartMethodParameters::ArmFfa {
+                flags,
+                attributes,
+                partition_id,
+                reserved,
+            } => {
+                b.put_u8(flags);
+                b.put_u8(attributes);
+                b.put_u16_le(partition_id);
+                b.put_slice(&reserved);
+            }
+            StartMethodParameters::Other(v) => {
+                b.put(v);
+            }
+        }
+        b.freeze()
+    }
+}
+
+impl Disassemble for StartMethodParameters {
+    fn disassemble(&self, w: &mut Disassembler) {
+        match self {
+            StartMethodParameters::ArmSmc {
+                interrupt,
+                interrupt_flags,
+                operation_flags,
+                reserved,
+                function_id,
+            } => {
+                w.u32("Interrupt", *interrupt);
+                w.u8("Interrupt Flags", *interrupt_flags);
+                w.u8("Operation Flags", *operation_flags);
+                w.bytes("Reserved", reserved);
+                w.u32("Function ID", *function_id);
+            }
+            StartMethodParameters::Pluton {
+                start_address,
+                reply_address,
+            } => {
+                w.u64("Start Address", *start_address);
+                w.u64("Reply Address", *reply_address);
+            }
+            StartMethodParameters::ArmFfa {
+                flags,
+                attributes,
+                partition_id,
+                reserved,
+            } => {
+                w.u8("Flags", *flags);
+                w.u8("Attributes", *attributes);
+                w.u16("Partition ID", *partition_id);
+                w.bytes("Reserved", reserved);
+            }
+            StartMethodParameters::Other(v) => {
+                if !v.is_empty() {
+                    w.bytes("Method Parameters", v);
+                }
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrustedComputingPlatformAlliance {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub platform: TrustedComputingPlatform,
+}
+
+impl TrustedComputingPlatformAlliance {
+    pub fn event_log(&self) -> Option<(u64, u64)> {
+        let (address, length) = match &self.platform {
+            TrustedComputingPlatform::Client(v) => {
+                (v.log_area_start_address, v.log_area_minimum_length as u64)
+            }
+            TrustedComputingPlatform::Server(v) => {
+                (v.log_area_start_address, v.log_area_minimum_length)
+            }
+            TrustedComputingPlatform::Unknown(_) => return None,
+        };
+        if address == 0 || length == 0 {
+            return None;
+        }
+
+        Some((address, length))
+    }
+}
+
+impl From<RawAcpiData> for TrustedComputingPlatformAlliance {
+    fn from(data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+        let platform = TrustedComputingPlatform::from(data.acpi_table_data);
+
+        TrustedComputingPlatformAlliance {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            platform,
+        }
+    }
+}
+
+impl From<TrustedComputingPlatformAlliance> for Bytes {
+    fn from(val: TrustedComputingPlatformAlliance) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        b.put(Bytes::from(val.platform));
+        b.freeze()
+    }
+}
+
+impl Disassemble for TrustedComputingPlatformAlliance {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "Trusted Computing Platform Alliance table");
+        match &self.platform {
+            TrustedComputingPlatform::Client(v) => {
+                w.u16("Platform Class", v.platform_class);
+                w.u32("Min Event Log Length", v.log_area_minimum_length);
+                w.u64("Event Log Address", v.log_area_start_address);
+            }
+            TrustedComputingPlatform::Server(v) => v.disassemble(w),
+            TrustedComputingPlatform::Unknown(v) => w.bytes("Data", v),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TrustedComputingPlatform {
+    Client(TrustedComputingClient),
+    Server(TrustedComputingServer),
+    // Too short for even the client layout.
+    Unknown(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Bytes),
+}
+
+impl Default for TrustedComputingPlatform {
+    fn default() -> Self {
+        TrustedComputingPlatform::Client(TrustedComputingClient::default())
+    }
+}
+
+impl From<Bytes> for TrustedComputingPlatform {
+    fn from(buf: Bytes) -> Self {
+        if buf.len() < TrustedComputingClient::LENGTH {
+            return TrustedComputingPlatform::Unknown(buf);
+        }
+
+        let platform_class = u16::from_le_bytes([buf[0], buf[1]]);
+        if platform_class == 1 && buf.len() >= TrustedComputingServer::LENGTH {
+            TrustedComputingPlatform::Server(TrustedComputingServer::from(buf))
+        } else {
+            TrustedComputingPlatform::Client(TrustedComputingClient::from(buf))
+        }
+    }
+}
+
+impl From<TrustedComputingPlatform> for Bytes {
+    fn from(val: TrustedComputingPlatform) -> Self {
+        match val {
+            TrustedComputingPlatform::Client(v) => Bytes::from(v),
+            TrustedComputingPlatform::Server(v) => Bytes::from(v),
+            TrustedComputingPlatform::Unknown(v) => v,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrustedComputingClient {
+    pub platform_class: u16,
+    pub log_area_minimum_length: u32,
+    pub log_area_start_address: u64,
+}
+
+impl TrustedComputingClient {
+    const LENGTH: usize = 14;
+}
+
+impl From<Bytes> for TrustedComputingClient {
+    fn from(mut buf: Bytes) -> Self {
+        let platform_class = buf.get_u16_le();
+        let log_area_minimum_length = buf.get_u32_le();
+        let log_area_start_address = buf.get_u64_le();
+
+        TrustedComputingClient {
+            platform_class,
+            log_area_minimum_length,
+            log_area_start_address,
+        }
+    }
+}
+
+impl From<TrustedComputingClient> for Bytes {
+    fn from(val: TrustedComputingClient) -> Self {
+        let mut b = BytesMut::with_capacity(TrustedComputingClient::LENGTH);
+        b.put_u16_le(val.platform_class);
+        b.put_u32_le(val.log_area_minimum_length);
+        b.put_u64_le(val.log_area_start_address);
+        b.freeze()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrustedComputingServer {
+    pub platform_class: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved1: [u8; 2],
+    pub log_area_minimum_length: u64,
+    pub log_area_start_address: u64,
+    pub specification_revision: u16,
+    pub device_flags: u8,
+    pub interrupt_flags: u8,
+    pub gpe: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved2: [u8; 3],
+    pub global_system_interrupt: u32,
+    pub base_address: GenericAddress,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved3: [u8; 4],
+    pub configuration_address: GenericAddress,
+    pub pci_segment_group: u8,
+    pub pci_bus: u8,
+    pub pci_device: u8,
+    pub pci_function: u8,
+}
+
+impl TrustedComputingServer {
+    const LENGTH: usize = 64;
+}
+
+impl From<Bytes> for TrustedComputingServer {
+    fn from(mut buf: Bytes) -> Self {
+        let platform_class = buf.get_u16_le();
+        let reserved1 = buf.split_to(2)[..].try_into().unwrap();
+        let log_area_minimum_length = buf.get_u64_le();
+        let log_area_start_address = buf.get_u64_le();
+        let specification_revision = buf.get_u16_le();
+        let device_flags = buf.get_u8();
+        let interrupt_flags = buf.get_u8();
+        let gpe = buf.get_u8();
+        let reserved2 = buf.split_to(3)[..].try_into().unwrap();
+        let global_system_interrupt = buf.get_u32_le();
+        let base_address = GenericAddress::from(buf.split_to(12));
+        let reserved3 = buf.split_to(4)[..].try_into().unwrap();
+        let configuration_address = GenericAddress::from(buf.split_to(12));
+        let pci_segment_group = buf.get_u8();
+        let pci_bus = buf.get_u8();
+        let pci_device = buf.get_u8();
+        let pci_function = buf.get_u8();
+
+        TrustedComputingServer {
+            platform_class,
+            reserved1,
+            log_area_minimum_length,
+            log_area_start_address,
+            specification_revision,
+            device_flags,
+            interrupt_flags,
+            gpe,
+            reserved2,
+            global_system_interrupt,
+            base_address,
+            reserved3,
+            configuration_address,
+            pci_segment_group,
+            pci_bus,
+            pci_device,
+            pci_function,
+        }
+    }
+}
+
+impl From<TrustedComputingServer> for Bytes {
+    fn from(val: TrustedComputingServer) -> Self {
+        let mut b = BytesMut::with_capacity(TrustedComputingServer::LENGTH);
+        b.put_u16_le(val.platform_class);
+        b.put_slice(&val.reserved1);
+        b.put_u64_le(val.log_area_minimum_length);
+        b.put_u64_le(val.log_area_start_address);
+        b.put_u16_le(val.specification_revision);
+        b.put_u8(val.device_flags);
+        b.put_u8(val.interrupt_flags);
+        b.put_u8(val.gpe);
+        b.put_slice(&val.reserved2);
+        b.put_u32_le(val.global_system_interrupt);
+        b.put(Bytes::from(val.base_address));
+        b.put_slice(&val.reserved3);
+        b.put(Bytes::from(val.configuration_address));
+        b.put_u8(val.pci_segment_group);
+        b.put_u8(val.pci_bus);
+        b.put_u8(val.pci_device);
+        b.put_u8(val.pci_function);
+        b.freeze()
+    }
+}
+
+impl Disassemble for TrustedComputingServer {
+    fn disassemble(&self, w: &mut Disassembler) {
+        w.u16("Platform Class", self.platform_class);
+        w.bytes("Reserved", &self.reserved1);
+        w.u64("Min Event Log Length", self.log_area_minimum_length);
+        w.u64("Event Log Address", self.log_area_start_address);
+        w.u16("Specification Revision", self.specification_revision);
+        w.u8("Device Flags", self.device_flags);
+        w.u8("Interrupt Flags", self.interrupt_flags);
+        w.u8("Gpe Number", self.gpe);
+        w.bytes("Reserved", &self.reserved2);
+        w.u32("Global Interrupt", self.global_system_interrupt);
+        w.gas("Address", &self.base_address);
+        w.bytes("Reserved", &self.reserved3);
+        w.gas("Configuration Address", &self.configuration_address);
+        w.u8("Pci Segment", self.pci_segment_group);
+        w.u8("Pci Bus", self.pci_bus);
+        w.u8("Pci Device", self.pci_device);
+        w.u8("Pci Function", self.pci_function);
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AddressSpace;
+
+    #[test]
+    fn trusted_platform_module2() {
+        let data = TrustedPlatformModule2 {
+            signature: "TPM2".to_string(),
+            length: 76,
+            revision: 4,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            platform_class: 0,
+            reserved: [0; 2],
+            control_area_address: 0xfed40040,
+            start_method: StartMethod::CommandResponseBufferWithArmSmc,
+            start_method_parameters: StartMethodParameters::ArmSmc {
+                interrupt: 6,
+                interrupt_flags: 7,
+                operation_flags: 8,
+                reserved: [0; 2],
+                function_id: 0xc4000001,
+            },
+            log_area_minimum_length: Some(0x10000),
+            log_area_start_address: Some(0x7e000000),
+        };
+        let b = Bytes::from(data.clone());
+        let raw = RawAcpiData::from(b);
+        let ret = TrustedPlatformModule2::from(raw);
+        assert_eq!(data, ret);
+        assert_eq!(ret.event_log(), Some((0x7e000000, 0x10000)));
+        assert!(ret.start_method.uses_command_response_buffer());
+    }
+
+    #[test]
+    fn trusted_computing_platform_alliance() {
+        let data = TrustedComputingPlatformAlliance {
+            signature: "TCPA".to_string(),
+            length: 100,
+            revision: 2,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            platform: TrustedComputingPlatform::Server(TrustedComputingServer {
+                platform_class: 1,
+                log_area_minimum_length: 0x20000,
+                log_area_start_address: 0x7d000000,
+                specification_revision: 0x0102,
+                device_flags: 1,
+                base_address: GenericAddress {
+                    address_space_id: AddressSpace::SystemMemory,
+                    register_bit_width: 8,
+                    register_bit_offset: 0,
+                    access_size: 1,
+                    address: 0xfed40000,
+                },
+                ..Default::default()
+            }),
+        };
+        let b = Bytes::from(data.clone());
+        let raw = RawAcpiData::from(b);
+        let ret = TrustedComputingPlatformAlliance::from(raw);
+        assert_eq!(data, ret);
+        assert_eq!(ret.event_log(), Some((0x7d000000, 0x20000)));
+
+        let raw = RawAcpiData {
+            signature: "TCPA".to_string(),
+            length: 36,
+            ..Default::default()
+        };
+        let ret = TrustedComputingPlatformAlliance::from(raw);
+        assert_eq!(
+            ret.platform,
+            TrustedComputingPlatform::Unknown(Bytes::new())
+        );
+        assert_eq!(ret.event_log(), None);
+    }
+}
@@ -0,0 +1,480 @@
+use super::disasm::{Disassemble, Disassembler, header};
+use super::gas::GenericAddress;
+use super::guid::Guid;
+use super::{RawAcpiData, put_at, string_to_array};
+use alloc::string::String;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+// https://uefi.org/specs/UEFI/2.10/Apx_O_UEFI_ACPI_Data_Table.html
+// https://uefi.org/specs/PI/1.8/V4_SMM_Communication.html
+
+pub const SMM_COMMUNICATION: Guid = Guid::new(
+    0xc68ed8e2,
+    0x9dc6,
+    0x4cbd,
+    [0x9d, 0x94, 0xdb, 0x65, 0xac, 0xc5, 0xc3, 0x32],
+);
+// EDK II publishes the MM communication buffer regions under this identifier.
+pub const PI_SMM_COMMUNICATION_REGION: Guid = Guid::new(
+    0x4e28ca50,
+    0xd582,
+    0x44ac,
+    [0xa1, 0x1f, 0xe3, 0xd5, 0x65, 0x26, 0xdb, 0x34],
+);
+
+const HEADER_LENGTH: usize = 36 + 16 + 2;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UefiTableIdentifier {
+    SmmCommunication,
+    PiSmmCommunicationRegion,
+    Unknown(Guid),
+}
+
+impl UefiTableIdentifier {
+    pub fn name(&self) -> &'static str {
+        match self {
+            UefiTableIdentifier::SmmCommunication => "SMM Communication",
+            UefiTableIdentifier::PiSmmCommunicationRegion => "PI SMM Communication Region",
+            UefiTableIdentifier::Unknown(_) => "Unknown",
+        }
+    }
+}
+
+impl From<Guid> for UefiTableIdentifier {
+    fn from(value: Guid) -> Self {
+        match value {
+            SMM_COMMUNICATION => UefiTableIdentifier::SmmCommunication,
+            PI_SMM_COMMUNICATION_REGION => UefiTableIdentifier::PiSmmCommunicationRegion,
+            v => UefiTableIdentifier::Unknown(v),
+        }
+    }
+}
+
+impl From<UefiTableIdentifier> for Guid {
+    fn from(val: UefiTableIdentifier) -> Self {
+        match val {
+            UefiTableIdentifier::SmmCommunication => SMM_COMMUNICATION,
+            UefiTableIdentifier::PiSmmCommunicationRegion => PI_SMM_COMMUNICATION_REGION,
+            UefiTableIdentifier::Unknown(v) => v,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SystemManagementModeCommunication {
+    pub signature: String,
+    pub length: u32,
+    pub revision: u8,
+    pub checksum: u8,
+    pub oem_id: String,
+    pub oem_table_id: String,
+    pub oem_revision: u32,
+    pub creator_id: u32,
+    pub creator_revision: u32,
+    pub identifier: Guid,
+    pub data_offset: u16,
+    pub data: UefiTableData,
+}
+
+impl SystemManagementModeCommunication {
+    pub fn table_identifier(&self) -> UefiTableIdentifier {
+        UefiTableIdentifier::from(self.identifier)
+    }
+}
+
+impl From<RawAcpiData> for SystemManagementModeCommunication {
+    fn from(data: RawAcpiData) -> Self {
+        let signature = data.signature;
+        let length = data.length;
+        let revision = data.revision;
+        let checksum = data.checksum;
+        let oem_id = data.oem_id;
+        let oem_table_id = data.oem_table_id;
+        let oem_revision = data.oem_revision;
+        let creator_id = data.creator_id;
+        let creator_revision = data.creator_revision;
+
+        let mut body = data.acpi_table_data;
+        let identifier = Guid(body.split_to(16)[..].try_into().unwrap());
+        let data_offset = body.get_u16_le();
+
+        // data_offset counts from the start of the table.
+        let skip = (data_offset as usize)
+            .saturating_sub(HEADER_LENGTH)
+            .min(body.len());
+        body.advance(skip);
+        let data = UefiTableData::new(&identifier, body);
+
+        SystemManagementModeCommunication {
+            signature,
+            length,
+            revision,
+            checksum,
+            oem_id,
+            oem_table_id,
+            oem_revision,
+            creator_id,
+            creator_revision,
+            identifier,
+            data_offset,
+            data,
+        }
+    }
+}
+
+impl From<SystemManagementModeCommunication> for Bytes {
+    fn from(val: SystemManagementModeCommunication) -> Self {
+        let signature = string_to_array::<4>(&val.signature);
+        let oem_id = string_to_array::<6>(&val.oem_id);
+        let oem_table_id = string_to_array::<8>(&val.oem_table_id);
+
+        let mut b = BytesMut::with_capacity(val.length as usize);
+        b.put_slice(&signature);
+        b.put_u32_le(val.length);
+        b.put_u8(val.revision);
+        b.put_u8(val.checksum);
+        b.put_slice(&oem_id);
+        b.put_slice(&oem_table_id);
+        b.put_u32_le(val.oem_revision);
+        b.put_u32_le(val.creator_id);
+        b.put_u32_le(val.creator_revision);
+        b.put_slice(&val.identifier.0);
+        b.put_u16_le(val.data_offset);
+        let offset = (val.data_offset as usize).max(HEADER_LENGTH);
+        put_at(&mut b, offset, &Bytes::from(val.data));
+        b.freeze()
+    }
+}
+
+impl Disassemble for SystemManagementModeCommunication {
+    fn disassemble(&self, w: &mut Disassembler) {
+        header!(w, self, "UEFI Boot Optimization Table");
+        w.field(
+            "UUID Identifier",
+            16,
+            format_args!("{} [{}]", self.identifier, self.table_identifier().name()),
+        );
+        w.u16("Data Offset", self.data_offset);
+        w.seek(w.offset().max(self.data_offset as usize));
+        match &self.data {
+            UefiTableData::SmmCommunication(v) => {
+                w.u32("Software SMI Number", v.sw_smi_number);
+                w.u64("Buffer Pointer Address", v.buffer_ptr_address);
+                if let Some(register) = &v.invocation_register {
+                    w.gas("Invocation Register", register);
+                }
+            }
+            UefiTableData::PiSmmCommunicationRegion(v) => {
+                w.u32("Version", v.version);
+                w.u32("Number of Entries", v.number_of_entries);
+                w.u32("Descriptor Size", v.descriptor_size);
+                w.u32("Reserved", v.reserved);
+                let size = (v.descriptor_size as usize).max(MemoryDescriptor::LENGTH);
+                for entry in &v.entries {
+                    let start = w.offset();
+                    w.blank();
+                    w.u32("Memory Type", entry.memory_type);
+                    w.seek(start + 8);
+                    w.u64("Physical Start", entry.physical_start);
+                    w.u64("Virtual Start", entry.virtual_start);
+                    w.u64("Number of Pages", entry.number_of_pages);
+                    w.u64("Attribute", entry.attribute);
+                    w.seek(start + size);
+                }
+            }
+            UefiTableData::Unknown(v) => {
+                if !v.is_empty() {
+                    w.bytes("Data", v);
+                }
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UefiTableData {
+    SmmCommunication(SmmCommunication),
+    PiSmmCommunicationRegion(PiSmmCommunicationRegion),
+    Unknown(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Bytes),
+}
+
+impl Default for UefiTableData {
+    fn default() -> Self {
+        UefiTableData::Unknown(Bytes::new())
+    }
+}
+
+impl UefiTableData {
+    pub fn new(identifier: &Guid, data: Bytes) -> Self {
+        match *identifier {
+            SMM_COMMUNICATION if data.len() >= SmmCommunication::LENGTH => {
+                UefiTableData::SmmCommunication(SmmCommunication::from(data))
+            }
+            PI_SMM_COMMUNICATION_REGION if data.len() >= PiSmmCommunicationRegion::LENGTH => {
+                UefiTableData::PiSmmCommunicationRegion(PiSmmCommunicationRegion::from(data))
+            }
+            _ => UefiTableData::Unknown(data),
+        }
+    }
+}
+
+impl From<UefiTableData> for Bytes {
+    fn from(val: UefiTableData) -> Self {
+        match val {
+            UefiTableData::SmmCommunication(v) => Bytes::from(v),
+            UefiTableData::PiSmmCommunicationRegion(v) => Bytes::from(v),
+            UefiTableData::Unknown(v) => v,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmmCommunication {
+    pub sw_smi_number: u32,
+    pub buffer_ptr_address: u64,
+    // Only present in the version 2 layout.
+    pub invocation_register: Option<GenericAddress>,
+}
+
+impl SmmCommunication {
+    const LENGTH: usize = 12;
+}
+
+impl From<Bytes> for SmmCommunication {
+    fn from(mut buf: Bytes) -> Self {
+        let sw_smi_number = buf.get_u32_le();
+        let buffer_ptr_address = buf.get_u64_le();
+        let invocation_register = if buf.len() >= 12 {
+            Some(GenericAddress::from(buf.split_to(12)))
+        } else {
+            None
+        };
+
+        SmmCommunication {
+            sw_smi_number,
+            buffer_ptr_address,
+            invocation_register,
+        }
+    }
+}
+
+impl From<SmmCommunication> for Bytes {
+    fn from(val: SmmCommunication) -> Self {
+        let mut b = BytesMut::with_capacity(24);
+        b.put_u32_le(val.sw_smi_number);
+        b.put_u64_le(val.buffer_ptr_address);
+        if let Some(v) = val.invocation_register {
+            b.put(Bytes::from(v));
+        }
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PiSmmCommunicationRegion {
+    pub version: u32,
+    pub number_of_entries: u32,
+    pub descriptor_size: u32,
+    pub reserved: u32,
+    pub entries: Vec<MemoryDescriptor>,
+}
+
+impl PiSmmCommunicationRegion {
+    const LENGTH: usize = 16;
+}
+
+impl From<Bytes> for PiSmmCommunicationRegion {
+    fn from(mut buf: Bytes) -> Self {
+        let version = buf.get_u32_le();
+        let number_of_entries = buf.get_u32_le();
+        let descriptor_size = buf.get_u32_le();
+        let reserved = buf.get_u32_le();
+
+        let size = (descriptor_size as usize).max(MemoryDescriptor::LENGTH);
+        let entries = buf
+            .chunks_exact(size)
+            .take(number_of_entries as usize)
+            .map(|d| MemoryDescriptor::from(Bytes::copy_from_slice(d)))
+            .collect();
+
+        PiSmmCommunicationRegion {
+            version,
+            number_of_entries,
+            descriptor_size,
+            reserved,
+            entries,
+        }
+    }
+}
+
+impl From<PiSmmCommunicationRegion> for Bytes {
+    fn from(val: PiSmmCommunicationRegion) -> Self {
+        let size = (val.descriptor_size as usize).max(MemoryDescriptor::LENGTH);
+        let mut b =
+            BytesMut::with_capacity(PiSmmCommunicationRegion::LENGTH + size * val.entries.len());
+        b.put_u32_le(val.version);
+        b.put_u32_le(val.number_of_entries);
+        b.put_u32_le(val.descriptor_size);
+        b.put_u32_le(val.reserved);
+        for entry in val.entries {
+            let start = b.len();
+            b.put(Bytes::from(entry));
+            b.resize(start + size, 0);
+        }
+        b.freeze()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryDescriptor {
+    pub memory_type: u32,
+    pub physical_start: u64,
+    pub virtual_start: u64,
+    pub number_of_pages: u64,
+    pub attribute: u64,
+}
+
+impl MemoryDescriptor {
+    const LENGTH: usize = 40;
+}
+
+impl From<Bytes> for MemoryDescriptor {
+    fn from(mut buf: Bytes) -> Self {
+        let memory_type = buf.get_u32_le();
+        let _padding = buf.get_u32_le();
+        let physical_start = buf.get_u64_le();
+        let virtual_start = buf.get_u64_le();
+        let number_of_pages = buf.get_u64_le();
+        let attribute = buf.get_u64_le();
+
+        MemoryDescriptor {
+            memory_type,
+            physical_start,
+            virtual_start,
+            number_of_pages,
+            attribute,
+        }
+    }
+}
+
+impl From<MemoryDescriptor> for Bytes {
+    fn from(val: MemoryDescriptor) -> Self {
+        let mut b = BytesMut::with_capacity(MemoryDescriptor::LENGTH);
+        b.put_u32_le(val.memory_type);
+        b.put_u32_le(0);
+        b.put_u64_le(val.physical_start);
+        b.put_u64_le(val.virtual_start);
+        b.put_u64_le(val.number_of_pages);
+        b.put_u64_le(val.attribute);
+        b.freeze()
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AddressSpace;
+
+    #[test]
+    fn system_management_mode_communication() {
+        let data = SystemManagementModeCommunication {
+            signature: "UEFI".to_string(),
+            length: 78,
+            revision: 1,
+            checksum: 2,
+            oem_id: "EF".to_string(),
+            oem_table_id: "GHI".to_string(),
+            oem_revision: 3,
+            creator_id: 4,
+            creator_revision: 5,
+            identifier: SMM_COMMUNICATION,
+            data_offset: 54,
+            data: UefiTableData::SmmCommunication(SmmCommunication {
+                sw_smi_number: 7,
+                buffer_ptr_address: 0x1716151413121110,
+                invocation_register: Some(GenericAddress {
+                    address_space_id: AddressSpace::SystemIo,
+                    register_bit_width: 8,
+                    register_bit_offset: 0,
+                    access_size: 1,
+                    address: 0xb2,
+                }),
+            }),
+        };
+        let b = Bytes::from(data.clone());
+        assert_eq!(b.len(), data.length as usize);
+        let raw = RawAcpiData::from(b);
+        let ret = SystemManagementModeCommunication::from(raw);
+        assert_eq!(data, ret);
+        assert_eq!(ret.table_identifier().name(), "SMM Communication");
+    }
+
+    #[test]
+    fn uefi_table_data() {
+        let region = PiSmmCommunicationRegion {
+            version: 1,
+            number_of_entries: 1,
+            descriptor_size: 48,
+            reserved: 0,
+            entries: vec![MemoryDescriptor {
+                memory_type: 6,
+                physical_start: 0x7f00_0000,
+                virtual_start: 0,
+                number_of_pages: 16,
+                attribute: 0x8000_0000_0000_000f,
+            }],
+        };
+        let b = Bytes::from(region.clone());
+        assert_eq!(b.len(), 64);
+        assert_eq!(b[16..20], 6u32.to_le_bytes());
+        assert_eq!(b[24..32], 0x7f00_0000u64.to_le_bytes());
+        let ret = UefiTableData::new(&PI_SMM_COMMUNICATION_REGION, b.clone());
+        assert_eq!(ret, UefiTableData::PiSmmCommunicationRegion(region));
+
+        // Version 1 has no invocation register.
+        let smm = SmmCommunication {
+            sw_smi_number: 0xb2,
+            buffer_ptr_address: 0x7e00_0000,
+            invocation_register: None,
+        };
+        let v1 = Bytes::from(smm.clone());
+        assert_eq!(v1.len(), 12);
+        let ret = UefiTableData::new(&SMM_COMMUNICATION, v1);
+        assert_eq!(ret, UefiTableData::SmmCommunication(smm));
+
+        // Too short for the reserved field.
+        let short = Bytes::from_static(&[1, 0, 0, 0, 0, 0, 0, 0, 48, 0, 0, 0, 0]);
+        assert_eq!(
+            UefiTableData::new(&PI_SMM_COMMUNICATION_REGION, short.clone()),
+            UefiTableData::Unknown(short)
+        );
+
+        let unknown = Guid([1; 16]);
+        assert_eq!(
+            UefiTableIdentifier::from(unknown),
+            UefiTableIdentifier::Unknown(unknown)
+        );
+        assert_eq!(
+            UefiTableData::new(&unknown, b.clone()),
+            UefiTableData::Unknown(b)
+        );
+    }
+}
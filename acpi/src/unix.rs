@@ -1,10 +1,16 @@
-use super::RawAcpiData;
+use super::error::Error as AcpiError;
+use super::{BootTimeline, GenericErrorStatusBlock, PciConfigReader, RawAcpiData};
 use bytes::Bytes;
-use std::fs;
-use std::io::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Error, Read, Seek, SeekFrom, Write};
 use std::path::PathBuf;
 
 const BASE_PATH: &str = "/sys/firmware/acpi/tables";
+const FPDT_PATH: &str = "/sys/firmware/acpi/fpdt";
+const BGRT_IMAGE_PATH: &str = "/sys/firmware/acpi/bgrt/image";
+const PCI_DEVICES_PATH: &str = "/sys/bus/pci/devices";
+const HOSTNAME_PATH: &str = "/proc/sys/kernel/hostname";
+const BIOS_VERSION_PATH: &str = "/sys/class/dmi/id/bios_version";
 
 pub fn get_raw_table(name: &str) -> Result<RawAcpiData, Error> {
     let path = PathBuf::from(BASE_PATH).join(name);
@@ -12,6 +18,36 @@ pub fn get_raw_table(name: &str) -> Result<RawAcpiData, Error> {
     Ok(RawAcpiData::from(Bytes::from(table)))
 }
 
+pub fn get_boot_error_region() -> Result<GenericErrorStatusBlock, AcpiError> {
+    let path = PathBuf::from(BASE_PATH).join("data").join("BERT");
+    let region = fs::read(path)?;
+    GenericErrorStatusBlock::try_from(Bytes::from(region))
+}
+
+pub fn get_boot_graphics_image() -> Result<Bytes, Error> {
+    let image = fs::read(BGRT_IMAGE_PATH)?;
+    Ok(Bytes::from(image))
+}
+
+pub fn get_boot_timeline() -> Result<BootTimeline, Error> {
+    let boot = PathBuf::from(FPDT_PATH).join("boot");
+    let resume = PathBuf::from(FPDT_PATH).join("resume");
+    let suspend = PathBuf::from(FPDT_PATH).join("suspend");
+
+    Ok(BootTimeline {
+        reset_end: read_number(boot.join("firmware_start_ns"))?,
+        os_loader_load_image_start: read_number(boot.join("bootloader_load_ns"))?,
+        os_loader_start_image_start: read_number(boot.join("bootloader_launch_ns"))?,
+        exit_boot_services_entry: read_number(boot.join("exitbootservice_start_ns"))?,
+        exit_boot_services_exit: read_number(boot.join("exitbootservice_end_ns"))?,
+        resume_count: read_number(resume.join("resume_count"))?,
+        full_resume: read_number(resume.join("resume_prev_ns"))?,
+        average_resume: read_number(resume.join("resume_avg_ns"))?,
+        suspend_start: read_number(suspend.join("suspend_start_ns"))?,
+        suspend_end: read_number(suspend.join("suspend_end_ns"))?,
+    })
+}
+
 pub fn table_types() -> Result<Vec<String>, Error> {
     let mut tables = vec![];
     for entry in fs::read_dir(BASE_PATH)? {
@@ -32,3 +68,69 @@ pub fn table_types() -> Result<Vec<String>, Error> {
 
     Ok(tables)
 }
+
+pub(crate) fn get_hostname() -> String {
+    read_text(HOSTNAME_PATH)
+}
+
+pub(crate) fn get_firmware_version() -> String {
+    read_text(BIOS_VERSION_PATH)
+}
+
+// Reads configuration space through the kernel instead of mapping ECAM windows directly.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SysfsPciConfig;
+
+impl PciConfigReader for SysfsPciConfig {
+    fn read_config(
+        &mut self,
+        segment: u16,
+        bus: u8,
+        device: u8,
+        function: u8,
+        offset: u16,
+        buf: &mut [u8],
+    ) -> Result<(), AcpiError> {
+        let name = format!("{segment:04x}:{bus:02x}:{device:02x}.{function:x}");
+        let path = PathBuf::from(PCI_DEVICES_PATH).join(name).join("config");
+        let mut config = File::open(path)?;
+        config.seek(SeekFrom::Start(offset as u64))?;
+        config.read_exact(buf)?;
+        Ok(())
+    }
+
+    fn write_config(
+        &mut self,
+        segment: u16,
+        bus: u8,
+        device: u8,
+        function: u8,
+        offset: u16,
+        buf: &[u8],
+    ) -> Result<(), AcpiError> {
+        let name = format!("{segment:04x}:{bus:02x}:{device:02x}.{function:x}");
+        let path = PathBuf::from(PCI_DEVICES_PATH).join(name).join("config");
+        let mut config = OpenOptions::new().write(true).open(path)?;
+        config.seek(SeekFrom::Start(offset as u64))?;
+        config.write_all(buf)?;
+        Ok(())
+    }
+}
+
+fn read_number<T>(path: PathBuf) -> Result<Option<T>, Error>
+where
+    T: std::str::FromStr,
+{
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let value = fs::read_to_string(path)?;
+    Ok(value.trim().parse::<T>().ok())
+}
+
+fn read_text(path: &str) -> String {
+    fs::read_to_string(path)
+        .map(|v| v.trim().to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,425 @@
+use super::error::Error;
+use super::{CedtStructure, MemoryMappedConfigurationSpace, RawAcpiData};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use bytes::Bytes;
+use core::slice::ChunksExact;
+
+// Borrowed counterparts of the owned tables. Bounds are checked once when the view is created, so
+// accessors read straight out of the slice and subtables are only decoded while iterating.
+
+const HEADER_LENGTH: usize = 36;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RawTableRef<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> RawTableRef<'a> {
+    pub fn signature(&self) -> Cow<'a, str> {
+        trimmed(&self.data[0..4])
+    }
+
+    pub fn length(&self) -> u32 {
+        u32::from_le_bytes(self.data[4..8].try_into().unwrap())
+    }
+
+    pub fn revision(&self) -> u8 {
+        self.data[8]
+    }
+
+    pub fn checksum(&self) -> u8 {
+        self.data[9]
+    }
+
+    pub fn oem_id(&self) -> Cow<'a, str> {
+        trimmed(&self.data[10..16])
+    }
+
+    pub fn oem_table_id(&self) -> Cow<'a, str> {
+        trimmed(&self.data[16..24])
+    }
+
+    pub fn oem_revision(&self) -> u32 {
+        u32::from_le_bytes(self.data[24..28].try_into().unwrap())
+    }
+
+    pub fn creator_id(&self) -> u32 {
+        u32::from_le_bytes(self.data[28..32].try_into().unwrap())
+    }
+
+    pub fn creator_revision(&self) -> u32 {
+        u32::from_le_bytes(self.data[32..36].try_into().unwrap())
+    }
+
+    // Everything after the header.
+    pub fn acpi_table_data(&self) -> &'a [u8] {
+        &self.data[HEADER_LENGTH..]
+    }
+
+    // The whole table, header included, cut to its `length`.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn is_checksum_valid(&self) -> bool {
+        self.data.iter().fold(0u8, |sum, b| sum.wrapping_add(*b)) == 0
+    }
+}
+
+// Trailing bytes past `length` are ignored, so a view can be taken over a larger buffer.
+impl<'a> TryFrom<&'a [u8]> for RawTableRef<'a> {
+    type Error = Error;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() < HEADER_LENGTH {
+            return Err(Error::InvalidTable);
+        }
+
+        let length = u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize;
+        if length < HEADER_LENGTH || length > data.len() {
+            return Err(Error::InvalidTable);
+        }
+
+        Ok(RawTableRef {
+            data: &data[..length],
+        })
+    }
+}
+
+impl From<RawTableRef<'_>> for RawAcpiData {
+    fn from(val: RawTableRef<'_>) -> Self {
+        RawAcpiData::from(Bytes::copy_from_slice(val.data))
+    }
+}
+
+// Matches `extract_string`, but only allocates when the bytes are not valid UTF-8.
+fn trimmed(value: &[u8]) -> Cow<'_, str> {
+    let mut v = value;
+    while let Some(b) = v.strip_suffix(&[0]) {
+        v = b;
+    }
+    String::from_utf8_lossy(v)
+}
+
+fn check_signature(table: &RawTableRef<'_>, signature: &str) -> Result<(), Error> {
+    if table.data[0..4] != *signature.as_bytes() {
+        return Err(Error::InvalidTable);
+    }
+
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct McfgRef<'a> {
+    table: RawTableRef<'a>,
+}
+
+impl<'a> McfgRef<'a> {
+    pub fn table(&self) -> RawTableRef<'a> {
+        self.table
+    }
+
+    pub fn reserved(&self) -> &'a [u8; 8] {
+        self.table.acpi_table_data()[..8].try_into().unwrap()
+    }
+
+    pub fn spaces(&self) -> McfgSpaces<'a> {
+        McfgSpaces {
+            chunks: self.table.acpi_table_data()[8..].chunks_exact(16),
+        }
+    }
+
+    pub fn space(&self, segment: u16, bus: u8) -> Option<MemoryMappedConfigurationSpace> {
+        self.spaces().find(|s| s.contains(segment, bus))
+    }
+
+    pub fn ecam_address(
+        &self,
+        segment: u16,
+        bus: u8,
+        device: u8,
+        function: u8,
+        offset: u16,
+    ) -> Option<u64> {
+        self.space(segment, bus)?
+            .ecam_address(bus, device, function, offset)
+    }
+}
+
+impl<'a> TryFrom<RawTableRef<'a>> for McfgRef<'a> {
+    type Error = Error;
+
+    fn try_from(table: RawTableRef<'a>) -> Result<Self, Self::Error> {
+        check_signature(&table, "MCFG")?;
+        if table.acpi_table_data().len() < 8 {
+            return Err(Error::InvalidTable);
+        }
+
+        Ok(McfgRef { table })
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for McfgRef<'a> {
+    type Error = Error;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        McfgRef::try_from(RawTableRef::try_from(data)?)
+    }
+}
+
+// Decodes one 16-byte allocation entry per step; a trailing partial entry is skipped.
+#[derive(Clone, Debug)]
+pub struct McfgSpaces<'a> {
+    chunks: ChunksExact<'a, u8>,
+}
+
+impl Iterator for McfgSpaces<'_> {
+    type Item = MemoryMappedConfigurationSpace;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(MemoryMappedConfigurationSpace::from)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl ExactSizeIterator for McfgSpaces<'_> {}
+
+// -----------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CedtRef<'a> {
+    table: RawTableRef<'a>,
+}
+
+impl<'a> CedtRef<'a> {
+    pub fn table(&self) -> RawTableRef<'a> {
+        self.table
+    }
+
+    pub fn structures(&self) -> CedtStructures<'a> {
+        CedtStructures {
+            data: self.table.acpi_table_data(),
+        }
+    }
+}
+
+impl<'a> TryFrom<RawTableRef<'a>> for CedtRef<'a> {
+    type Error = Error;
+
+    fn try_from(table: RawTableRef<'a>) -> Result<Self, Self::Error> {
+        check_signature(&table, "CEDT")?;
+        Ok(CedtRef { table })
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for CedtRef<'a> {
+    type Error = Error;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        CedtRef::try_from(RawTableRef::try_from(data)?)
+    }
+}
+
+// Stops at the first structure whose length is too short or runs past the table, as the owned
+// parser does.
+#[derive(Clone, Debug)]
+pub struct CedtStructures<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for CedtStructures<'a> {
+    type Item = CedtStructureRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.len() < 4 {
+            return None;
+        }
+
+        let length = u16::from_le_bytes([self.data[2], self.data[3]]) as usize;
+        if length < 4 || self.data.len() < length {
+            self.data = &[];
+            return None;
+        }
+
+        let (structure, rest) = self.data.split_at(length);
+        self.data = rest;
+        Some(CedtStructureRef { data: structure })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CedtStructureRef<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> CedtStructureRef<'a> {
+    pub fn structure_type(&self) -> u8 {
+        self.data[0]
+    }
+
+    pub fn length(&self) -> u16 {
+        u16::from_le_bytes([self.data[2], self.data[3]])
+    }
+
+    // The structure without its 4-byte type and length header.
+    pub fn body(&self) -> &'a [u8] {
+        &self.data[4..]
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+impl From<CedtStructureRef<'_>> for CedtStructure {
+    fn from(val: CedtStructureRef<'_>) -> Self {
+        CedtStructure::from(Bytes::copy_from_slice(val.data))
+    }
+}
+
+// -----------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CxlEarlyDiscovery, MemoryMappedConfiguration, MemoryMappedConfigurationSpace};
+    use alloc::vec::Vec;
+    use bytes::BufMut;
+
+    #[test]
+    fn raw_table_ref() {
+        let owned = RawAcpiData {
+            signature: "ABCD".to_string(),
+            length: 39,
+            revision: 1,
+            checksum: 0,
+            oem_id: "OEMID".to_string(),
+            oem_table_id: "TABLE".to_string(),
+            oem_revision: 2,
+            creator_id: 3,
+            creator_revision: 4,
+            acpi_table_data: Bytes::from_static(b"JKL"),
+        };
+        let mut data = Vec::from(Bytes::from(owned.clone()));
+        data.extend_from_slice(b"trailing");
+
+        let ret = RawTableRef::try_from(&data[..]).unwrap();
+        assert!(matches!(ret.signature(), Cow::Borrowed("ABCD")));
+        assert_eq!(ret.length(), 39);
+        assert_eq!(ret.revision(), 1);
+        assert_eq!(ret.oem_id(), "OEMID");
+        assert_eq!(ret.oem_table_id(), "TABLE");
+        assert_eq!(ret.oem_revision(), 2);
+        assert_eq!(ret.creator_id(), 3);
+        assert_eq!(ret.creator_revision(), 4);
+        assert_eq!(ret.acpi_table_data(), b"JKL");
+        assert_eq!(ret.as_bytes().len(), 39);
+        assert_eq!(ret.is_checksum_valid(), owned.is_checksum_valid());
+        assert_eq!(RawAcpiData::from(ret), owned);
+
+        assert!(matches!(
+            RawTableRef::try_from(&data[..35]),
+            Err(Error::InvalidTable)
+        ));
+        assert!(matches!(
+            RawTableRef::try_from(&data[..38]),
+            Err(Error::InvalidTable)
+        ));
+        data[4] = 35;
+        assert!(matches!(
+            RawTableRef::try_from(&data[..]),
+            Err(Error::InvalidTable)
+        ));
+    }
+
+    #[test]
+    fn mcfg_ref() {
+        let owned = MemoryMappedConfiguration {
+            signature: "MCFG".to_string(),
+            length: 44 + 16 * 2,
+            revision: 1,
+            oem_id: "OEMID".to_string(),
+            oem_table_id: "TABLE".to_string(),
+            spaces: vec![
+                MemoryMappedConfigurationSpace {
+                    base_address: 0xe000_0000,
+                    segment_number: 0,
+                    bus_number_start: 0,
+                    bus_number_end: 0x3f,
+                    reserved: [0; 4],
+                },
+                MemoryMappedConfigurationSpace {
+                    base_address: 0xf000_0000,
+                    segment_number: 0,
+                    bus_number_start: 0,
+                    bus_number_end: 0xff,
+                    reserved: [0; 4],
+                },
+            ],
+            ..Default::default()
+        };
+        let data = Vec::from(Bytes::from(owned.clone()));
+
+        let ret = McfgRef::try_from(&data[..]).unwrap();
+        assert_eq!(ret.reserved(), &[0; 8]);
+        assert_eq!(ret.spaces().len(), 2);
+        assert_eq!(ret.spaces().collect::<Vec<_>>(), owned.spaces);
+        assert_eq!(ret.ecam_address(0, 1, 2, 3, 4), Some(0xe011_3004));
+        assert_eq!(ret.space(1, 0), None);
+
+        let mut data = data;
+        data[3] = b'X';
+        assert!(matches!(
+            McfgRef::try_from(&data[..]),
+            Err(Error::InvalidTable)
+        ));
+
+        let data = Bytes::from(RawAcpiData {
+            signature: "MCFG".to_string(),
+            length: 40,
+            acpi_table_data: Bytes::from_static(&[0; 4]),
+            ..Default::default()
+        });
+        assert!(matches!(
+            McfgRef::try_from(&data[..]),
+            Err(Error::InvalidTable)
+        ));
+    }
+
+    #[test]
+    fn cedt_ref() {
+        let mut body = vec![];
+        body.put_slice(&[2, 0, 8, 0, 1, 2, 3, 4]);
+        body.put_slice(&[9, 0, 6, 0, 5, 6]);
+        // Truncated: claims more bytes than remain.
+        body.put_slice(&[0, 0, 32, 0]);
+        let data = Vec::from(Bytes::from(RawAcpiData {
+            signature: "CEDT".to_string(),
+            length: 36 + 18,
+            revision: 1,
+            acpi_table_data: Bytes::from(body),
+            ..Default::default()
+        }));
+        let owned = CxlEarlyDiscovery::from(RawAcpiData::from(Bytes::from(data.clone())));
+
+        let ret = CedtRef::try_from(&data[..]).unwrap();
+        let structures = ret.structures().collect::<Vec<_>>();
+        assert_eq!(structures.len(), 2);
+        assert_eq!(structures[0].structure_type(), 2);
+        assert_eq!(structures[0].length(), 8);
+        assert_eq!(structures[1].body(), &[5, 6]);
+        assert_eq!(
+            structures
+                .into_iter()
+                .map(CedtStructure::from)
+                .collect::<Vec<_>>(),
+            owned.structures
+        );
+    }
+}
@@ -19,6 +19,15 @@ pub fn table_types() -> Result<Vec<String>, Error> {
     enum_system_firmware_table(FIRMWARE_TABLE_ACPI)
 }
 
+pub(crate) fn get_hostname() -> String {
+    std::env::var("COMPUTERNAME").unwrap_or_default()
+}
+
+// Not exposed without reading SMBIOS, which this crate does not parse.
+pub(crate) fn get_firmware_version() -> String {
+    String::new()
+}
+
 fn enum_system_firmware_table(signature: u32) -> Result<Vec<String>, Error> {
     // https://docs.microsoft.com/en-us/windows/win32/api/sysinfoapi/nf-sysinfoapi-enumsystemfirmwaretables
 